@@ -1,14 +1,20 @@
+use std::path::PathBuf;
+
 #[cfg(test)]
 use crossterm::event::KeyEvent;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     compid::CompID,
+    config::radioconfig::RadioSource,
+    datastore::PlayRecord,
     osclient::{
         response::{getplaylist::Media, getplaylists::SimplePlaylist},
-        types::MediaID,
+        types::{MediaID, PlaylistID},
+    },
+    playerworker::player::{
+        FromPlayerWorker, PlayOrder, QueueLocation, RepeatMode, ToPlayerWorker,
     },
-    playerworker::player::{FromPlayerWorker, QueueLocation, ToPlayerWorker},
     queryworker::query::{QueryStatus, ToQueryWorker},
 };
 
@@ -29,6 +35,25 @@ impl ToString for Mode {
     }
 }
 
+/// Changes to a playlist that need an input or a confirmation from the user
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PlaylistEdit {
+    Create,
+    Rename(SimplePlaylist),
+    SetComment(SimplePlaylist),
+    Delete(SimplePlaylist),
+    /// Save the media as a new playlist
+    CreateWith(Vec<MediaID>),
+    /// Read the playlist file at the path the user enters, which starts with the given directory
+    Import(PathBuf),
+    /// Replace everything in the playlist with the media
+    Replace {
+        id: PlaylistID,
+        name: String,
+        items: Vec<MediaID>,
+    },
+}
+
 /// These actions are all related to modifying the queue in one way or another.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum QueueAction {
@@ -78,6 +103,17 @@ pub enum TargetedAction {
     Skip,
     Previous,
     GoToStart,
+    ToggleCrossfade,
+    /// Switch between playing only the cached media and playing everything
+    ToggleOffline,
+    /// Switch to the next streaming profile in the config
+    CycleStreamProfile,
+    /// Switch to the next repeat mode (Off -> All -> One)
+    CycleRepeat,
+    SetRepeat(RepeatMode),
+    /// Switch to the next play order (Normal -> Random -> Reverse)
+    CyclePlayOrder,
+    SetPlayOrder(PlayOrder),
     ChangeVolume(f32),
     ChangeSpeed(f32),
     SetVolume(f32),
@@ -95,6 +131,10 @@ pub enum TargetedAction {
 
     TapToBPM,
     FocusPlaylistList,
+    FocusAlbumList,
+    FocusArtistList,
+    FocusHistory,
+    FocusStarred,
     FocusPlaylistQueue,
     FocusPlayQueue,
     OpenTasks,
@@ -127,6 +167,20 @@ pub enum TargetedAction {
     OpenSearch,
     /// Reset search
     ClearSearch,
+
+    /// Open a popup that searches artists, albums and songs on the server. Unlike OpenSearch, this
+    /// is not limited to the items that are already loaded.
+    OpenGlobalSearch,
+    /// Replace the current queue with the one saved on the server by any client
+    ResumeRemoteQueue,
+    /// Open a popup that adds random media to the queue, optionally limited to a genre, a range
+    /// of years or a music folder
+    OpenRandomMix,
+    /// Start or stop adding media to the queue automatically before it runs out
+    ToggleRadio,
+    /// Switch to the next place the radio finds media from
+    CycleRadioSource,
+    SetRadioSource(RadioSource),
     /// Applies search with the given string. Anything that contains this string is highlighted.
     ApplySearch(String),
 
@@ -145,10 +199,30 @@ pub enum TargetedAction {
 
     ViewPlaylistInfo(SimplePlaylist),
     ViewMediaInfo(Media),
+    /// Opens a popup that asks for the new name, comment, or a confirmation
+    EditPlaylist(PlaylistEdit),
+    /// Changes the name, comment or visibility of the given playlist in a way that can be undone.
+    /// Fields that are not set are left as they are.
+    SetPlaylistInfo {
+        playlist: SimplePlaylist,
+        name: Option<String>,
+        comment: Option<String>,
+        public: Option<bool>,
+    },
 
     PrepareAddToPlaylist(Vec<MediaID>),
+    /// Opens a popup that saves the media as a new playlist, or over an existing playlist
+    PrepareSaveAsPlaylist(Vec<MediaID>),
 
     AddCurrentItemToPlaylist,
+
+    /// Sent by the play queue whenever the repeat mode or the play order changes
+    PlayModeChanged {
+        repeat: RepeatMode,
+        order: PlayOrder,
+    },
+    /// A media has stopped playing, and should be added to the top of the history list
+    RecordPlay(PlayRecord),
 }
 
 impl ToString for TargetedAction {
@@ -162,6 +236,19 @@ impl ToString for TargetedAction {
             TargetedAction::Previous => "Skip to previous music".to_string(),
             TargetedAction::Queue(q) => q.to_string(),
             TargetedAction::GoToStart => "Rewind to start".to_string(),
+            TargetedAction::ToggleCrossfade => "Toggle crossfade".to_string(),
+            TargetedAction::ToggleOffline => "Toggle offline mode".to_string(),
+            TargetedAction::CycleStreamProfile => {
+                "Switch to the next streaming profile".to_string()
+            }
+            TargetedAction::CycleRepeat => "Cycle through repeat modes".to_string(),
+            TargetedAction::SetRepeat(r) => format!("Set repeat mode to {}", r),
+            TargetedAction::CyclePlayOrder => "Cycle through play orders".to_string(),
+            TargetedAction::SetPlayOrder(o) => format!("Set play order to {}", o),
+            TargetedAction::PlayModeChanged { .. } => "Update the play mode indicator".to_string(),
+            TargetedAction::RecordPlay(record) => {
+                format!("Add {} to the history", record.media.title)
+            }
             TargetedAction::ChangeVolume(v) => {
                 if *v >= 0.0 {
                     format!("Increase volume by {}", v)
@@ -175,6 +262,10 @@ impl ToString for TargetedAction {
             TargetedAction::WindowRight => "Focus window on the right".to_string(),
             TargetedAction::TapToBPM => "Tap to BPM".to_string(),
             TargetedAction::FocusPlaylistList => "Focus playlist list".to_string(),
+            TargetedAction::FocusAlbumList => "Focus album list".to_string(),
+            TargetedAction::FocusArtistList => "Focus artist list".to_string(),
+            TargetedAction::FocusHistory => "Focus play history".to_string(),
+            TargetedAction::FocusStarred => "Focus starred items".to_string(),
             TargetedAction::FocusPlaylistQueue => "Focus playlist queue".to_string(),
             TargetedAction::FocusPlayQueue => "Focus play queue".to_string(),
             TargetedAction::OpenTasks => "Open tasks view".to_string(),
@@ -186,6 +277,10 @@ impl ToString for TargetedAction {
             TargetedAction::Resume => "Resume program".to_string(),
             TargetedAction::ClearScreen => "Re-render".to_string(),
             TargetedAction::Quit => "Quit program".to_string(),
+            TargetedAction::ResumeRemoteQueue => "Resume the queue saved on the server".to_string(),
+            TargetedAction::ToggleRadio => "Toggle radio mode".to_string(),
+            TargetedAction::CycleRadioSource => "Switch to the next radio source".to_string(),
+            TargetedAction::SetRadioSource(source) => format!("Set radio source to {}", source),
             TargetedAction::ChangeSpeed(s) => {
                 if *s >= 0.0 {
                     format!("Increase playback speed by {}", s)
@@ -209,6 +304,26 @@ impl ToString for TargetedAction {
             TargetedAction::Shuffle => "Shuffle music".to_string(),
             TargetedAction::ViewPlaylistInfo(_) => "View playlist's information".to_string(),
             TargetedAction::ViewMediaInfo(_) => "View media's information".to_string(),
+            TargetedAction::EditPlaylist(edit) => match edit {
+                PlaylistEdit::Create => "Create a playlist".to_string(),
+                PlaylistEdit::Rename(p) => format!("Rename playlist {}", p.name),
+                PlaylistEdit::SetComment(p) => format!("Change comment of playlist {}", p.name),
+                PlaylistEdit::Delete(p) => format!("Delete playlist {}", p.name),
+                PlaylistEdit::Import(_) => "Import a playlist from a file".to_string(),
+                PlaylistEdit::CreateWith(items) => {
+                    format!("Save {} items as a new playlist", items.len())
+                }
+                PlaylistEdit::Replace { name, items, .. } => {
+                    format!("Replace playlist {} with {} items", name, items.len())
+                }
+            },
+            TargetedAction::SetPlaylistInfo { playlist, .. } => {
+                format!("Update playlist {}", playlist.name)
+            }
+            TargetedAction::PrepareSaveAsPlaylist(_) => {
+                "Request user to select the playlist the selected items will be saved as"
+                    .to_string()
+            }
             TargetedAction::PrepareAddToPlaylist(_) => {
                 "Request user to select the playlist in which the selected items will go"
                     .to_string()
@@ -222,6 +337,8 @@ impl ToString for TargetedAction {
             TargetedAction::ClearFilter => "Remove filter".to_string(),
             TargetedAction::OpenSearch => "Search for a specific keyword".to_string(),
             TargetedAction::ClearSearch => "Clear the current search".to_string(),
+            TargetedAction::OpenGlobalSearch => "Search the entire server".to_string(),
+            TargetedAction::OpenRandomMix => "Add random media to the queue".to_string(),
             TargetedAction::ApplyFilter(f) => format!("Filter content by keyword '{}'", f),
             TargetedAction::SearchUpdate(s, _) => format!("Search content by keyword '{}'", s),
             TargetedAction::CloseFilter => "Close filter dialog".to_string(),
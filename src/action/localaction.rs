@@ -1,7 +1,12 @@
+use std::fmt::Display;
+
 use serde::Deserialize;
 use strum::Display;
 
-use crate::{osclient::types::PlaylistID, playerworker::player::QueueLocation};
+use crate::{
+    osclient::types::{AlbumListType, PlaylistID},
+    playerworker::player::QueueLocation,
+};
 
 /// Actions for all of the visual lists (lists where you can select multiple items). Not applicable
 /// for lists where you cannot select items.
@@ -62,6 +67,8 @@ pub enum PlayQueueAction {
     Delete,
     /// Star or unstar selected elements
     ToggleStar,
+    /// Rate selected elements from 1 to 5, or remove the rating with 0
+    SetRating(u8),
     /// Jump to a specific location in the play queue
     PlaySelected,
     /// Move the cursor to the currently playing item
@@ -72,6 +79,27 @@ pub enum PlayQueueAction {
     ViewInfo,
     /// Add selected items to a playlist
     AddToPlaylist,
+    /// Save selected items to the download directory
+    Download,
+    /// Move selected items up by one
+    MoveUp,
+    /// Move selected items down by one
+    MoveDown,
+    /// Move selected items so that they are played right after the current item
+    MoveNext,
+    /// Move selected items to the end of the queue
+    MoveToEnd,
+    /// Save everything in the queue as a new playlist, or over an existing playlist
+    SaveAsPlaylist,
+    /// Save the items after the one currently being played as a new playlist, or over an existing
+    /// playlist
+    SaveRemainingAsPlaylist,
+    /// Save everything in the queue as a playlist file
+    Export,
+    /// Revert the last change made to the queue
+    Undo,
+    /// Apply the last change that has been reverted again
+    Redo,
 }
 
 impl ToString for PlayQueueAction {
@@ -79,11 +107,23 @@ impl ToString for PlayQueueAction {
         match self {
             PlayQueueAction::Delete => "Delete items from queue",
             PlayQueueAction::ToggleStar => "Star/unstar items",
+            PlayQueueAction::SetRating(0) => "Remove the rating of items",
+            PlayQueueAction::SetRating(rating) => return format!("Rate items {}", rating),
             PlayQueueAction::PlaySelected => "Jump to the cursor's position",
             PlayQueueAction::FocusPlaying => "Focus currently playing item",
             PlayQueueAction::Randomise => "Randomise queued items",
             PlayQueueAction::ViewInfo => "Display metadata of the highlighted song",
             PlayQueueAction::AddToPlaylist => "Add items to a playlist",
+            PlayQueueAction::Download => "Download selected items",
+            PlayQueueAction::MoveUp => "Move items up",
+            PlayQueueAction::MoveDown => "Move items down",
+            PlayQueueAction::MoveNext => "Move items after the current one",
+            PlayQueueAction::MoveToEnd => "Move items to the end of the queue",
+            PlayQueueAction::SaveAsPlaylist => "Save the queue as a playlist",
+            PlayQueueAction::SaveRemainingAsPlaylist => "Save the remaining items as a playlist",
+            PlayQueueAction::Export => "Export the queue to a file",
+            PlayQueueAction::Undo => "Undo the last change to the queue",
+            PlayQueueAction::Redo => "Redo the last undone change to the queue",
         }
         .to_string()
     }
@@ -125,6 +165,26 @@ pub enum PlaylistListAction {
     ViewInfo,
     /// Refresh the playlist list
     Refresh,
+    /// Save the entire playlist to the download directory
+    Download,
+    /// Create an empty playlist
+    Create,
+    /// Change the name of the current playlist
+    Rename,
+    /// Change the comment of the current playlist
+    EditComment,
+    /// Make the current playlist public if it is private, and vice versa
+    TogglePublic,
+    /// Delete the current playlist from the server
+    Delete,
+    /// Save the current playlist as a playlist file
+    Export,
+    /// Create a playlist from a playlist file
+    Import,
+    /// Revert the last change made to the name, comment or visibility of a playlist
+    Undo,
+    /// Apply the last change that has been reverted again
+    Redo,
 }
 
 impl ToString for PlaylistListAction {
@@ -143,11 +203,170 @@ impl ToString for PlaylistListAction {
             },
             PlaylistListAction::ViewInfo => "View details of the current playlist",
             PlaylistListAction::Refresh => "Refresh the playlist list",
+            PlaylistListAction::Download => "Download the entire playlist",
+            PlaylistListAction::Create => "Create a new playlist",
+            PlaylistListAction::Rename => "Rename the current playlist",
+            PlaylistListAction::EditComment => "Edit comment of the current playlist",
+            PlaylistListAction::TogglePublic => "Make the current playlist public/private",
+            PlaylistListAction::Delete => "Delete the current playlist",
+            PlaylistListAction::Export => "Export the current playlist to a file",
+            PlaylistListAction::Import => "Import a playlist from a file",
+            PlaylistListAction::Undo => "Undo the last change to a playlist",
+            PlaylistListAction::Redo => "Redo the last undone change to a playlist",
         }
         .to_string()
     }
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub enum AlbumListAction {
+    /// Add the entire album to the queue
+    Add(QueueLocation),
+    /// Shuffle the entire album, then add it to the queue
+    RandomAdd(QueueLocation),
+    /// Open the selected album in the playlist queue view
+    ViewSelected,
+    /// Fetch the album list again
+    Refresh,
+    /// Fetch the next page of albums, appending them to the end of the list
+    LoadMore,
+    /// Switch to the next list type
+    NextListType,
+    /// Switch to the previous list type
+    PrevListType,
+    /// Switch to a specific list type
+    SetListType(AlbumListType),
+}
+
+impl Display for AlbumListAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlbumListAction::Add(queue_location) => match queue_location {
+                QueueLocation::Front => write!(f, "Play the entire album immediately"),
+                QueueLocation::Next => write!(f, "Play the entire album next"),
+                QueueLocation::Last => write!(f, "Append the entire album to the end of the queue"),
+            },
+            AlbumListAction::RandomAdd(queue_location) => match queue_location {
+                QueueLocation::Front => write!(f, "Shuffle the album and play it immediately"),
+                QueueLocation::Next => write!(f, "Shuffle the album and play it next"),
+                QueueLocation::Last => write!(f, "Shuffle the album and append it to the queue"),
+            },
+            AlbumListAction::ViewSelected => write!(f, "Open selected album"),
+            AlbumListAction::Refresh => write!(f, "Refresh the album list"),
+            AlbumListAction::LoadMore => write!(f, "Load more albums"),
+            AlbumListAction::NextListType => write!(f, "Sort albums by the next criteria"),
+            AlbumListAction::PrevListType => write!(f, "Sort albums by the previous criteria"),
+            AlbumListAction::SetListType(t) => write!(f, "List albums: {}", t),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub enum HistoryAction {
+    /// Add the selected media to the queue again
+    Add(QueueLocation),
+    /// Shuffle the selected media, then add them to the queue
+    RandomAdd(QueueLocation),
+    /// View the details of the media under the cursor
+    ViewInfo,
+}
+
+impl Display for HistoryAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryAction::Add(queue_location) => match queue_location {
+                QueueLocation::Front => write!(f, "Play the selected items again immediately"),
+                QueueLocation::Next => write!(f, "Play the selected items again next"),
+                QueueLocation::Last => write!(f, "Append the selected items to the queue"),
+            },
+            HistoryAction::RandomAdd(queue_location) => match queue_location {
+                QueueLocation::Front => write!(f, "Shuffle the selected items and play them"),
+                QueueLocation::Next => write!(f, "Shuffle the selected items and play them next"),
+                QueueLocation::Last => write!(f, "Shuffle the selected items and append them"),
+            },
+            HistoryAction::ViewInfo => write!(f, "View the details of the media"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub enum StarredAction {
+    Up,
+    Down,
+    Top,
+    Bottom,
+    /// Move to the next section (Artists -> Albums -> Songs)
+    NextSection,
+    /// Move to the previous section
+    PrevSection,
+    /// Open the artist or the album under the cursor. For songs, this plays the song immediately.
+    Open,
+    /// Add the song, the album or every album of the artist under the cursor to the queue
+    Add(QueueLocation),
+    /// Unstar the item under the cursor, removing it from the list
+    Unstar,
+    /// View the details of the item under the cursor
+    ViewInfo,
+    /// Fetch the starred items again
+    Refresh,
+}
+
+impl Display for StarredAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StarredAction::Up => write!(f, "Move up"),
+            StarredAction::Down => write!(f, "Move down"),
+            StarredAction::Top => write!(f, "Move to top"),
+            StarredAction::Bottom => write!(f, "Move to bottom"),
+            StarredAction::NextSection => write!(f, "Move to the next section"),
+            StarredAction::PrevSection => write!(f, "Move to the previous section"),
+            StarredAction::Open => write!(f, "Open selected artist/album, or play the song"),
+            StarredAction::Add(queue_location) => match queue_location {
+                QueueLocation::Front => write!(f, "Play selected item immediately"),
+                QueueLocation::Next => write!(f, "Play selected item next"),
+                QueueLocation::Last => write!(f, "Append selected item to the end of the queue"),
+            },
+            StarredAction::Unstar => write!(f, "Unstar selected item"),
+            StarredAction::ViewInfo => write!(f, "View details of the selected item"),
+            StarredAction::Refresh => write!(f, "Fetch starred items again"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub enum ArtistListAction {
+    /// Open the artist or the album under the cursor
+    ViewSelected,
+    /// Go back to the artist index
+    Back,
+    /// Add the entire album to the queue
+    Add(QueueLocation),
+    /// Shuffle the entire album, then add it to the queue
+    RandomAdd(QueueLocation),
+    /// Fetch the current view from the server again
+    Refresh,
+}
+
+impl Display for ArtistListAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArtistListAction::ViewSelected => write!(f, "Open selected artist or album"),
+            ArtistListAction::Back => write!(f, "Go back to the artist index"),
+            ArtistListAction::Add(queue_location) => match queue_location {
+                QueueLocation::Front => write!(f, "Play the entire album immediately"),
+                QueueLocation::Next => write!(f, "Play the entire album next"),
+                QueueLocation::Last => write!(f, "Append the entire album to the end of the queue"),
+            },
+            ArtistListAction::RandomAdd(queue_location) => match queue_location {
+                QueueLocation::Front => write!(f, "Shuffle the album and play it immediately"),
+                QueueLocation::Next => write!(f, "Shuffle the album and play it next"),
+                QueueLocation::Last => write!(f, "Shuffle the album and append it to the queue"),
+            },
+            ArtistListAction::Refresh => write!(f, "Fetch the current view again"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub enum PlaylistQueueAction {
     /// Refresh the playlist queue
@@ -156,12 +375,26 @@ pub enum PlaylistQueueAction {
     Add(QueueLocation),
     /// Star or unstar selected items
     ToggleStar,
+    /// Rate selected items from 1 to 5, or remove the rating with 0
+    SetRating(u8),
     /// Shuffle the selected items, and add them to the queue
     RandomAdd(QueueLocation),
     /// View information of the item under the cursor
     ViewInfo,
     /// Add selected items to a playlist
     AddToPlaylist,
+    /// Save selected items to the download directory
+    Download,
+    /// Remove selected items from the playlist
+    Delete,
+    /// Move selected items up by one in the playlist
+    MoveUp,
+    /// Move selected items down by one in the playlist
+    MoveDown,
+    /// Revert the last change made to the items in the playlist
+    Undo,
+    /// Apply the last change that has been reverted again
+    Redo,
 }
 
 impl ToString for PlaylistQueueAction {
@@ -179,8 +412,16 @@ impl ToString for PlaylistQueueAction {
                 QueueLocation::Last => "Shuffle the selected items and append it to the queue",
             },
             PlaylistQueueAction::ToggleStar => "Star/unstar items",
+            PlaylistQueueAction::SetRating(0) => "Remove the rating of items",
+            PlaylistQueueAction::SetRating(rating) => return format!("Rate items {}", rating),
             PlaylistQueueAction::ViewInfo => "View details of the current media",
             PlaylistQueueAction::AddToPlaylist => "Add selected items to a playlist",
+            PlaylistQueueAction::Download => "Download selected items",
+            PlaylistQueueAction::Delete => "Remove selected items from the playlist",
+            PlaylistQueueAction::MoveUp => "Move selected items up",
+            PlaylistQueueAction::MoveDown => "Move selected items down",
+            PlaylistQueueAction::Undo => "Undo the last change to the playlist",
+            PlaylistQueueAction::Redo => "Redo the last undone change to the playlist",
         }
         .to_string()
     }
@@ -207,6 +448,7 @@ pub enum PopupAction {
     Top,
     Bottom,
     Close,
+    Cancel,
 }
 
 impl ToString for PopupAction {
@@ -217,6 +459,7 @@ impl ToString for PopupAction {
             PopupAction::Top => "Move to top",
             PopupAction::Bottom => "Move to bottom",
             PopupAction::Close => "Close popup",
+            PopupAction::Cancel => "Cancel the selected task",
         }
         .to_string()
     }
@@ -248,3 +491,52 @@ impl ToString for SelectPlaylistPopupAction {
         }
     }
 }
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub enum GlobalSearchAction {
+    Up,
+    Down,
+    Top,
+    Bottom,
+    /// Move to the next section of the result (Artists -> Albums -> Songs)
+    NextSection,
+    /// Move to the previous section of the result
+    PrevSection,
+    /// Go back to editing the search keyword
+    Edit,
+    /// Open the artist or the album under the cursor. For songs, this plays the song immediately.
+    Open,
+    /// Add the song, or every song of the album or the artist under the cursor to the queue
+    Add(QueueLocation),
+    /// Star or unstar the artist, the album or the song under the cursor
+    ToggleStar,
+    /// Add the song, or every song of the album or the artist under the cursor to a playlist
+    AddToPlaylist,
+    /// View information of the song under the cursor
+    ViewInfo,
+    Close,
+}
+
+impl Display for GlobalSearchAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GlobalSearchAction::Up => write!(f, "Move up"),
+            GlobalSearchAction::Down => write!(f, "Move down"),
+            GlobalSearchAction::Top => write!(f, "Move to top"),
+            GlobalSearchAction::Bottom => write!(f, "Move to bottom"),
+            GlobalSearchAction::NextSection => write!(f, "Move to the next section"),
+            GlobalSearchAction::PrevSection => write!(f, "Move to the previous section"),
+            GlobalSearchAction::Edit => write!(f, "Edit search keyword"),
+            GlobalSearchAction::Open => write!(f, "Open selected artist/album, or play the song"),
+            GlobalSearchAction::Add(queue_location) => match queue_location {
+                QueueLocation::Front => write!(f, "Play selected item immediately"),
+                QueueLocation::Next => write!(f, "Play selected item next"),
+                QueueLocation::Last => write!(f, "Append selected item to the end of the queue"),
+            },
+            GlobalSearchAction::ToggleStar => write!(f, "Star/unstar selected item"),
+            GlobalSearchAction::AddToPlaylist => write!(f, "Add selected item to a playlist"),
+            GlobalSearchAction::ViewInfo => write!(f, "View details of the song"),
+            GlobalSearchAction::Close => write!(f, "Close popup"),
+        }
+    }
+}
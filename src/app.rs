@@ -1,11 +1,22 @@
 mod delayer;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
 use color_eyre::Result;
 
 use crossterm::event::KeyEvent;
 
 use ratatui::prelude::Rect;
-use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
-use tracing::debug;
+use tokio::{
+    sync::{
+        mpsc::{UnboundedReceiver, UnboundedSender},
+        RwLock,
+    },
+    time::timeout,
+};
+use tracing::{debug, error};
 
 use crate::{
     action::action::{Action, Mode, TargetedAction},
@@ -24,9 +35,17 @@ use crate::{
         },
     },
     config::Config,
+    datastore::{DataStore, PlayRecord, QueueCursor, QueueState},
     mpris::MprisSignal,
-    playerworker::player::{FromPlayerWorker, ToPlayerWorker},
-    queryworker::query::{QueryStatus, ToQueryWorker},
+    osclient::response::getplaylist::Media,
+    playerworker::{
+        player::{FromPlayerWorker, ToPlayerWorker},
+        playerstatus::PlayerStatus,
+    },
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{saveplayqueue::SavePlayQueueParams, QueryStatus, ToQueryWorker},
+    },
     tui::{Event, Tui},
 };
 
@@ -44,6 +63,17 @@ pub struct App {
     mpris_tx: UnboundedSender<MprisSignal>,
     tui: Tui,
     delayer: Delayer,
+    playerstatus: Arc<RwLock<PlayerStatus>>,
+    store: Option<DataStore>,
+    /// None if the queue should not be uploaded to the server
+    sync_interval: Option<Duration>,
+    last_sync: Instant,
+    /// Queue that was last uploaded to the server
+    synced_queue: Option<SavePlayQueueParams>,
+    /// Media that is currently being played and when it started, to be saved to the history
+    playing: Option<(Media, i64)>,
+    /// Last position reported by the player
+    position: Duration,
     #[cfg(test)]
     debug_tx: UnboundedSender<bool>,
 }
@@ -56,11 +86,44 @@ impl App {
         mpris_tx: UnboundedSender<MprisSignal>,
         query_tx: UnboundedSender<ToQueryWorker>,
         player_tx: UnboundedSender<ToPlayerWorker>,
+        playerstatus: Arc<RwLock<PlayerStatus>>,
         tick_rate: f64,
         frame_rate: f64,
         #[cfg(test)] debug_tx: UnboundedSender<bool>,
     ) -> Result<Self> {
-        let (component, action) = Home::new(config.clone());
+        let store = if config.config.persist_data {
+            match DataStore::open(&config.config.data_dir) {
+                Ok(s) => Some(s),
+                Err(e) => {
+                    error!("Failed to open the database: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let saved_queue = store.as_ref().and_then(|s| match s.load_queue() {
+            Ok(q) => q,
+            Err(e) => {
+                error!("Failed to load the saved queue: {e}");
+                None
+            }
+        });
+        let saved_history = match (&store, config.features.history.enable) {
+            (Some(s), true) => s
+                .get_plays(config.features.history.limit)
+                .unwrap_or_else(|e| {
+                    error!("Failed to load the play history: {e}");
+                    vec![]
+                }),
+            _ => vec![],
+        };
+        let (component, action) = Home::new(config.clone(), saved_queue, saved_history);
+        let sync_interval = if config.features.queue_sync.enable {
+            Some(Duration::from_secs(config.features.queue_sync.interval))
+        } else {
+            None
+        };
         let _ = action_tx.send(action);
         Ok(Self {
             tui: Tui::new()?.tick_rate(tick_rate).frame_rate(frame_rate),
@@ -76,6 +139,13 @@ impl App {
             player_tx,
             mode: Mode::Normal,
             delayer: Delayer::new(),
+            playerstatus,
+            store,
+            sync_interval,
+            last_sync: Instant::now(),
+            synced_queue: None,
+            playing: None,
+            position: Duration::default(),
             #[cfg(test)]
             debug_tx,
         })
@@ -100,9 +170,116 @@ impl App {
         }
         // let _ = tokio::join!(self.query_thread);
         self.tui.exit()?;
+        self.finish_play();
+        self.sync_on_quit().await;
+        self.save_state().await;
         Ok(())
     }
 
+    /// Returns a query that uploads the queue if it has changed since the last upload
+    async fn queue_sync_query(&mut self) -> Option<ToQueryWorker> {
+        let (items, cursor) = self.component.get_queue_state()?;
+        let current = match cursor {
+            QueueCursor::InQueue(idx) => items.get(idx).map(|m| m.id.clone()),
+            _ => None,
+        };
+        let (position, playing) = {
+            let status = self.playerstatus.read().await;
+            (status.position.as_millis() as u64, status.playing)
+        };
+        let params = SavePlayQueueParams {
+            ids: items.into_iter().map(|m| m.id).collect(),
+            position: current.as_ref().map(|_| position),
+            current,
+        };
+        let changed = match &self.synced_queue {
+            // The first state after login is only used as a baseline. Otherwise, simply starting
+            // the program would overwrite the queue saved by other clients.
+            None => false,
+            // Position is ignored while paused as it may be changing because of restoration
+            Some(last) => {
+                last.ids != params.ids
+                    || last.current != params.current
+                    || (playing && last.position != params.position)
+            }
+        };
+        let query = if changed {
+            Some(ToQueryWorker::new(HighLevelQuery::SavePlayQueue(
+                params.clone(),
+            )))
+        } else {
+            None
+        };
+        if changed || self.synced_queue.is_none() {
+            self.synced_queue = Some(params);
+        }
+        query
+    }
+
+    /// Upload the queue one last time, and wait for the server to respond
+    async fn sync_on_quit(&mut self) {
+        if self.sync_interval.is_none() {
+            return;
+        }
+        let Some(query) = self.queue_sync_query().await else {
+            return;
+        };
+        let ticket = query.ticket;
+        if self.query_tx.send(query).is_err() {
+            return;
+        }
+        let wait = async {
+            while let Some(action) = self.action_rx.recv().await {
+                if let Action::FromQuery { ticket: t, .. } = action {
+                    if t == ticket {
+                        break;
+                    }
+                }
+            }
+        };
+        if timeout(Duration::from_secs(3), wait).await.is_err() {
+            error!("Timed out while saving the queue to the server");
+        }
+    }
+
+    /// Saves the media that was being played to the history, returning the action that shows it in
+    /// the history list
+    fn finish_play(&mut self) -> Option<Action> {
+        let (media, played_at) = self.playing.take()?;
+        let record = PlayRecord {
+            played_at,
+            media,
+            played: self.position,
+        };
+        if let Some(store) = &self.store {
+            if let Err(e) = store.push_play(&record, self.config.features.history.limit) {
+                error!("Failed to save the play history: {e}");
+            }
+        }
+        Some(Action::Targeted(TargetedAction::RecordPlay(record)))
+    }
+
+    /// Write the current queue and player state into the database
+    async fn save_state(&mut self) {
+        let Some(store) = &mut self.store else {
+            return;
+        };
+        let Some((items, cursor)) = self.component.get_queue_state() else {
+            return;
+        };
+        let status = self.playerstatus.read().await;
+        let state = QueueState {
+            items,
+            cursor,
+            position: status.position,
+            volume: status.volume,
+            speed: status.speed,
+        };
+        if let Err(e) = store.save_queue(&state) {
+            error!("Failed to save the queue: {e}");
+        }
+    }
+
     async fn handle_events(&mut self) -> Result<()> {
         let Some(event) = self.tui.next_event().await else {
             return Ok(());
@@ -115,6 +292,14 @@ impl App {
                 while let Some(q) = self.delayer.on_tick() {
                     self.query_tx.send(q)?;
                 }
+                if let Some(interval) = self.sync_interval {
+                    if self.synced_queue.is_none() || self.last_sync.elapsed() >= interval {
+                        self.last_sync = Instant::now();
+                        if let Some(q) = self.queue_sync_query().await {
+                            action_tx.send(Action::ToQuery(q))?;
+                        }
+                    }
+                }
             }
             Event::Render => self.render()?,
             Event::Resize(x, y) => action_tx.send(Action::Resize(x, y))?,
@@ -220,6 +405,21 @@ impl App {
                         self.player_tx.send(ToPlayerWorker::SetPosition(to))?
                     }
                     TargetedAction::GoToStart => self.player_tx.send(ToPlayerWorker::GoToStart)?,
+                    TargetedAction::ToggleCrossfade => {
+                        self.player_tx.send(ToPlayerWorker::ToggleCrossfade)?
+                    }
+                    TargetedAction::PlayModeChanged { repeat, order } => {
+                        {
+                            let mut status = self.playerstatus.write().await;
+                            status.repeat = repeat;
+                            status.order = order;
+                        }
+                        let _ = self.mpris_tx.send(MprisSignal::LoopStatus(repeat));
+                        let _ = self.mpris_tx.send(MprisSignal::Shuffle(order));
+                        if let Some(more) = self.component.handle_action(targeted_action) {
+                            self.action_tx.send(more)?
+                        }
+                    }
                     TargetedAction::EndKeySeq => {
                         self.key_stack.drain(..);
                         if let Some(more) = self.component.handle_action(targeted_action) {
@@ -283,6 +483,29 @@ impl App {
                     self.player_tx.send(to_player_worker)?
                 }
                 Action::FromPlayer(pw) => {
+                    if self.config.features.history.enable {
+                        let finished = match &pw {
+                            FromPlayerWorker::Position(pos) => {
+                                self.position = *pos;
+                                None
+                            }
+                            FromPlayerWorker::NowPlaying(media) => {
+                                let finished = self.finish_play();
+                                self.playing = media
+                                    .clone()
+                                    .map(|m| (m, jiff::Timestamp::now().as_second()));
+                                self.position = Duration::default();
+                                finished
+                            }
+                            FromPlayerWorker::Finished | FromPlayerWorker::Complete => {
+                                self.finish_play()
+                            }
+                            _ => None,
+                        };
+                        if let Some(action) = finished {
+                            self.action_tx.send(action)?
+                        }
+                    }
                     let _ = match &pw {
                         FromPlayerWorker::Complete => {
                             self.delayer.on_stop();
@@ -291,6 +514,9 @@ impl App {
                         FromPlayerWorker::Playing(b) => {
                             self.mpris_tx.send(MprisSignal::Playing(*b))
                         }
+                        FromPlayerWorker::Advanced(m) => {
+                            self.mpris_tx.send(MprisSignal::NowPlaying(Some(m.clone())))
+                        }
                         FromPlayerWorker::Volume(v) => self.mpris_tx.send(MprisSignal::Volume(*v)),
                         FromPlayerWorker::Speed(s) => self.mpris_tx.send(MprisSignal::Speed(*s)),
                         _ => Ok(()),
@@ -0,0 +1,249 @@
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use reqwest::Url;
+
+use crate::{config::Config, osclient::types::MediaID};
+
+const CACHE_DIR: &str = "cache";
+/// Media that is still being downloaded is kept here, and moved into the cache once it is complete
+const PARTIAL_DIR: &str = ".partial";
+/// Name of the file if the media is not transcoded
+const ORIGINAL: &str = "original";
+
+/// Media files kept in the data directory so that they can be played again without downloading
+/// them, or without the server at all.
+///
+/// Each media has its own directory named after its ID, and each file in it is the same media with
+/// different transcoding parameters. Modification time of a file is updated whenever it is played,
+/// and the least recently played files are removed once the cache exceeds its maximum size.
+#[derive(Clone, Debug)]
+pub struct AudioCache {
+    dir: PathBuf,
+    /// Maximum size in bytes
+    max_size: u64,
+}
+
+impl AudioCache {
+    /// Returns None if caching is disabled, or if nothing should be written to the data directory
+    pub fn new(config: &Config) -> Option<Self> {
+        let cache = &config.features.cache;
+        if !cache.enable || !config.config.persist_data {
+            return None;
+        }
+        Some(Self::open(
+            config.config.data_dir.join(CACHE_DIR),
+            cache.max_size * 1024 * 1024,
+        ))
+    }
+
+    pub fn open(dir: PathBuf, max_size: u64) -> Self {
+        Self { dir, max_size }
+    }
+
+    /// Name of the cached file for the media streamed from the given URL. Only the transcoding
+    /// parameters are used, as the rest of the URL (such as the authentication) may change.
+    pub fn variant(url: &Url) -> String {
+        let mut params: Vec<String> = url
+            .query_pairs()
+            .filter(|(k, _)| k == "format" || k == "maxBitRate")
+            .map(|(k, v)| format!("{k}-{v}"))
+            .collect();
+        if params.is_empty() {
+            ORIGINAL.to_string()
+        } else {
+            params.sort();
+            Self::sanitise(&params.join("_"))
+        }
+    }
+
+    fn sanitise(name: &str) -> String {
+        name.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    /// Every character other than letters, digits, '-' and '_' is percent-encoded so that no two
+    /// IDs share a directory
+    fn media_dir(&self, id: &MediaID) -> PathBuf {
+        let name: String = id
+            .bytes()
+            .map(|b| {
+                if b.is_ascii_alphanumeric() || b == b'-' || b == b'_' {
+                    (b as char).to_string()
+                } else {
+                    format!("%{b:02X}")
+                }
+            })
+            .collect();
+        self.dir.join(name)
+    }
+
+    /// Marks the file as the most recently played one
+    fn touch(path: &Path) {
+        if let Ok(f) = File::options().write(true).open(path) {
+            let _ = f.set_modified(SystemTime::now());
+        }
+    }
+
+    /// Path to the cached media with the exact transcoding parameters
+    pub fn get(&self, id: &MediaID, variant: &str) -> Option<PathBuf> {
+        let path = self.media_dir(id).join(variant);
+        if path.is_file() {
+            Self::touch(&path);
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Path to the cached media regardless of how it has been transcoded
+    pub fn get_any(&self, id: &MediaID) -> Option<PathBuf> {
+        let path = fs::read_dir(self.media_dir(id))
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .find(|p| p.is_file())?;
+        Self::touch(&path);
+        Some(path)
+    }
+
+    pub fn is_cached(&self, id: &MediaID) -> bool {
+        fs::read_dir(self.media_dir(id))
+            .is_ok_and(|mut entries| entries.any(|e| e.is_ok_and(|e| e.path().is_file())))
+    }
+
+    /// Directory in which the media that is being downloaded is stored
+    pub fn partial_dir(&self) -> io::Result<PathBuf> {
+        let dir = self.dir.join(PARTIAL_DIR);
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Add the fully downloaded file to the cache, then remove old files if the cache is too big.
+    /// The file is hard linked rather than moved because it is still being read by the player.
+    pub fn insert(&self, id: &MediaID, variant: &str, file: &Path) -> io::Result<()> {
+        let dir = self.media_dir(id);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(variant);
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        if fs::hard_link(file, &path).is_err() {
+            fs::copy(file, &path)?;
+        }
+        self.evict()
+    }
+
+    /// Remove the least recently played files until the cache fits in the maximum size
+    pub fn evict(&self) -> io::Result<()> {
+        let mut files = vec![];
+        let mut total = 0;
+        for dir in fs::read_dir(&self.dir)? {
+            let dir = dir?;
+            if dir.file_name() == PARTIAL_DIR || !dir.file_type()?.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(dir.path())? {
+                let file = file?;
+                let meta = file.metadata()?;
+                total += meta.len();
+                files.push((meta.modified()?, meta.len(), file.path()));
+            }
+        }
+        if total <= self.max_size {
+            return Ok(());
+        }
+        files.sort_by_key(|(modified, _, _)| *modified);
+        for (_, len, path) in files {
+            if total <= self.max_size {
+                break;
+            }
+            fs::remove_file(&path)?;
+            total -= len;
+            if let Some(parent) = path.parent() {
+                // Fails if there are other versions of the media left, which is fine
+                let _ = fs::remove_dir(parent);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use stream_download::storage::temp::tempfile;
+
+    use super::*;
+
+    /// Adds a media that was last played the given number of seconds ago
+    fn add(cache: &AudioCache, tmp: &Path, id: &str, size: usize, age: u64) {
+        let file = tmp.join(format!("{id}.tmp"));
+        fs::write(&file, vec![0u8; size]).unwrap();
+        let id = MediaID(id.to_string());
+        cache.insert(&id, ORIGINAL, &file).unwrap();
+        // Modification time is set explicitly, as it may be too coarse to tell the files apart
+        File::options()
+            .write(true)
+            .open(cache.media_dir(&id).join(ORIGINAL))
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(age))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_variant() {
+        let plain: Url = "http://localhost/rest/stream?id=1&u=a&t=b&s=c"
+            .parse()
+            .unwrap();
+        assert_eq!(AudioCache::variant(&plain), ORIGINAL);
+        let transcoded: Url = "http://localhost/rest/stream?maxBitRate=128&id=1&format=mp3&s=c"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            AudioCache::variant(&transcoded),
+            "format-mp3_maxBitRate-128"
+        );
+    }
+
+    #[test]
+    fn test_evict_least_recent() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = AudioCache::open(tmp.path().join(CACHE_DIR), 250);
+        add(&cache, tmp.path(), "a", 100, 300);
+        add(&cache, tmp.path(), "b", 100, 200);
+        // Playing the media makes it the most recent one
+        assert!(cache.get(&MediaID("a".to_string()), ORIGINAL).is_some());
+        add(&cache, tmp.path(), "c", 100, 0);
+        assert!(cache.is_cached(&MediaID("a".to_string())));
+        assert!(!cache.is_cached(&MediaID("b".to_string())));
+        assert!(cache.is_cached(&MediaID("c".to_string())));
+        assert!(cache.get_any(&MediaID("c".to_string())).is_some());
+        assert!(cache.get(&MediaID("c".to_string()), "format-mp3").is_none());
+    }
+
+    #[test]
+    fn test_media_dir() {
+        let cache = AudioCache::open(PathBuf::from(CACHE_DIR), 0);
+        let dirs: Vec<PathBuf> = ["a.b", "a_b", "a%2Eb", "a/b"]
+            .into_iter()
+            .map(|id| cache.media_dir(&MediaID(id.to_string())))
+            .collect();
+        for (i, dir) in dirs.iter().enumerate() {
+            assert_eq!(dir.parent(), Some(Path::new(CACHE_DIR)));
+            assert!(!dirs[i + 1..].contains(dir));
+        }
+    }
+}
@@ -1,6 +1,13 @@
-use clap::Parser;
+use std::path::PathBuf;
 
-use crate::config::pathconfig::PathConfig;
+use clap::{Parser, Subcommand};
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    config::{pathconfig::PathConfig, playlistfileconfig::PlaylistFormat, Config},
+    playlistfile,
+    queryworker::query::setcredential::Credential,
+};
 
 #[derive(Parser, Debug)]
 #[command(author, version = version(), about)]
@@ -30,6 +37,81 @@ pub struct Cli {
     /// Do not save any data to the database. Incompatible with --data.
     #[arg(long, default_value_t = false)]
     pub no_data: bool,
+
+    /// Run a single task with the credentials in the config instead of opening the player
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Save a playlist on the server as an M3U or XSPF file
+    Export {
+        /// Name or ID of the playlist
+        playlist: String,
+        /// File to write to. The format is determined by the extension. If not set, the file is
+        /// saved to the playlist directory in the config.
+        file: Option<PathBuf>,
+    },
+    /// Create a playlist on the server from an M3U or XSPF file
+    Import {
+        /// File to read from
+        file: PathBuf,
+        /// Name of the new playlist. If not set, the name in the file or the name of the file is
+        /// used.
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+}
+
+impl Command {
+    pub async fn run(self, config: Config) -> Result<()> {
+        let Some(creds) = Credential::from_config(&config) else {
+            return Err(eyre!(
+                "No credentials detected in the config. (Loaded config from {:?})",
+                PathConfig::get_config_dir()
+            ));
+        };
+        let client = creds.into_client().map_err(|e| eyre!(e.to_string()))?;
+        match self {
+            Command::Export { playlist, file } => {
+                let playlist = playlistfile::find_playlist(&client, &playlist)
+                    .await
+                    .map_err(|e| eyre!(e))?;
+                let items = playlistfile::fetch_items(&client, playlist.id)
+                    .await
+                    .map_err(|e| eyre!(e))?;
+                let settings = config.features.playlist_file;
+                let (path, format) = match file {
+                    Some(path) => {
+                        let format = PlaylistFormat::from_path(&path).unwrap_or(settings.format);
+                        (path, format)
+                    }
+                    None => (
+                        playlistfile::export_path(&settings.dir, &playlist.name, settings.format),
+                        settings.format,
+                    ),
+                };
+                playlistfile::export(&path, &playlist.name, &items, format)
+                    .await
+                    .map_err(|e| eyre!(e))?;
+                println!("Exported {} items to {}", items.len(), path.display());
+            }
+            Command::Import { file, name } => {
+                let res = playlistfile::import(&client, &file, name)
+                    .await
+                    .map_err(|e| eyre!(e))?;
+                println!("Imported {} items as {}", res.count, res.name);
+                if !res.unmatched.is_empty() {
+                    println!("{} items were not found:", res.unmatched.len());
+                    for entry in res.unmatched {
+                        println!("  {}", entry);
+                    }
+                }
+            }
+        };
+        Ok(())
+    }
 }
 
 impl Cli {
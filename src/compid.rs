@@ -22,9 +22,13 @@ pub enum CompID {
     Login,
     MainScreen,
     PlaylistList,
+    AlbumList,
+    ArtistList,
     PlaylistQueue,
     PlayQueue,
     NowPlaying,
     ImageComp,
     Lyrics,
+    GlobalSearch,
+    Starred,
 }
@@ -2,9 +2,6 @@ mod loading;
 mod login;
 mod mainscreen;
 
-use std::process::Command;
-
-use color_eyre::Result;
 use crossterm::event::KeyEvent;
 use loading::Loading;
 use login::Login;
@@ -20,6 +17,8 @@ use crate::{
         renderable::Renderable,
     },
     config::{pathconfig::PathConfig, Config},
+    datastore::{PlayRecord, QueueCursor, QueueState},
+    osclient::response::getplaylist::Media,
     playerworker::player::FromPlayerWorker,
     queryworker::{
         highlevelquery::HighLevelQuery,
@@ -38,6 +37,10 @@ enum Comp {
 pub struct Home {
     component: Comp,
     config: Config,
+    /// Queue from the previous session, restored once the user logs in
+    saved_queue: Option<QueueState>,
+    /// Media played in the previous sessions, shown once the user logs in
+    saved_history: Vec<PlayRecord>,
 }
 
 impl OnTick for Home {
@@ -68,12 +71,7 @@ impl HandleQuery for Home {
                 // 2. Login with the config credentials fails
                 if let QueryStatus::Finished(ResponseType::Login(p)) = res {
                     match p {
-                        Ok(()) => {
-                            // Switch child component to MainScreen
-                            let (comp, actions) = MainScreen::new(self.config.clone());
-                            self.component = Comp::Main(comp);
-                            Some(actions)
-                        }
+                        Ok(()) => Some(self.start_main_screen()),
                         Err(err) => {
                             // Switch child component to Login
                             self.component = Comp::Login(Login::new(Some(vec![
@@ -90,10 +88,7 @@ impl HandleQuery for Home {
             }
             Comp::Login(login) => {
                 if let QueryStatus::Finished(ResponseType::Login(Ok(()))) = res {
-                    // Switch child component to MainScreen
-                    let (comp, actions) = MainScreen::new(self.config.clone());
-                    self.component = Comp::Main(comp);
-                    Some(actions)
+                    Some(self.start_main_screen())
                 } else {
                     login.handle_query(dest, ticket, res)
                 }
@@ -128,46 +123,33 @@ impl HandleAction for Home {
 }
 
 impl Home {
-    pub fn new(config: Config) -> (Self, Action) {
-        let auth = config.clone().auth;
-        let config_creds = if let Some(creds) = auth {
-            fn run_cmd(cmd: &String) -> Result<String> {
-                let exec = Command::new("sh").arg("-c").arg(cmd).output()?;
-                let stdout = String::from_utf8_lossy(&exec.stdout);
-                Ok(stdout.trim().to_string())
-            }
-            if let Ok(url) = run_cmd(&creds.url) {
-                if let Ok(username) = run_cmd(&creds.username) {
-                    if let Ok(password) = run_cmd(&creds.password) {
-                        Some(Credential::Password {
-                            url,
-                            secure: true,
-                            username,
-                            password,
-                            legacy: config.config.use_legacy_auth,
-                        })
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else {
-            match config.clone().unsafe_auth {
-                Some(unsafe_creds) => Some(Credential::Password {
-                    url: unsafe_creds.url,
-                    username: unsafe_creds.username,
-                    password: unsafe_creds.password,
-                    legacy: config.config.use_legacy_auth,
-                    secure: true,
-                }),
-                None => None,
-            }
+    /// Switch child component to MainScreen
+    fn start_main_screen(&mut self) -> Action {
+        let (mut comp, action) =
+            MainScreen::new(self.config.clone(), std::mem::take(&mut self.saved_history));
+        let action = match self.saved_queue.take() {
+            Some(state) => Action::Multiple(vec![action, comp.restore_queue(state)]),
+            None => action,
         };
-        let (comp, actions): (Comp, Action) = match config_creds {
+        self.component = Comp::Main(comp);
+        action
+    }
+
+    /// Returns the current queue. None is returned if the user has not logged in yet, in which
+    /// case the queue from the previous session should be left untouched.
+    pub fn get_queue_state(&self) -> Option<(Vec<Media>, QueueCursor)> {
+        match &self.component {
+            Comp::Main(main_screen) => Some(main_screen.get_queue_state()),
+            _ => None,
+        }
+    }
+
+    pub fn new(
+        config: Config,
+        saved_queue: Option<QueueState>,
+        saved_history: Vec<PlayRecord>,
+    ) -> (Self, Action) {
+        let (comp, actions): (Comp, Action) = match Credential::from_config(&config) {
             Some(creds) => {
                 let url = creds.get_url();
                 let username = creds.get_username();
@@ -188,6 +170,8 @@ impl Home {
             Self {
                 component: comp,
                 config,
+                saved_queue,
+                saved_history,
             },
             actions,
         )
@@ -1,24 +1,35 @@
+mod albumlist;
+mod artistlist;
 mod bpmtoy;
+mod confirm;
 mod filter;
+mod globalsearch;
 mod help;
+mod importreport;
 mod mediainfo;
 mod nowplaying;
+mod playhistory;
 mod playlistinfo;
 pub mod playlistlist;
 mod playlistqueue;
 mod playqueue;
+mod prompt;
+mod randommix;
 mod search;
 mod selectplaylistpopup;
+mod starred;
 mod tasks;
 
 use crate::{
-    action::action::{Action, Mode, SearchType, TargetedAction},
+    action::action::{Action, Mode, PlaylistEdit, QueueAction, SearchType, TargetedAction},
     compid::CompID,
     components::{
         home::mainscreen::{
-            bpmtoy::BPMToy, filter::Filter, help::Help, mediainfo::MediaInfo,
-            playlistinfo::PlaylistInfo, search::Search, selectplaylistpopup::SelectPlaylistPopup,
-            tasks::Tasks,
+            albumlist::AlbumList, artistlist::ArtistList, bpmtoy::BPMToy, confirm::Confirm,
+            filter::Filter, globalsearch::GlobalSearch, help::Help, importreport::ImportReport,
+            mediainfo::MediaInfo, playhistory::PlayHistory, playlistinfo::PlaylistInfo,
+            prompt::Prompt, randommix::RandomMix, search::Search,
+            selectplaylistpopup::SelectPlaylistPopup, starred::Starred, tasks::Tasks,
         },
         traits::{
             focusable::Focusable,
@@ -35,10 +46,16 @@ use crate::{
         },
     },
     config::{keyparser::KeyParser, Config},
-    playerworker::player::FromPlayerWorker,
+    datastore::{PlayRecord, QueueCursor, QueueState},
+    osclient::response::getplaylist::Media,
+    playerworker::player::{FromPlayerWorker, ToPlayerWorker},
     queryworker::{
         highlevelquery::HighLevelQuery,
-        query::{QueryStatus, ResponseType, ToQueryWorker},
+        query::{
+            getplaylist::GetPlaylistParams,
+            saveplaylist::{SavePlaylistParams, SaveTarget},
+            QueryStatus, ResponseType, ToQueryWorker,
+        },
     },
 };
 use crossterm::event::KeyEvent;
@@ -60,6 +77,16 @@ enum LastSelected {
     PlayQueue,
 }
 
+/// Determines what is displayed in the leftmost pane
+#[derive(PartialEq)]
+enum Library {
+    Playlists,
+    Albums,
+    Artists,
+    History,
+    Starred,
+}
+
 #[derive(PartialEq)]
 enum CurrentlySelected {
     PlaylistList,
@@ -74,25 +101,40 @@ enum Popup {
     Help,
     MediaInfo(MediaInfo),
     PlaylistInfo(PlaylistInfo),
+    ImportReport(ImportReport),
     SelectPlaylist(SelectPlaylistPopup),
     Filtering(Filter),
     Searching(Search),
+    GlobalSearch,
+    Prompt(Prompt),
+    Confirm(Confirm),
+    RandomMix(RandomMix),
 }
 
 pub struct MainScreen {
     state: CurrentlySelected,
+    library: Library,
     pl_list: PlaylistList,
+    album_list: AlbumList,
+    artist_list: ArtistList,
+    history: PlayHistory,
+    starred: Starred,
     pl_queue: PlaylistQueue,
     now_playing: NowPlaying,
     tasks: Tasks,
     popup: Popup,
     search: Option<String>,
+    global_search: Option<GlobalSearch>,
     bpmtoy: Option<BPMToy>,
     playqueue: PlayQueue,
     message: (bool, String),
     key_stack: Vec<String>,
     current_mode: Mode,
     help: Help,
+    /// Only the cached media is played and displayed if this is set
+    offline: bool,
+    /// Index of the streaming profile in use
+    stream_profile: usize,
     config: Config,
 }
 
@@ -128,6 +170,10 @@ impl HandleRaw for MainScreen {
         match &mut self.popup {
             Popup::Filtering(filter) => filter.handle_raw(key),
             Popup::Searching(search) => search.handle_raw(key),
+            Popup::Prompt(prompt) => prompt.handle_raw(key),
+            Popup::Confirm(confirm) => confirm.handle_raw(key),
+            Popup::RandomMix(comp) => comp.handle_raw(key),
+            Popup::GlobalSearch => self.global_search.as_mut()?.handle_raw(key),
             _ => None,
         }
     }
@@ -136,7 +182,13 @@ impl HandleRaw for MainScreen {
 impl PassKeySeq for MainScreen {
     fn get_help(&self) -> Vec<ComponentKeyHelp> {
         match &self.state {
-            CurrentlySelected::PlaylistList => self.pl_list.get_help(),
+            CurrentlySelected::PlaylistList => match &self.library {
+                Library::Playlists => self.pl_list.get_help(),
+                Library::Albums => self.album_list.get_help(),
+                Library::Artists => self.artist_list.get_help(),
+                Library::History => self.history.get_help(),
+                Library::Starred => self.starred.get_help(),
+            },
             CurrentlySelected::PlaylistQueue => self.pl_queue.get_help(),
             CurrentlySelected::PlayQueue => self.playqueue.get_help(),
             CurrentlySelected::NowPlaying(_) => self.now_playing.get_help(),
@@ -152,7 +204,13 @@ impl PassKeySeq for MainScreen {
         }
         let res = match &mut self.popup {
             Popup::None => match &self.state {
-                CurrentlySelected::PlaylistList => self.pl_list.handle_key_seq(keyseq),
+                CurrentlySelected::PlaylistList => match &self.library {
+                    Library::Playlists => self.pl_list.handle_key_seq(keyseq),
+                    Library::Albums => self.album_list.handle_key_seq(keyseq),
+                    Library::Artists => self.artist_list.handle_key_seq(keyseq),
+                    Library::History => self.history.handle_key_seq(keyseq),
+                    Library::Starred => self.starred.handle_key_seq(keyseq),
+                },
                 CurrentlySelected::PlaylistQueue => self.pl_queue.handle_key_seq(keyseq),
                 CurrentlySelected::PlayQueue => self.playqueue.handle_key_seq(keyseq),
                 CurrentlySelected::NowPlaying(_) => self.now_playing.handle_key_seq(keyseq),
@@ -161,8 +219,17 @@ impl PassKeySeq for MainScreen {
             Popup::Help => self.help.handle_key_seq(keyseq),
             Popup::MediaInfo(comp) => comp.handle_key_seq(keyseq),
             Popup::PlaylistInfo(comp) => comp.handle_key_seq(keyseq),
+            Popup::ImportReport(comp) => comp.handle_key_seq(keyseq),
             Popup::SelectPlaylist(comp) => comp.handle_key_seq(keyseq),
-            Popup::Filtering(_) | Popup::Searching(_) => None,
+            Popup::GlobalSearch => self
+                .global_search
+                .as_mut()
+                .and_then(|comp| comp.handle_key_seq(keyseq)),
+            Popup::Filtering(_)
+            | Popup::Searching(_)
+            | Popup::Prompt(_)
+            | Popup::Confirm(_)
+            | Popup::RandomMix(_) => None,
         };
         if matches!(res, Some(_)) {
             self.key_stack.drain(..);
@@ -176,11 +243,12 @@ impl MainScreen {
         self.help.display(self.get_help());
         self.popup = Popup::Help;
     }
-    pub fn new(config: Config) -> (Self, Action) {
+    pub fn new(config: Config, history: Vec<PlayRecord>) -> (Self, Action) {
         let (pl_list, action) = PlaylistList::new(config.clone(), true);
         (
             Self {
                 search: None,
+                global_search: None,
                 bpmtoy: if config.features.bpmtoy.enable.clone() {
                     Some(BPMToy::new(config.clone()))
                 } else {
@@ -190,11 +258,19 @@ impl MainScreen {
                     config.local.popup.clone(),
                     config.behaviour.show_internal_tasks.clone(),
                 ),
+                library: Library::Playlists,
                 pl_list,
+                album_list: AlbumList::new(config.clone(), false),
+                artist_list: ArtistList::new(config.clone(), false),
                 pl_queue: PlaylistQueue::new(config.clone(), false),
-                playqueue: PlayQueue::new(false, config.clone()),
+                playqueue: PlayQueue::new(false, config.clone())
+                    .with_recent(history.iter().map(|r| r.media.clone()).collect()),
+                history: PlayHistory::new(config.clone(), history, false),
+                starred: Starred::new(config.clone(), false),
                 now_playing: NowPlaying::new(false, config.clone()),
                 help: Help::new(config.clone()),
+                offline: config.features.cache.offline,
+                stream_profile: config.features.streaming.initial(),
                 message: (false, {
                     if let Some(s) = config.global.find_action_str(TargetedAction::OpenHelp) {
                         format!("Welcome to Ampterm. Press {} at any point to see all of its keybindings.", s)
@@ -215,9 +291,28 @@ impl MainScreen {
             Action::Multiple(vec![action, Action::ChangeMode(Mode::Normal)]),
         )
     }
+    pub fn get_queue_state(&self) -> (Vec<Media>, QueueCursor) {
+        self.playqueue.get_state()
+    }
+    pub fn restore_queue(&mut self, state: QueueState) -> Action {
+        self.playqueue.restore(state)
+    }
     fn update_focus(&mut self) {
-        self.pl_list
-            .set_enabled(self.state == CurrentlySelected::PlaylistList);
+        self.pl_list.set_enabled(
+            self.state == CurrentlySelected::PlaylistList && self.library == Library::Playlists,
+        );
+        self.album_list.set_enabled(
+            self.state == CurrentlySelected::PlaylistList && self.library == Library::Albums,
+        );
+        self.artist_list.set_enabled(
+            self.state == CurrentlySelected::PlaylistList && self.library == Library::Artists,
+        );
+        self.history.set_enabled(
+            self.state == CurrentlySelected::PlaylistList && self.library == Library::History,
+        );
+        self.starred.set_enabled(
+            self.state == CurrentlySelected::PlaylistList && self.library == Library::Starred,
+        );
         self.pl_queue
             .set_enabled(self.state == CurrentlySelected::PlaylistQueue);
         self.playqueue
@@ -248,7 +343,13 @@ impl Renderable for MainScreen {
         let areas = vertical.split(area);
         let listareas = horizontal.split(areas[1]);
         let text_areas = text_layout.split(areas[3]);
-        self.pl_list.draw(frame, listareas[0]);
+        match &self.library {
+            Library::Playlists => self.pl_list.draw(frame, listareas[0]),
+            Library::Albums => self.album_list.draw(frame, listareas[0]),
+            Library::Artists => self.artist_list.draw(frame, listareas[0]),
+            Library::History => self.history.draw(frame, listareas[0]),
+            Library::Starred => self.starred.draw(frame, listareas[0]),
+        }
         self.pl_queue.draw(frame, listareas[1]);
         self.playqueue.draw(frame, listareas[2]);
 
@@ -267,10 +368,19 @@ impl Renderable for MainScreen {
             Popup::Tasks => self.tasks.draw(frame, area),
             Popup::Help => self.help.draw(frame, area),
             Popup::PlaylistInfo(comp) => comp.draw(frame, area),
+            Popup::ImportReport(comp) => comp.draw(frame, area),
             Popup::MediaInfo(comp) => comp.draw(frame, area),
             Popup::SelectPlaylist(comp) => comp.draw(frame, area),
             Popup::Filtering(comp) => comp.draw(frame, area),
             Popup::Searching(comp) => comp.draw(frame, area),
+            Popup::Prompt(comp) => comp.draw(frame, area),
+            Popup::Confirm(comp) => comp.draw(frame, area),
+            Popup::RandomMix(comp) => comp.draw(frame, area),
+            Popup::GlobalSearch => {
+                if let Some(comp) = &mut self.global_search {
+                    comp.draw(frame, area)
+                }
+            }
         }
 
         frame.render_widget(
@@ -323,19 +433,35 @@ impl HandleQuery for MainScreen {
         self.tasks.update_task(&ticket, &res);
         match dest {
             CompID::PlaylistList => self.pl_list.handle_query(dest, ticket, res),
+            CompID::AlbumList => self.album_list.handle_query(dest, ticket, res),
+            CompID::ArtistList => self.artist_list.handle_query(dest, ticket, res),
             CompID::PlaylistQueue => self.pl_queue.handle_query(dest, ticket, res),
             CompID::NowPlaying | CompID::Lyrics | CompID::ImageComp => {
                 self.now_playing.handle_query(dest, ticket, res)
             }
             CompID::PlayQueue => self.playqueue.handle_query(dest, ticket, res),
+            CompID::Starred => self.starred.handle_query(dest, ticket, res),
+            CompID::GlobalSearch => self
+                .global_search
+                .as_mut()
+                .and_then(|comp| comp.handle_query(dest, ticket, res)),
             CompID::MainScreen => {
                 if let QueryStatus::Finished(body) = res {
                     match body {
-                        ResponseType::UpdatePlaylist(Ok(())) => {
+                        ResponseType::UpdatePlaylist(Ok(()))
+                        | ResponseType::CreatePlaylist(Ok(()))
+                        | ResponseType::DeletePlaylist(Ok(()))
+                        | ResponseType::SavePlaylist(Ok(())) => {
                             return Some(Action::ToQuery(ToQueryWorker::new(
                                 HighLevelQuery::ListPlaylists,
                             )))
                         }
+                        ResponseType::UpdatePlaylist(Err(err))
+                        | ResponseType::CreatePlaylist(Err(err))
+                        | ResponseType::DeletePlaylist(Err(err))
+                        | ResponseType::SavePlaylist(Err(err)) => {
+                            self.message = (true, err);
+                        }
                         ResponseType::GetPlaylists(pl) => {
                             match pl {
                                 Ok(p) => {
@@ -350,6 +476,70 @@ impl HandleQuery for MainScreen {
                                 }
                             };
                         }
+                        ResponseType::Download(Ok(count)) => {
+                            self.message = (
+                                false,
+                                format!(
+                                    "Downloaded {count} files to {}",
+                                    self.config.features.download.dir.display()
+                                ),
+                            );
+                        }
+                        ResponseType::Download(Err(err)) => {
+                            self.message = (true, format!("Download failed: {err}"));
+                        }
+                        ResponseType::ExportPlaylist(Ok(path)) => {
+                            self.message = (false, format!("Exported to {}", path.display()));
+                        }
+                        ResponseType::ExportPlaylist(Err(err)) => {
+                            self.message = (true, format!("Export failed: {err}"));
+                        }
+                        ResponseType::ImportPlaylist(Ok(result)) => {
+                            if result.unmatched.is_empty() {
+                                self.message = (
+                                    false,
+                                    format!("Imported {} items as {}", result.count, result.name),
+                                );
+                            } else {
+                                self.popup = Popup::ImportReport(ImportReport::new(
+                                    result,
+                                    self.config.local.popup.clone(),
+                                ));
+                            }
+                            return Some(Action::ToQuery(ToQueryWorker::new(
+                                HighLevelQuery::ListPlaylists,
+                            )));
+                        }
+                        ResponseType::ImportPlaylist(Err(err)) => {
+                            self.message = (true, format!("Import failed: {err}"));
+                        }
+                        // User may close the popup before the request is finished
+                        ResponseType::GetGenres(Ok(genres)) => {
+                            if let Popup::RandomMix(popup) = &mut self.popup {
+                                popup.update_genres(genres);
+                            }
+                        }
+                        ResponseType::GetMusicFolders(Ok(folders)) => {
+                            if let Popup::RandomMix(popup) = &mut self.popup {
+                                popup.update_folders(folders);
+                            }
+                        }
+                        ResponseType::GetGenres(Err(err))
+                        | ResponseType::GetMusicFolders(Err(err)) => {
+                            self.message = (true, err);
+                        }
+                        ResponseType::RandomMix(Ok((items, _))) if items.is_empty() => {
+                            self.message = (false, "No media matched the random mix".to_string());
+                        }
+                        ResponseType::RandomMix(Ok((items, location))) => {
+                            self.message = (false, format!("Adding {} random media", items.len()));
+                            return Some(Action::Targeted(TargetedAction::Queue(
+                                QueueAction::Add(items, location),
+                            )));
+                        }
+                        ResponseType::RandomMix(Err(err)) => {
+                            self.message = (true, format!("Random mix failed: {err}"));
+                        }
                         _ => {}
                     }
                 };
@@ -370,6 +560,7 @@ impl HandleAction for MainScreen {
                         vec![media.id],
                         self.config.local.select_playlist_popup.clone(),
                         format!("Add {} to a playlist", media.title),
+                        false,
                     );
                     self.popup = Popup::SelectPlaylist(popup);
                     Some(action)
@@ -383,6 +574,18 @@ impl HandleAction for MainScreen {
                     list,
                     self.config.local.select_playlist_popup.clone(),
                     format!("Add {} items to a playlist", len),
+                    false,
+                );
+                self.popup = Popup::SelectPlaylist(popup);
+                Some(action)
+            }
+            TargetedAction::PrepareSaveAsPlaylist(list) => {
+                let len = list.len();
+                let (popup, action) = SelectPlaylistPopup::new(
+                    list,
+                    self.config.local.select_playlist_popup.clone(),
+                    format!("Save {} items as a playlist", len),
+                    true,
                 );
                 self.popup = Popup::SelectPlaylist(popup);
                 Some(action)
@@ -394,9 +597,46 @@ impl HandleAction for MainScreen {
                 ));
                 None
             }
+            TargetedAction::EditPlaylist(edit) => {
+                self.popup = match edit {
+                    PlaylistEdit::Delete(p) => Popup::Confirm(Confirm::new(
+                        format!("Delete playlist {}?", p.name),
+                        Action::ToQuery(ToQueryWorker::new(HighLevelQuery::DeletePlaylist(
+                            GetPlaylistParams {
+                                name: p.name,
+                                id: p.id,
+                            },
+                        ))),
+                    )),
+                    PlaylistEdit::Replace { id, name, items } => Popup::Confirm(Confirm::new(
+                        format!("Replace everything in {} with {} items?", name, items.len()),
+                        Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SavePlaylist(
+                            SavePlaylistParams {
+                                target: SaveTarget::Existing { id, name },
+                                items,
+                            },
+                        ))),
+                    )),
+                    edit => Popup::Prompt(Prompt::new(edit)),
+                };
+                Some(Action::ChangeMode(Mode::Insert))
+            }
+            TargetedAction::SetPlaylistInfo {
+                playlist,
+                name,
+                comment,
+                public,
+            } => self.pl_list.set_info(playlist, name, comment, public),
             TargetedAction::ViewMediaInfo(media) => {
-                self.popup =
-                    Popup::MediaInfo(MediaInfo::new(media, self.config.local.popup.clone()));
+                self.popup = Popup::MediaInfo(MediaInfo::new(
+                    media,
+                    self.config
+                        .features
+                        .streaming
+                        .profiles
+                        .get(self.stream_profile),
+                    self.config.local.popup.clone(),
+                ));
                 None
             }
             TargetedAction::ToggleHelp => {
@@ -418,7 +658,68 @@ impl HandleAction for MainScreen {
             TargetedAction::Queue(_)
             | TargetedAction::Skip
             | TargetedAction::Previous
-            | TargetedAction::Shuffle => self.playqueue.handle_action(action),
+            | TargetedAction::Shuffle
+            | TargetedAction::CycleRepeat
+            | TargetedAction::SetRepeat(_)
+            | TargetedAction::CyclePlayOrder
+            | TargetedAction::SetPlayOrder(_)
+            | TargetedAction::ResumeRemoteQueue
+            | TargetedAction::ToggleRadio
+            | TargetedAction::CycleRadioSource
+            | TargetedAction::SetRadioSource(_) => self.playqueue.handle_action(action),
+            TargetedAction::ToggleOffline => {
+                self.offline = !self.offline;
+                let mut actions = vec![
+                    Action::ToPlayer(ToPlayerWorker::SetOffline(self.offline)),
+                    Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SetOffline(self.offline))),
+                    Action::Targeted(TargetedAction::Info(format!(
+                        "Offline mode {}",
+                        if self.offline { "enabled" } else { "disabled" }
+                    ))),
+                    self.playqueue.set_offline(self.offline),
+                ];
+                if let Some(a) = self.pl_queue.set_offline(self.offline) {
+                    actions.push(a);
+                }
+                // Lists from the server are hidden in offline mode, and fetched again once it is
+                // disabled
+                actions.push(Action::ToQuery(ToQueryWorker::new(
+                    HighLevelQuery::ListPlaylists,
+                )));
+                actions.extend(
+                    [
+                        self.album_list.reload(),
+                        self.artist_list.reload(),
+                        self.starred.reload(),
+                    ]
+                    .into_iter()
+                    .flatten(),
+                );
+                Some(Action::Multiple(actions))
+            }
+            TargetedAction::CycleStreamProfile => {
+                let profiles = &self.config.features.streaming.profiles;
+                if profiles.is_empty() {
+                    return None;
+                }
+                self.stream_profile = (self.stream_profile + 1) % profiles.len();
+                let profile = profiles[self.stream_profile].clone();
+                Some(Action::Multiple(vec![
+                    Action::Targeted(TargetedAction::Info(format!(
+                        "Streaming profile: {}",
+                        profile
+                    ))),
+                    Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SetStreamProfile(
+                        profile,
+                    ))),
+                    // The next media may have been loaded with the previous profile
+                    self.playqueue.reload_next(),
+                ]))
+            }
+            TargetedAction::PlayModeChanged { repeat, order } => {
+                self.now_playing.set_play_mode(repeat, order);
+                None
+            }
             TargetedAction::WindowUp | TargetedAction::WindowDown => {
                 self.state = match &self.state {
                     CurrentlySelected::PlaylistList => {
@@ -479,10 +780,39 @@ impl HandleAction for MainScreen {
                 }
             }
             TargetedAction::FocusPlaylistList => {
+                self.library = Library::Playlists;
                 self.state = CurrentlySelected::PlaylistList;
                 self.update_focus();
                 None
             }
+            TargetedAction::FocusAlbumList => {
+                self.library = Library::Albums;
+                self.state = CurrentlySelected::PlaylistList;
+                self.update_focus();
+                self.album_list.init()
+            }
+            TargetedAction::FocusArtistList => {
+                self.library = Library::Artists;
+                self.state = CurrentlySelected::PlaylistList;
+                self.update_focus();
+                self.artist_list.init()
+            }
+            TargetedAction::FocusHistory => {
+                self.library = Library::History;
+                self.state = CurrentlySelected::PlaylistList;
+                self.update_focus();
+                None
+            }
+            TargetedAction::FocusStarred => {
+                self.library = Library::Starred;
+                self.state = CurrentlySelected::PlaylistList;
+                self.update_focus();
+                self.starred.init()
+            }
+            TargetedAction::RecordPlay(record) => {
+                self.history.push(record);
+                None
+            }
             TargetedAction::FocusPlaylistQueue => {
                 self.state = CurrentlySelected::PlaylistQueue;
                 self.update_focus();
@@ -508,6 +838,9 @@ impl HandleAction for MainScreen {
             TargetedAction::OpenFilter => {
                 let applicable = match &self.state {
                     CurrentlySelected::PlaylistQueue => self.pl_queue.init_filter(),
+                    CurrentlySelected::PlaylistList if self.library == Library::History => {
+                        self.history.init_filter()
+                    }
                     _ => false,
                 };
                 if applicable {
@@ -521,6 +854,9 @@ impl HandleAction for MainScreen {
                 self.popup = Popup::None;
                 match &self.state {
                     CurrentlySelected::PlaylistQueue => self.pl_queue.set_filter("".to_string()),
+                    CurrentlySelected::PlaylistList if self.library == Library::History => {
+                        self.history.set_filter("".to_string())
+                    }
                     _ => {}
                 };
                 Some(Action::ChangeMode(Mode::Normal))
@@ -529,6 +865,9 @@ impl HandleAction for MainScreen {
                 self.popup = Popup::None;
                 match &self.state {
                     CurrentlySelected::PlaylistQueue => self.pl_queue.set_filter(filter),
+                    CurrentlySelected::PlaylistList if self.library == Library::History => {
+                        self.history.set_filter(filter)
+                    }
                     _ => {}
                 };
                 Some(Action::ChangeMode(Mode::Normal))
@@ -541,6 +880,9 @@ impl HandleAction for MainScreen {
                     CurrentlySelected::PlaylistQueue => {
                         self.pl_queue.test_search(search.clone(), stype.clone())
                     }
+                    CurrentlySelected::PlaylistList if self.library == Library::History => {
+                        self.history.test_search(search.clone(), stype.clone())
+                    }
                     _ => {}
                 };
                 if stype == SearchType::Normal {
@@ -557,13 +899,32 @@ impl HandleAction for MainScreen {
                     CurrentlySelected::PlaylistQueue => self
                         .pl_queue
                         .test_search("".to_string(), SearchType::Revert),
+                    CurrentlySelected::PlaylistList if self.library == Library::History => {
+                        self.history.test_search("".to_string(), SearchType::Revert)
+                    }
                     _ => {}
                 };
                 Some(Action::ChangeMode(Mode::Normal))
             }
+            TargetedAction::OpenGlobalSearch => {
+                let comp = self
+                    .global_search
+                    .get_or_insert_with(|| GlobalSearch::new(self.config.clone()));
+                let mode = comp.get_mode();
+                self.popup = Popup::GlobalSearch;
+                Some(Action::ChangeMode(mode))
+            }
+            TargetedAction::OpenRandomMix => {
+                let (popup, action) = RandomMix::new();
+                self.popup = Popup::RandomMix(popup);
+                Some(action)
+            }
             TargetedAction::OpenSearch => {
                 let applicable = match &self.state {
                     CurrentlySelected::PlaylistQueue => self.pl_queue.init_search(),
+                    CurrentlySelected::PlaylistList if self.library == Library::History => {
+                        self.history.init_search()
+                    }
                     _ => false,
                 };
                 if applicable {
@@ -0,0 +1,198 @@
+mod error;
+mod loaded;
+
+use crate::{
+    action::action::Action,
+    compid::CompID,
+    components::{
+        home::mainscreen::albumlist::{error::Error, loaded::Loaded},
+        lib::centered::Centered,
+        traits::{
+            focusable::Focusable,
+            handlekeyseq::{ComponentKeyHelp, HandleKeySeq, KeySeqResult, PassKeySeq},
+            handlequery::HandleQuery,
+            renderable::Renderable,
+        },
+    },
+    config::Config,
+    osclient::types::AlbumListType,
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{
+            getalbumlist::{GetAlbumListParams, GetAlbumListResponse},
+            QueryStatus, ResponseType, ToQueryWorker,
+        },
+    },
+};
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    text::Span,
+    widgets::Block,
+    Frame,
+};
+
+/// Number of albums fetched with a single request
+const PAGE_SIZE: usize = 100;
+
+enum Comp {
+    Error(Error),
+    Loaded(Box<Loaded>),
+    Loading(Centered),
+}
+
+pub struct AlbumList {
+    comp: Comp,
+    enabled: bool,
+    config: Config,
+    list_type: AlbumListType,
+    /// Albums are not fetched until the user opens the album list for the first time
+    initialised: bool,
+    /// Ticket of the most recent list request. Responses to older requests are ignored.
+    pending: Option<usize>,
+}
+
+impl AlbumList {
+    pub fn new(config: Config, enabled: bool) -> Self {
+        Self {
+            comp: Comp::Loading(Centered::new(vec!["Loading...".to_string()])),
+            enabled,
+            config,
+            list_type: AlbumListType::Newest,
+            initialised: false,
+            pending: None,
+        }
+    }
+
+    /// Returns the query that fetches the first page of albums if it has not been sent yet
+    pub fn init(&mut self) -> Option<Action> {
+        if self.initialised {
+            None
+        } else {
+            self.initialised = true;
+            Some(Action::ToQuery(Self::list_query(self.list_type.clone(), 0)))
+        }
+    }
+
+    /// Fetches the first page again if the list has been fetched before
+    pub fn reload(&self) -> Option<Action> {
+        self.initialised
+            .then(|| Action::ToQuery(Self::list_query(self.list_type.clone(), 0)))
+    }
+
+    pub fn list_query(list_type: AlbumListType, offset: usize) -> ToQueryWorker {
+        ToQueryWorker::new(HighLevelQuery::ListAlbums(GetAlbumListParams {
+            list_type,
+            size: PAGE_SIZE,
+            offset,
+        }))
+    }
+
+    fn gen_block(&self) -> Block<'static> {
+        let style = if self.enabled {
+            Style::new().white()
+        } else {
+            Style::new().dark_gray()
+        };
+        let title = Span::styled(
+            format!("Album ({})", self.list_type),
+            if self.enabled {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            },
+        );
+        Block::bordered().title(title).border_style(style)
+    }
+}
+
+impl Renderable for AlbumList {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let block = self.gen_block();
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        match &mut self.comp {
+            Comp::Error(error) => error.draw(frame, inner),
+            Comp::Loaded(loaded) => loaded.draw(frame, inner),
+            Comp::Loading(loading) => loading.draw(frame, inner),
+        }
+    }
+}
+
+impl HandleQuery for AlbumList {
+    fn handle_query(&mut self, dest: CompID, ticket: usize, res: QueryStatus) -> Option<Action> {
+        match res {
+            QueryStatus::Requested(HighLevelQuery::ListAlbums(params)) => {
+                self.pending = Some(ticket);
+                // Fetching the next page keeps the current list visible
+                if params.offset == 0 {
+                    self.list_type = params.list_type;
+                    self.comp = Comp::Loading(Centered::new(vec!["Loading...".to_string()]));
+                }
+                None
+            }
+            QueryStatus::Finished(ResponseType::GetAlbumList(GetAlbumListResponse {
+                params,
+                albums,
+            })) => {
+                if self.pending != Some(ticket) {
+                    return None;
+                }
+                self.pending = None;
+                match albums {
+                    Ok(albums) => {
+                        let exhausted = albums.len() < params.size;
+                        if let (Comp::Loaded(c), true) = (&mut self.comp, params.offset != 0) {
+                            c.append_rows(albums, exhausted);
+                        } else {
+                            self.comp = Comp::Loaded(Box::new(Loaded::new(
+                                self.config.clone(),
+                                params.list_type,
+                                albums,
+                                exhausted,
+                            )));
+                        }
+                    }
+                    Err(error) => {
+                        self.comp =
+                            Comp::Error(Error::new(self.config.clone(), params.list_type, error));
+                    }
+                }
+                None
+            }
+            _ => {
+                if let Comp::Loaded(comp) = &mut self.comp {
+                    comp.handle_query(dest, ticket, res)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl PassKeySeq for AlbumList {
+    fn get_help(&self) -> Vec<ComponentKeyHelp> {
+        match &self.comp {
+            Comp::Loaded(comp) => comp.get_help(),
+            Comp::Error(comp) => comp.get_help(),
+            Comp::Loading(_) => vec![],
+        }
+    }
+    fn handle_key_seq(&mut self, keyseq: &Vec<KeyEvent>) -> Option<KeySeqResult> {
+        match &mut self.comp {
+            Comp::Loaded(comp) => comp.handle_key_seq(keyseq),
+            Comp::Error(comp) => comp.handle_key_seq(keyseq),
+            Comp::Loading(_) => None,
+        }
+    }
+}
+
+impl Focusable for AlbumList {
+    fn set_enabled(&mut self, enable: bool) {
+        if self.enabled != enable {
+            self.enabled = enable;
+        };
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    action::{action::Action, localaction::AlbumListAction},
+    components::{
+        home::mainscreen::albumlist::AlbumList,
+        lib::centered::Centered,
+        traits::{
+            handlekeyseq::{HandleKeySeq, KeySeqResult},
+            renderable::Renderable,
+        },
+    },
+    config::{keybindings::KeyBindings, Config},
+    osclient::types::AlbumListType,
+};
+use ratatui::{layout::Rect, Frame};
+
+pub struct Error {
+    list_type: AlbumListType,
+    comp: Centered,
+    binds: KeyBindings<AlbumListAction>,
+}
+
+impl Error {
+    pub fn new(config: Config, list_type: AlbumListType, error: String) -> Self {
+        let mut msg = vec!["Error!".to_string(), error];
+        if let Some(keyseq) = config
+            .local
+            .albumlist
+            .find_action_str(AlbumListAction::Refresh)
+        {
+            msg.push(format!("Reload with {}", keyseq));
+        }
+        Self {
+            list_type,
+            comp: Centered::new(msg),
+            binds: config.local.albumlist,
+        }
+    }
+}
+
+impl Renderable for Error {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        self.comp.draw(frame, area)
+    }
+}
+
+impl HandleKeySeq<AlbumListAction> for Error {
+    fn get_name(&self) -> &str {
+        "AlbumList"
+    }
+    fn handle_local_action(&mut self, action: AlbumListAction) -> KeySeqResult {
+        let list_type = match action {
+            AlbumListAction::Refresh => self.list_type.clone(),
+            AlbumListAction::NextListType => self.list_type.next(),
+            AlbumListAction::PrevListType => self.list_type.prev(),
+            AlbumListAction::SetListType(t) => t,
+            _ => return KeySeqResult::NoActionNeeded,
+        };
+        KeySeqResult::ActionNeeded(Action::ToQuery(AlbumList::list_query(list_type, 0)))
+    }
+
+    fn get_keybinds(&self) -> &KeyBindings<AlbumListAction> {
+        &self.binds
+    }
+}
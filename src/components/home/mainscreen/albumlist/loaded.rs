@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use crate::{
+    action::{
+        action::{Action, QueueAction, TargetedAction},
+        localaction::AlbumListAction,
+    },
+    compid::CompID,
+    components::{
+        home::mainscreen::albumlist::AlbumList,
+        lib::{scrollbar::ScrollBar, visualtable::VisualTable},
+        traits::{
+            handlekeyseq::{ComponentKeyHelp, HandleKeySeq, KeySeqResult},
+            handlequery::HandleQuery,
+            renderable::Renderable,
+        },
+    },
+    config::{keybindings::KeyBindings, Config},
+    osclient::{response::getalbumlist2::SimpleAlbum, types::AlbumListType},
+    playerworker::player::QueueLocation,
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{
+            getalbum::{GetAlbumParams, GetAlbumResponse},
+            QueryStatus, ResponseType, ToQueryWorker,
+        },
+    },
+};
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    widgets::{Row, Table},
+    Frame,
+};
+use tracing::error;
+
+pub struct Loaded {
+    autofocus: bool,
+    table: VisualTable,
+    keymap: KeyBindings<AlbumListAction>,
+    list_type: AlbumListType,
+    list: Vec<SimpleAlbum>,
+    /// True if the server has no more albums to give
+    exhausted: bool,
+    callback: HashMap<usize, (QueueLocation, bool)>,
+    bar: ScrollBar,
+}
+
+impl Loaded {
+    fn select_album(&self) -> Option<Action> {
+        let pos = self.table.get_current()?;
+        let query = Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SelectAlbum(
+            GetAlbumParams {
+                name: self.list[pos].name.clone(),
+                id: self.list[pos].id.clone(),
+            },
+        )));
+        if self.autofocus {
+            Some(Action::Multiple(vec![
+                query,
+                Action::Targeted(TargetedAction::FocusPlaylistQueue),
+            ]))
+        } else {
+            Some(query)
+        }
+    }
+
+    /// This needs to be a function not tied to &self because it needs to be used by [`Self::new`]
+    fn gen_rows(items: &[SimpleAlbum]) -> Vec<Row<'static>> {
+        items
+            .iter()
+            .map(|item| {
+                Row::new(vec![
+                    item.name.clone(),
+                    item.artist.clone().unwrap_or("Unknown".to_string()),
+                    item.year.map(|y| y.to_string()).unwrap_or_default(),
+                ])
+            })
+            .collect()
+    }
+
+    /// Adds the next page of albums to the end of the list
+    pub fn append_rows(&mut self, mut items: Vec<SimpleAlbum>, exhausted: bool) {
+        let pos = self.list.len();
+        let len = items.len();
+        self.list.append(&mut items);
+        self.exhausted = exhausted;
+        self.table.add_rows_at(Self::gen_rows(&self.list), pos, len);
+        self.bar.update_max(self.list.len() as u32);
+    }
+
+    pub fn new(
+        config: Config,
+        list_type: AlbumListType,
+        list: Vec<SimpleAlbum>,
+        exhausted: bool,
+    ) -> Self {
+        fn table_proc(table: Table<'static>) -> Table<'static> {
+            table
+                .highlight_symbol(">")
+                .row_highlight_style(Style::new().reversed())
+        }
+        let rows = Self::gen_rows(&list);
+        let table = VisualTable::new(
+            config.clone(),
+            rows,
+            [
+                Constraint::Ratio(3, 5),
+                Constraint::Ratio(2, 5),
+                Constraint::Length(4),
+            ]
+            .to_vec(),
+            table_proc,
+        );
+        let len = list.len();
+        Self {
+            list,
+            list_type,
+            exhausted,
+            table,
+            callback: HashMap::new(),
+            autofocus: config.behaviour.auto_focus,
+            keymap: config.local.albumlist.clone(),
+            bar: ScrollBar::new(len as u32, 0),
+        }
+    }
+
+    pub fn add_to_queue(&mut self, ql: QueueLocation, randomise: bool) -> Option<Action> {
+        let pos = self.table.get_current()?;
+        let req = ToQueryWorker::new(HighLevelQuery::AddAlbumToQueue(GetAlbumParams {
+            name: self.list[pos].name.clone(),
+            id: self.list[pos].id.clone(),
+        }));
+        self.callback.insert(req.ticket, (ql, randomise));
+        Some(Action::ToQuery(req))
+    }
+}
+
+impl Renderable for Loaded {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let [list, bar] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        self.table.draw(frame, list);
+        self.bar.draw(frame, bar);
+    }
+}
+
+impl HandleQuery for Loaded {
+    fn handle_query(&mut self, _dest: CompID, ticket: usize, res: QueryStatus) -> Option<Action> {
+        if let QueryStatus::Finished(ResponseType::GetAlbum(res)) = res {
+            if let Some((ql, randomise)) = self.callback.remove(&ticket) {
+                match res {
+                    GetAlbumResponse::Success(full_album) => {
+                        return Some(Action::Targeted(TargetedAction::Queue(if randomise {
+                            QueueAction::RandomAdd(full_album.song, ql)
+                        } else {
+                            QueueAction::Add(full_album.song, ql)
+                        })));
+                    }
+                    GetAlbumResponse::Failure {
+                        id: _,
+                        name: _,
+                        msg,
+                    } => {
+                        error!("Failed to add album to queue: {msg}");
+                    }
+                }
+            }
+        };
+        None
+    }
+}
+
+impl HandleKeySeq<AlbumListAction> for Loaded {
+    fn get_other_helps(&self) -> Vec<ComponentKeyHelp> {
+        self.table.get_help()
+    }
+    fn get_name(&self) -> &str {
+        "AlbumList"
+    }
+    fn pass_to_lower_comp(&mut self, keyseq: &Vec<KeyEvent>) -> Option<KeySeqResult> {
+        let res = self.table.handle_key_seq(keyseq);
+        self.bar
+            .update_pos(self.table.get_current().unwrap_or(0) as u32);
+        res
+    }
+    fn handle_local_action(&mut self, action: AlbumListAction) -> KeySeqResult {
+        let res = match action {
+            AlbumListAction::Add(pos) => self.add_to_queue(pos, false),
+            AlbumListAction::RandomAdd(pos) => self.add_to_queue(pos, true),
+            AlbumListAction::ViewSelected => self.select_album(),
+            AlbumListAction::Refresh => Some(Action::ToQuery(AlbumList::list_query(
+                self.list_type.clone(),
+                0,
+            ))),
+            AlbumListAction::LoadMore => {
+                if self.exhausted {
+                    Some(Action::Targeted(TargetedAction::Info(
+                        "All albums have been loaded.".to_string(),
+                    )))
+                } else {
+                    Some(Action::ToQuery(AlbumList::list_query(
+                        self.list_type.clone(),
+                        self.list.len(),
+                    )))
+                }
+            }
+            AlbumListAction::NextListType => Some(Action::ToQuery(AlbumList::list_query(
+                self.list_type.next(),
+                0,
+            ))),
+            AlbumListAction::PrevListType => Some(Action::ToQuery(AlbumList::list_query(
+                self.list_type.prev(),
+                0,
+            ))),
+            AlbumListAction::SetListType(t) => Some(Action::ToQuery(AlbumList::list_query(t, 0))),
+        };
+        match res {
+            Some(a) => KeySeqResult::ActionNeeded(a),
+            None => KeySeqResult::NoActionNeeded,
+        }
+    }
+
+    fn get_keybinds(&self) -> &KeyBindings<AlbumListAction> {
+        &self.keymap
+    }
+}
@@ -0,0 +1,237 @@
+mod albums;
+mod index;
+
+use crate::{
+    action::{action::Action, localaction::ArtistListAction},
+    compid::CompID,
+    components::{
+        home::mainscreen::artistlist::{albums::Albums, index::Index},
+        lib::centered::Centered,
+        traits::{
+            focusable::Focusable,
+            handlekeyseq::{ComponentKeyHelp, HandleKeySeq, KeySeqResult, PassKeySeq},
+            handlequery::HandleQuery,
+            renderable::Renderable,
+        },
+    },
+    config::Config,
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{getartist::GetArtistResponse, QueryStatus, ResponseType, ToQueryWorker},
+    },
+};
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    text::Span,
+    widgets::Block,
+    Frame,
+};
+
+enum Comp {
+    Error(Centered),
+    Loaded(Box<Index>),
+    Loading(Centered),
+}
+
+/// Artist the user has opened from the index
+enum Drilled {
+    Loading(String, usize),
+    Error(String, String),
+    Loaded(Box<Albums>),
+}
+
+pub struct ArtistList {
+    comp: Comp,
+    drilled: Option<Drilled>,
+    enabled: bool,
+    config: Config,
+    /// Artists are not fetched until the user opens the artist list for the first time
+    initialised: bool,
+}
+
+impl ArtistList {
+    pub fn new(config: Config, enabled: bool) -> Self {
+        Self {
+            comp: Comp::Loading(Centered::new(vec!["Loading...".to_string()])),
+            drilled: None,
+            enabled,
+            config,
+            initialised: false,
+        }
+    }
+
+    /// Returns the query that fetches the artist index if it has not been sent yet
+    pub fn init(&mut self) -> Option<Action> {
+        if self.initialised {
+            None
+        } else {
+            self.initialised = true;
+            Some(Action::ToQuery(ToQueryWorker::new(
+                HighLevelQuery::ListArtists,
+            )))
+        }
+    }
+
+    /// Fetches the artist index again if it has been fetched before, leaving the albums of the
+    /// artist being shown
+    pub fn reload(&mut self) -> Option<Action> {
+        if !self.initialised {
+            return None;
+        }
+        self.drilled = None;
+        Some(Action::ToQuery(ToQueryWorker::new(
+            HighLevelQuery::ListArtists,
+        )))
+    }
+
+    fn gen_block(&self) -> Block<'static> {
+        let style = if self.enabled {
+            Style::new().white()
+        } else {
+            Style::new().dark_gray()
+        };
+        let title = match &self.drilled {
+            Some(Drilled::Loading(name, _)) | Some(Drilled::Error(name, _)) => {
+                format!("Artist: {}", name)
+            }
+            Some(Drilled::Loaded(albums)) => format!("Artist: {}", albums.get_name()),
+            None => "Artist".to_string(),
+        };
+        let title = Span::styled(
+            title,
+            if self.enabled {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            },
+        );
+        Block::bordered().title(title).border_style(style)
+    }
+
+    fn error_msg(&self, error: String) -> Centered {
+        let mut msg = vec!["Error!".to_string(), error];
+        if let Some(keyseq) = self
+            .config
+            .local
+            .artistlist
+            .find_action_str(ArtistListAction::Refresh)
+        {
+            msg.push(format!("Reload with {}", keyseq));
+        }
+        Centered::new(msg)
+    }
+}
+
+impl Renderable for ArtistList {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let block = self.gen_block();
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if let Some(Drilled::Error(_, error)) = &self.drilled {
+            self.error_msg(error.clone()).draw(frame, inner);
+            return;
+        }
+        match &mut self.drilled {
+            Some(Drilled::Loading(_, _)) => {
+                Centered::new(vec!["Loading...".to_string()]).draw(frame, inner)
+            }
+            Some(Drilled::Error(_, _)) => {}
+            Some(Drilled::Loaded(albums)) => albums.draw(frame, inner),
+            None => match &mut self.comp {
+                Comp::Error(error) => error.draw(frame, inner),
+                Comp::Loaded(loaded) => loaded.draw(frame, inner),
+                Comp::Loading(loading) => loading.draw(frame, inner),
+            },
+        }
+    }
+}
+
+impl HandleQuery for ArtistList {
+    fn handle_query(&mut self, dest: CompID, ticket: usize, res: QueryStatus) -> Option<Action> {
+        match res {
+            QueryStatus::Finished(ResponseType::GetArtists(res)) => {
+                match res {
+                    Ok(index) => {
+                        if let Comp::Loaded(c) = &mut self.comp {
+                            c.set_rows(index);
+                        } else {
+                            self.comp =
+                                Comp::Loaded(Box::new(Index::new(self.config.clone(), index)));
+                        }
+                    }
+                    Err(error) => self.comp = Comp::Error(self.error_msg(error)),
+                };
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SelectArtist(params)) => {
+                self.drilled = Some(Drilled::Loading(params.name, ticket));
+                None
+            }
+            QueryStatus::Finished(ResponseType::GetArtist(res)) => {
+                if let Some(Drilled::Loading(_, t)) = &self.drilled {
+                    if *t == ticket {
+                        self.drilled = Some(match res {
+                            GetArtistResponse::Success(artist) => {
+                                Drilled::Loaded(Box::new(Albums::new(self.config.clone(), artist)))
+                            }
+                            GetArtistResponse::Failure { id: _, name, msg } => {
+                                Drilled::Error(name, msg)
+                            }
+                        });
+                    }
+                }
+                None
+            }
+            _ => {
+                if let Some(Drilled::Loaded(albums)) = &mut self.drilled {
+                    albums.handle_query(dest, ticket, res)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl PassKeySeq for ArtistList {
+    fn get_help(&self) -> Vec<ComponentKeyHelp> {
+        match &self.drilled {
+            Some(Drilled::Loaded(albums)) => albums.get_help(),
+            Some(_) => vec![],
+            None => match &self.comp {
+                Comp::Loaded(comp) => comp.get_help(),
+                _ => vec![],
+            },
+        }
+    }
+    fn handle_key_seq(&mut self, keyseq: &Vec<KeyEvent>) -> Option<KeySeqResult> {
+        let action = self.config.local.artistlist.get(keyseq);
+        if self.drilled.is_some() && action == Some(&ArtistListAction::Back) {
+            self.drilled = None;
+            return Some(KeySeqResult::NoActionNeeded);
+        }
+        match &mut self.drilled {
+            Some(Drilled::Loaded(albums)) => albums.handle_key_seq(keyseq),
+            Some(_) => None,
+            None => match &mut self.comp {
+                Comp::Loaded(comp) => comp.handle_key_seq(keyseq),
+                Comp::Error(_) if action == Some(&ArtistListAction::Refresh) => {
+                    Some(KeySeqResult::ActionNeeded(Action::ToQuery(
+                        ToQueryWorker::new(HighLevelQuery::ListArtists),
+                    )))
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+impl Focusable for ArtistList {
+    fn set_enabled(&mut self, enable: bool) {
+        if self.enabled != enable {
+            self.enabled = enable;
+        };
+    }
+}
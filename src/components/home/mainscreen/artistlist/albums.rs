@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use crate::{
+    action::{
+        action::{Action, QueueAction, TargetedAction},
+        localaction::ArtistListAction,
+    },
+    compid::CompID,
+    components::{
+        lib::{scrollbar::ScrollBar, visualtable::VisualTable},
+        traits::{
+            handlekeyseq::{ComponentKeyHelp, HandleKeySeq, KeySeqResult},
+            handlequery::HandleQuery,
+            renderable::Renderable,
+        },
+    },
+    config::{keybindings::KeyBindings, Config},
+    osclient::response::{getalbumlist2::SimpleAlbum, getartist::FullArtist},
+    playerworker::player::QueueLocation,
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{
+            getalbum::{GetAlbumParams, GetAlbumResponse},
+            getartist::GetArtistParams,
+            QueryStatus, ResponseType, ToQueryWorker,
+        },
+    },
+};
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    widgets::{Row, Table},
+    Frame,
+};
+use tracing::error;
+
+/// Albums of a single artist
+pub struct Albums {
+    autofocus: bool,
+    artist: FullArtist,
+    table: VisualTable,
+    keymap: KeyBindings<ArtistListAction>,
+    callback: HashMap<usize, (QueueLocation, bool)>,
+    bar: ScrollBar,
+}
+
+impl Albums {
+    fn gen_rows(items: &[SimpleAlbum]) -> Vec<Row<'static>> {
+        items
+            .iter()
+            .map(|item| {
+                Row::new(vec![
+                    item.name.clone(),
+                    item.year.map(|y| y.to_string()).unwrap_or_default(),
+                ])
+            })
+            .collect()
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.artist.name
+    }
+
+    pub fn new(config: Config, artist: FullArtist) -> Self {
+        fn table_proc(table: Table<'static>) -> Table<'static> {
+            table
+                .highlight_symbol(">")
+                .row_highlight_style(Style::new().reversed())
+        }
+        let table = VisualTable::new(
+            config.clone(),
+            Self::gen_rows(&artist.album),
+            [Constraint::Fill(1), Constraint::Length(4)].to_vec(),
+            table_proc,
+        );
+        Self {
+            bar: ScrollBar::new(artist.album.len() as u32, 0),
+            artist,
+            table,
+            callback: HashMap::new(),
+            autofocus: config.behaviour.auto_focus,
+            keymap: config.local.artistlist,
+        }
+    }
+
+    fn select_album(&self) -> Option<Action> {
+        let pos = self.table.get_current()?;
+        let album = &self.artist.album[pos];
+        let query = Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SelectAlbum(
+            GetAlbumParams {
+                name: album.name.clone(),
+                id: album.id.clone(),
+            },
+        )));
+        if self.autofocus {
+            Some(Action::Multiple(vec![
+                query,
+                Action::Targeted(TargetedAction::FocusPlaylistQueue),
+            ]))
+        } else {
+            Some(query)
+        }
+    }
+
+    fn add_to_queue(&mut self, ql: QueueLocation, randomise: bool) -> Option<Action> {
+        let pos = self.table.get_current()?;
+        let album = &self.artist.album[pos];
+        let req = ToQueryWorker::new(HighLevelQuery::AddAlbumToQueue(GetAlbumParams {
+            name: album.name.clone(),
+            id: album.id.clone(),
+        }));
+        self.callback.insert(req.ticket, (ql, randomise));
+        Some(Action::ToQuery(req))
+    }
+}
+
+impl Renderable for Albums {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let [list, bar] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        self.table.draw(frame, list);
+        self.bar.draw(frame, bar);
+    }
+}
+
+impl HandleQuery for Albums {
+    fn handle_query(&mut self, _dest: CompID, ticket: usize, res: QueryStatus) -> Option<Action> {
+        if let QueryStatus::Finished(ResponseType::GetAlbum(res)) = res {
+            if let Some((ql, randomise)) = self.callback.remove(&ticket) {
+                match res {
+                    GetAlbumResponse::Success(full_album) => {
+                        return Some(Action::Targeted(TargetedAction::Queue(if randomise {
+                            QueueAction::RandomAdd(full_album.song, ql)
+                        } else {
+                            QueueAction::Add(full_album.song, ql)
+                        })));
+                    }
+                    GetAlbumResponse::Failure {
+                        id: _,
+                        name: _,
+                        msg,
+                    } => {
+                        error!("Failed to add album to queue: {msg}");
+                    }
+                }
+            }
+        };
+        None
+    }
+}
+
+impl HandleKeySeq<ArtistListAction> for Albums {
+    fn get_other_helps(&self) -> Vec<ComponentKeyHelp> {
+        self.table.get_help()
+    }
+    fn get_name(&self) -> &str {
+        "ArtistList"
+    }
+    fn pass_to_lower_comp(&mut self, keyseq: &Vec<KeyEvent>) -> Option<KeySeqResult> {
+        let res = self.table.handle_key_seq(keyseq);
+        self.bar
+            .update_pos(self.table.get_current().unwrap_or(0) as u32);
+        res
+    }
+    fn handle_local_action(&mut self, action: ArtistListAction) -> KeySeqResult {
+        let res = match action {
+            ArtistListAction::ViewSelected => self.select_album(),
+            ArtistListAction::Add(pos) => self.add_to_queue(pos, false),
+            ArtistListAction::RandomAdd(pos) => self.add_to_queue(pos, true),
+            ArtistListAction::Refresh => Some(Action::ToQuery(ToQueryWorker::new(
+                HighLevelQuery::SelectArtist(GetArtistParams {
+                    name: self.artist.name.clone(),
+                    id: self.artist.id.clone(),
+                }),
+            ))),
+            // Handled by the parent component
+            ArtistListAction::Back => None,
+        };
+        match res {
+            Some(a) => KeySeqResult::ActionNeeded(a),
+            None => KeySeqResult::NoActionNeeded,
+        }
+    }
+
+    fn get_keybinds(&self) -> &KeyBindings<ArtistListAction> {
+        &self.keymap
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{
+    action::{action::Action, localaction::ArtistListAction},
+    components::{
+        lib::{scrollbar::ScrollBar, visualtable::VisualTable},
+        traits::{
+            handlekeyseq::{ComponentKeyHelp, HandleKeySeq, KeySeqResult},
+            renderable::Renderable,
+        },
+    },
+    config::{keybindings::KeyBindings, Config},
+    osclient::response::getartists::{ArtistIndex, SimpleArtist},
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{getartist::GetArtistParams, ToQueryWorker},
+    },
+};
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    widgets::{Row, Table},
+    Frame,
+};
+
+/// List of all artists, grouped by the first letter of their names
+pub struct Index {
+    table: VisualTable,
+    keymap: KeyBindings<ArtistListAction>,
+    /// Artists in the order they are displayed
+    list: Vec<SimpleArtist>,
+    bar: ScrollBar,
+}
+
+impl Index {
+    /// The group's name is displayed next to the first artist of each group only, which keeps every
+    /// row selectable while still showing where each group starts.
+    fn gen_rows(index: &[ArtistIndex]) -> Vec<Row<'static>> {
+        index
+            .iter()
+            .flat_map(|group| {
+                group.artist.iter().enumerate().map(|(i, artist)| {
+                    Row::new(vec![
+                        if i == 0 {
+                            group.name.clone()
+                        } else {
+                            "".to_string()
+                        },
+                        artist.name.clone(),
+                        artist
+                            .album_count
+                            .map(|c| c.to_string())
+                            .unwrap_or_default(),
+                    ])
+                })
+            })
+            .collect()
+    }
+
+    fn flatten(index: &[ArtistIndex]) -> Vec<SimpleArtist> {
+        index
+            .iter()
+            .flat_map(|group| group.artist.iter().cloned())
+            .collect()
+    }
+
+    pub fn set_rows(&mut self, index: Vec<ArtistIndex>) {
+        self.table.set_rows(Self::gen_rows(&index));
+        self.list = Self::flatten(&index);
+        self.bar.update_max(self.list.len() as u32);
+    }
+
+    pub fn new(config: Config, index: Vec<ArtistIndex>) -> Self {
+        fn table_proc(table: Table<'static>) -> Table<'static> {
+            table
+                .highlight_symbol(">")
+                .row_highlight_style(Style::new().reversed())
+        }
+        let table = VisualTable::new(
+            config.clone(),
+            Self::gen_rows(&index),
+            [
+                Constraint::Length(2),
+                Constraint::Fill(1),
+                Constraint::Max(4),
+            ]
+            .to_vec(),
+            table_proc,
+        );
+        let list = Self::flatten(&index);
+        Self {
+            bar: ScrollBar::new(list.len() as u32, 0),
+            list,
+            table,
+            keymap: config.local.artistlist,
+        }
+    }
+}
+
+impl Renderable for Index {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let [list, bar] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        self.table.draw(frame, list);
+        self.bar.draw(frame, bar);
+    }
+}
+
+impl HandleKeySeq<ArtistListAction> for Index {
+    fn get_other_helps(&self) -> Vec<ComponentKeyHelp> {
+        self.table.get_help()
+    }
+    fn get_name(&self) -> &str {
+        "ArtistList"
+    }
+    fn pass_to_lower_comp(&mut self, keyseq: &Vec<KeyEvent>) -> Option<KeySeqResult> {
+        let res = self.table.handle_key_seq(keyseq);
+        self.bar
+            .update_pos(self.table.get_current().unwrap_or(0) as u32);
+        res
+    }
+    fn handle_local_action(&mut self, action: ArtistListAction) -> KeySeqResult {
+        match action {
+            ArtistListAction::ViewSelected => {
+                if let Some(pos) = self.table.get_current() {
+                    KeySeqResult::ActionNeeded(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::SelectArtist(GetArtistParams {
+                            name: self.list[pos].name.clone(),
+                            id: self.list[pos].id.clone(),
+                        }),
+                    )))
+                } else {
+                    KeySeqResult::NoActionNeeded
+                }
+            }
+            ArtistListAction::Refresh => KeySeqResult::ActionNeeded(Action::ToQuery(
+                ToQueryWorker::new(HighLevelQuery::ListArtists),
+            )),
+            _ => KeySeqResult::NoActionNeeded,
+        }
+    }
+
+    fn get_keybinds(&self) -> &KeyBindings<ArtistListAction> {
+        &self.keymap
+    }
+}
@@ -0,0 +1,64 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    action::action::{Action, Mode, TargetedAction},
+    components::traits::{handleraw::HandleRaw, renderable::Renderable},
+};
+
+/// Asks the user to confirm the action before running it
+pub struct Confirm {
+    message: String,
+    action: Action,
+}
+
+impl Confirm {
+    pub fn new(message: String, action: Action) -> Self {
+        Self { message, action }
+    }
+}
+
+impl Renderable for Confirm {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let vertical = Layout::vertical([Constraint::Length(4)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        let title = Span::styled("Confirm", Style::default().add_modifier(Modifier::BOLD));
+        let block = Block::bordered()
+            .title(title)
+            .border_style(Style::new().white());
+        let text = Paragraph::new(vec![
+            Line::from(self.message.clone()),
+            Line::from("[y]es / [n]o").dark_gray(),
+        ])
+        .block(block);
+        frame.render_widget(Clear, area);
+        frame.render_widget(text, area);
+    }
+}
+
+impl HandleRaw for Confirm {
+    fn handle_raw(&mut self, key: KeyEvent) -> Option<Action> {
+        let mut actions = vec![
+            Action::Targeted(TargetedAction::ClosePopup),
+            Action::ChangeMode(Mode::Normal),
+        ];
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                actions.push(self.action.clone());
+                Some(Action::Multiple(actions))
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                Some(Action::Multiple(actions))
+            }
+            _ => None,
+        }
+    }
+}
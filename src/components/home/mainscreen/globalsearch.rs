@@ -0,0 +1,579 @@
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::Span,
+    widgets::{Block, Borders, Clear, List, ListState},
+    Frame,
+};
+use tui_textarea::TextArea;
+
+use crate::{
+    action::{
+        action::{Action, Mode, QueueAction, TargetedAction},
+        localaction::GlobalSearchAction,
+    },
+    compid::CompID,
+    components::{
+        lib::centered::Centered,
+        traits::{
+            handlekeyseq::{HandleKeySeq, KeySeqResult},
+            handlequery::HandleQuery,
+            handleraw::HandleRaw,
+            renderable::Renderable,
+        },
+    },
+    config::{keybindings::KeyBindings, Config},
+    osclient::{
+        response::{getplaylist::Media, search3::SearchResult3},
+        types::{AlbumID, ArtistID, MediaID},
+    },
+    playerworker::player::QueueLocation,
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{
+            getalbum::{GetAlbumParams, GetAlbumResponse},
+            getartist::GetArtistParams,
+            search::SearchParams,
+            setrating::SetRatingResponse,
+            QueryStatus, ResponseType, ToQueryWorker,
+        },
+    },
+};
+
+const ARTIST_COUNT: usize = 20;
+const ALBUM_COUNT: usize = 20;
+const SONG_COUNT: usize = 50;
+
+#[derive(PartialEq, Clone, Copy)]
+enum Section {
+    Artists,
+    Albums,
+    Songs,
+}
+
+impl Section {
+    fn next(&self) -> Self {
+        match self {
+            Section::Artists => Section::Albums,
+            Section::Albums => Section::Songs,
+            Section::Songs => Section::Artists,
+        }
+    }
+    fn prev(&self) -> Self {
+        match self {
+            Section::Artists => Section::Songs,
+            Section::Albums => Section::Artists,
+            Section::Songs => Section::Albums,
+        }
+    }
+}
+
+/// What to do with the songs of an album or an artist once they are fetched
+enum Callback {
+    Queue(QueueLocation),
+    Playlist,
+}
+
+enum State {
+    /// User has not submitted any keyword yet
+    Empty,
+    Loading(usize),
+    Error(String),
+    Loaded {
+        result: SearchResult3,
+        section: Section,
+        artists: ListState,
+        albums: ListState,
+        songs: ListState,
+    },
+}
+
+/// Popup that searches the entire server with `search3`. The popup is kept by [`MainScreen`] after
+/// it is closed so that the user can come back to the previous result.
+pub struct GlobalSearch {
+    input: TextArea<'static>,
+    editing: bool,
+    state: State,
+    binds: KeyBindings<GlobalSearchAction>,
+    autofocus: bool,
+    callback: HashMap<usize, Callback>,
+}
+
+impl GlobalSearch {
+    pub fn new(config: Config) -> Self {
+        let mut input = TextArea::default();
+        input.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default())
+                .title("Search Server"),
+        );
+        Self {
+            input,
+            editing: true,
+            state: State::Empty,
+            binds: config.local.global_search,
+            autofocus: config.behaviour.auto_focus,
+            callback: HashMap::new(),
+        }
+    }
+
+    /// Returns the mode the program should be in once the popup is opened
+    pub fn get_mode(&self) -> Mode {
+        if self.editing {
+            Mode::Insert
+        } else {
+            Mode::Normal
+        }
+    }
+
+    fn gen_list(items: Vec<String>, title: &str, focused: bool) -> List<'static> {
+        let title = Span::styled(
+            format!("{} ({})", title, items.len()),
+            if focused {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            },
+        );
+        List::new(items)
+            .block(Block::bordered().title(title).border_style(if focused {
+                Style::new().white()
+            } else {
+                Style::new().dark_gray()
+            }))
+            .highlight_style(Style::new().reversed())
+            .highlight_symbol(">")
+            .scroll_padding(1)
+    }
+
+    fn fav_marker(starred: &Option<String>) -> &'static str {
+        if starred.is_some() {
+            "★ "
+        } else {
+            "  "
+        }
+    }
+
+    fn first_section(result: &SearchResult3) -> Section {
+        if !result.artist.is_empty() {
+            Section::Artists
+        } else if !result.album.is_empty() {
+            Section::Albums
+        } else {
+            Section::Songs
+        }
+    }
+
+    fn set_star(&mut self, media: &MediaID, star: bool) {
+        if let State::Loaded { result, .. } = &mut self.state {
+            for m in result.song.iter_mut() {
+                if m.id == *media {
+                    m.starred = if star {
+                        Some("Starred".to_string())
+                    } else {
+                        None
+                    };
+                }
+            }
+        }
+    }
+
+    fn set_album_star(&mut self, album: &AlbumID, star: bool) {
+        if let State::Loaded { result, .. } = &mut self.state {
+            for a in result.album.iter_mut().filter(|a| a.id == *album) {
+                a.starred = star.then(|| "Starred".to_string());
+            }
+        }
+    }
+
+    fn set_artist_star(&mut self, artist: &ArtistID, star: bool) {
+        if let State::Loaded { result, .. } = &mut self.state {
+            for a in result.artist.iter_mut().filter(|a| a.id == *artist) {
+                a.starred = star.then(|| "Starred".to_string());
+            }
+        }
+    }
+
+    /// Sends the query that fetches the songs, which are passed to the callback once they arrive
+    fn fetch_songs(&mut self, query: HighLevelQuery, callback: Callback) -> Action {
+        let req = ToQueryWorker::new(query);
+        self.callback.insert(req.ticket, callback);
+        Action::ToQuery(req)
+    }
+
+    /// Hands the fetched songs over to the queue or the add-to-playlist popup
+    fn on_songs(&mut self, ticket: usize, res: Result<Vec<Media>, String>) -> Option<Action> {
+        let callback = self.callback.remove(&ticket)?;
+        Some(Action::Targeted(match (res, callback) {
+            (Ok(songs), Callback::Queue(ql)) => TargetedAction::Queue(QueueAction::Add(songs, ql)),
+            (Ok(songs), Callback::Playlist) => {
+                TargetedAction::PrepareAddToPlaylist(songs.into_iter().map(|m| m.id).collect())
+            }
+            (Err(msg), _) => TargetedAction::Err(format!("Failed to fetch the songs: {msg}")),
+        }))
+    }
+
+    fn set_rating(&mut self, media: &MediaID, rating: u8) {
+        if let State::Loaded { result, .. } = &mut self.state {
+            for m in result.song.iter_mut() {
+                if m.id == *media {
+                    m.set_rating(rating);
+                }
+            }
+        }
+    }
+
+    /// Runs the given action against the item under the cursor of the focused section
+    fn on_selected(&mut self, action: GlobalSearchAction) -> Option<Action> {
+        let State::Loaded {
+            result,
+            section,
+            artists,
+            albums,
+            songs,
+        } = &self.state
+        else {
+            return None;
+        };
+        match section {
+            Section::Artists => {
+                let artist = result.artist.get(artists.selected()?)?;
+                let params = GetArtistParams {
+                    name: artist.name.clone(),
+                    id: artist.id.clone(),
+                };
+                match action {
+                    GlobalSearchAction::Open => Some(Action::Multiple(vec![
+                        Action::Targeted(TargetedAction::ClosePopup),
+                        Action::Targeted(TargetedAction::FocusArtistList),
+                        Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SelectArtist(params))),
+                    ])),
+                    GlobalSearchAction::Add(ql) => Some(self.fetch_songs(
+                        HighLevelQuery::AddArtistToQueue(params),
+                        Callback::Queue(ql),
+                    )),
+                    GlobalSearchAction::ToggleStar => Some(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::SetArtistStar {
+                            artist: artist.id.clone(),
+                            star: artist.starred.is_none(),
+                        },
+                    ))),
+                    GlobalSearchAction::AddToPlaylist => {
+                        Some(self.fetch_songs(
+                            HighLevelQuery::AddArtistToQueue(params),
+                            Callback::Playlist,
+                        ))
+                    }
+                    _ => None,
+                }
+            }
+            Section::Albums => {
+                let album = result.album.get(albums.selected()?)?;
+                let params = GetAlbumParams {
+                    name: album.name.clone(),
+                    id: album.id.clone(),
+                };
+                match action {
+                    GlobalSearchAction::Open => {
+                        let mut actions = vec![
+                            Action::Targeted(TargetedAction::ClosePopup),
+                            Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SelectAlbum(
+                                params,
+                            ))),
+                        ];
+                        if self.autofocus {
+                            actions.push(Action::Targeted(TargetedAction::FocusPlaylistQueue));
+                        }
+                        Some(Action::Multiple(actions))
+                    }
+                    GlobalSearchAction::Add(ql) => {
+                        Some(self.fetch_songs(
+                            HighLevelQuery::AddAlbumToQueue(params),
+                            Callback::Queue(ql),
+                        ))
+                    }
+                    GlobalSearchAction::ToggleStar => Some(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::SetAlbumStar {
+                            album: album.id.clone(),
+                            star: album.starred.is_none(),
+                        },
+                    ))),
+                    GlobalSearchAction::AddToPlaylist => {
+                        Some(self.fetch_songs(
+                            HighLevelQuery::AddAlbumToQueue(params),
+                            Callback::Playlist,
+                        ))
+                    }
+                    _ => None,
+                }
+            }
+            Section::Songs => {
+                let song = result.song.get(songs.selected()?)?;
+                match action {
+                    GlobalSearchAction::Open => Some(Action::Targeted(TargetedAction::Queue(
+                        QueueAction::Add(vec![song.clone()], QueueLocation::Front),
+                    ))),
+                    GlobalSearchAction::Add(ql) => Some(Action::Targeted(TargetedAction::Queue(
+                        QueueAction::Add(vec![song.clone()], ql),
+                    ))),
+                    GlobalSearchAction::ToggleStar => Some(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::SetStar {
+                            media: song.id.clone(),
+                            star: song.starred.is_none(),
+                        },
+                    ))),
+                    GlobalSearchAction::AddToPlaylist => Some(Action::Targeted(
+                        TargetedAction::PrepareAddToPlaylist(vec![song.id.clone()]),
+                    )),
+                    GlobalSearchAction::ViewInfo => Some(Action::Targeted(
+                        TargetedAction::ViewMediaInfo(song.clone()),
+                    )),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl Renderable for GlobalSearch {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let vertical = Layout::vertical([Constraint::Percentage(80)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(80)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        frame.render_widget(Clear, area);
+        let [input, results] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+        frame.render_widget(&self.input, input);
+        match &mut self.state {
+            State::Empty => {
+                Centered::new(vec!["Press Enter to search".to_string()]).draw(frame, results)
+            }
+            State::Loading(_) => {
+                Centered::new(vec!["Searching...".to_string()]).draw(frame, results)
+            }
+            State::Error(msg) => {
+                Centered::new(vec!["Error!".to_string(), msg.clone()]).draw(frame, results)
+            }
+            State::Loaded {
+                result,
+                section,
+                artists,
+                albums,
+                songs,
+            } => {
+                let [artist_area, album_area, song_area] = Layout::vertical([
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(50),
+                ])
+                .areas(results);
+                let artist_list = Self::gen_list(
+                    result
+                        .artist
+                        .iter()
+                        .map(|a| format!("{}{}", Self::fav_marker(&a.starred), a.name))
+                        .collect(),
+                    "Artists",
+                    !self.editing && *section == Section::Artists,
+                );
+                let album_list = Self::gen_list(
+                    result
+                        .album
+                        .iter()
+                        .map(|a| {
+                            format!(
+                                "{}{} - {}",
+                                Self::fav_marker(&a.starred),
+                                a.name,
+                                a.artist.clone().unwrap_or("Unknown".to_string())
+                            )
+                        })
+                        .collect(),
+                    "Albums",
+                    !self.editing && *section == Section::Albums,
+                );
+                let song_list = Self::gen_list(
+                    result
+                        .song
+                        .iter()
+                        .map(|m| {
+                            format!(
+                                "{}{} - {}",
+                                m.get_fav_marker(),
+                                m.title,
+                                m.artist.clone().unwrap_or("Unknown".to_string())
+                            )
+                        })
+                        .collect(),
+                    "Songs",
+                    !self.editing && *section == Section::Songs,
+                );
+                frame.render_stateful_widget(artist_list, artist_area, artists);
+                frame.render_stateful_widget(album_list, album_area, albums);
+                frame.render_stateful_widget(song_list, song_area, songs);
+            }
+        }
+    }
+}
+
+impl HandleRaw for GlobalSearch {
+    fn handle_raw(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => {
+                if matches!(self.state, State::Loaded { .. }) {
+                    self.editing = false;
+                    Some(Action::ChangeMode(Mode::Normal))
+                } else {
+                    Some(Action::Multiple(vec![
+                        Action::Targeted(TargetedAction::ClosePopup),
+                        Action::ChangeMode(Mode::Normal),
+                    ]))
+                }
+            }
+            KeyCode::Enter => {
+                let query = self.input.lines()[0].clone();
+                if query.is_empty() {
+                    return None;
+                }
+                self.editing = false;
+                Some(Action::Multiple(vec![
+                    Action::ToQuery(ToQueryWorker::new(HighLevelQuery::Search(SearchParams {
+                        query,
+                        artist_count: ARTIST_COUNT,
+                        album_count: ALBUM_COUNT,
+                        song_count: SONG_COUNT,
+                    }))),
+                    Action::ChangeMode(Mode::Normal),
+                ]))
+            }
+            _ => {
+                self.input.input(key);
+                None
+            }
+        }
+    }
+}
+
+impl HandleQuery for GlobalSearch {
+    fn handle_query(&mut self, _dest: CompID, ticket: usize, res: QueryStatus) -> Option<Action> {
+        match res {
+            QueryStatus::Requested(HighLevelQuery::Search(_)) => {
+                self.state = State::Loading(ticket);
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SetStar { media, star }) => {
+                self.set_star(&media, star);
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SetRating { media, rating, .. }) => {
+                self.set_rating(&media, rating);
+                None
+            }
+            QueryStatus::Finished(ResponseType::SetRating(SetRatingResponse::Failure {
+                media,
+                previous,
+                ..
+            })) => {
+                self.set_rating(&media, previous);
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SetAlbumStar { album, star }) => {
+                self.set_album_star(&album, star);
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SetArtistStar { artist, star }) => {
+                self.set_artist_star(&artist, star);
+                None
+            }
+            QueryStatus::Finished(ResponseType::Search(res)) => {
+                if let State::Loading(t) = self.state {
+                    if t == ticket {
+                        self.state = match res {
+                            Ok(result) => State::Loaded {
+                                section: Self::first_section(&result),
+                                artists: ListState::default().with_selected(Some(0)),
+                                albums: ListState::default().with_selected(Some(0)),
+                                songs: ListState::default().with_selected(Some(0)),
+                                result,
+                            },
+                            Err(msg) => State::Error(msg),
+                        };
+                    }
+                }
+                None
+            }
+            QueryStatus::Finished(ResponseType::GetAlbum(res)) => self.on_songs(
+                ticket,
+                match res {
+                    GetAlbumResponse::Success(full_album) => Ok(full_album.song),
+                    GetAlbumResponse::Failure { msg, .. } => Err(msg),
+                },
+            ),
+            QueryStatus::Finished(ResponseType::GetArtistSongs(res)) => self.on_songs(ticket, res),
+            _ => None,
+        }
+    }
+}
+
+impl HandleKeySeq<GlobalSearchAction> for GlobalSearch {
+    fn get_name(&self) -> &str {
+        "GlobalSearch"
+    }
+
+    fn handle_local_action(&mut self, action: GlobalSearchAction) -> KeySeqResult {
+        match action {
+            GlobalSearchAction::Close => {
+                return KeySeqResult::ActionNeeded(Action::Targeted(TargetedAction::ClosePopup))
+            }
+            GlobalSearchAction::Edit => {
+                self.editing = true;
+                return KeySeqResult::ActionNeeded(Action::ChangeMode(Mode::Insert));
+            }
+            GlobalSearchAction::Up
+            | GlobalSearchAction::Down
+            | GlobalSearchAction::Top
+            | GlobalSearchAction::Bottom
+            | GlobalSearchAction::NextSection
+            | GlobalSearchAction::PrevSection => {
+                if let State::Loaded {
+                    result: _,
+                    section,
+                    artists,
+                    albums,
+                    songs,
+                } = &mut self.state
+                {
+                    let list = match section {
+                        Section::Artists => artists,
+                        Section::Albums => albums,
+                        Section::Songs => songs,
+                    };
+                    match action {
+                        GlobalSearchAction::Up => list.select_previous(),
+                        GlobalSearchAction::Down => list.select_next(),
+                        GlobalSearchAction::Top => list.select_first(),
+                        GlobalSearchAction::Bottom => list.select_last(),
+                        GlobalSearchAction::NextSection => *section = section.next(),
+                        GlobalSearchAction::PrevSection => *section = section.prev(),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {
+                if let Some(a) = self.on_selected(action) {
+                    return KeySeqResult::ActionNeeded(a);
+                }
+            }
+        };
+        KeySeqResult::NoActionNeeded
+    }
+
+    fn get_keybinds(&self) -> &KeyBindings<GlobalSearchAction> {
+        &self.binds
+    }
+}
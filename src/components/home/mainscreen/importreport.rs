@@ -0,0 +1,88 @@
+use ratatui::{
+    layout::{Constraint, Flex, Layout},
+    prelude::Rect,
+    style::{Modifier, Style, Stylize},
+    text::Span,
+    widgets::{Block, Clear, List, ListState},
+    Frame,
+};
+
+use crate::{
+    action::{
+        action::{Action, TargetedAction},
+        localaction::PopupAction,
+    },
+    components::traits::{
+        handlekeyseq::{HandleKeySeq, KeySeqResult},
+        renderable::Renderable,
+    },
+    config::keybindings::KeyBindings,
+    playlistfile::ImportResult,
+};
+
+/// Lists the entries of an imported playlist file that could not be found on the server
+pub struct ImportReport {
+    list: List<'static>,
+    state: ListState,
+    binds: KeyBindings<PopupAction>,
+    block: Block<'static>,
+}
+
+impl ImportReport {
+    pub fn new(result: ImportResult, binds: KeyBindings<PopupAction>) -> Self {
+        let title = format!(
+            "Imported {} items as {}, {} not found",
+            result.count,
+            result.name,
+            result.unmatched.len()
+        );
+        Self {
+            list: List::new(result.unmatched)
+                .highlight_style(Style::new().reversed())
+                .highlight_symbol(">"),
+            state: ListState::default().with_selected(Some(0)),
+            binds,
+            block: {
+                let style = Style::new().white();
+                let title = Span::styled(title, Style::default().add_modifier(Modifier::BOLD));
+                Block::bordered().title(title).border_style(style)
+            },
+        }
+    }
+}
+
+impl Renderable for ImportReport {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let vertical = Layout::vertical([Constraint::Percentage(60)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(&self.block, area);
+        frame.render_stateful_widget(&self.list, self.block.inner(area), &mut self.state);
+    }
+}
+
+impl HandleKeySeq<PopupAction> for ImportReport {
+    fn get_name(&self) -> &str {
+        "ImportReport"
+    }
+
+    fn handle_local_action(&mut self, action: PopupAction) -> KeySeqResult {
+        match action {
+            PopupAction::Up => self.state.select_previous(),
+            PopupAction::Down => self.state.select_next(),
+            PopupAction::Top => self.state.select_first(),
+            PopupAction::Bottom => self.state.select_last(),
+            PopupAction::Cancel => {}
+            PopupAction::Close => {
+                return KeySeqResult::ActionNeeded(Action::Targeted(TargetedAction::ClosePopup));
+            }
+        };
+        KeySeqResult::NoActionNeeded
+    }
+
+    fn get_keybinds(&self) -> &KeyBindings<PopupAction> {
+        &self.binds
+    }
+}
@@ -16,7 +16,7 @@ use crate::{
         handlekeyseq::{HandleKeySeq, KeySeqResult},
         renderable::Renderable,
     },
-    config::keybindings::KeyBindings,
+    config::{keybindings::KeyBindings, streamingconfig::StreamProfile},
     osclient::response::getplaylist::Media,
 };
 
@@ -28,7 +28,21 @@ pub struct MediaInfo {
 }
 
 impl MediaInfo {
-    pub fn new(media: Media, binds: KeyBindings<PopupAction>) -> Self {
+    pub fn new(
+        media: Media,
+        profile: Option<&StreamProfile>,
+        binds: KeyBindings<PopupAction>,
+    ) -> Self {
+        let format = profile.and_then(|p| p.format.as_deref());
+        let (codec, _) = media.stream_format(format);
+        let codec = codec.unwrap_or("").to_string();
+        // The server only lowers the bit rate, and the original one is used if it is not transcoded
+        let bit_rate = match (profile.and_then(|p| p.max_bit_rate), media.bit_rate) {
+            (Some(max), Some(orig)) if format != Some("raw") => (max as i32).min(orig).to_string(),
+            (Some(max), None) if format != Some("raw") => format!("<= {max}"),
+            (_, Some(orig)) => orig.to_string(),
+            (_, None) => "".to_string(),
+        };
         let rows: Vec<Row<'static>> = [
             ["Title".to_string(), media.title],
             ["ID".to_string(), media.id.to_string()],
@@ -107,6 +121,8 @@ impl MediaInfo {
                     "".to_string()
                 },
             ],
+            ["Stream Format".to_string(), codec],
+            ["Stream Bit Rate".to_string(), bit_rate],
             [
                 "Sampling Rate".to_string(),
                 if let Some(c) = media.sampling_rate {
@@ -135,16 +151,15 @@ impl MediaInfo {
             ],
             [
                 "User Rating".to_string(),
-                if let Some(rating) = media.user_rating {
-                    rating.to_string()
-                } else {
-                    "".to_string()
+                match media.user_rating {
+                    Some(rating) if rating > 0 => format!("{}/5", rating),
+                    _ => "".to_string(),
                 },
             ],
             [
                 "Average Rating".to_string(),
                 if let Some(rating) = media.average_rating {
-                    rating.to_string()
+                    format!("{:.1}/5", rating)
                 } else {
                     "".to_string()
                 },
@@ -210,6 +225,7 @@ impl HandleKeySeq<PopupAction> for MediaInfo {
             PopupAction::Down => self.state.select_next(),
             PopupAction::Top => self.state.select_first(),
             PopupAction::Bottom => self.state.select_last(),
+            PopupAction::Cancel => {}
             PopupAction::Close => {
                 return KeySeqResult::ActionNeeded(Action::Targeted(TargetedAction::ClosePopup));
             }
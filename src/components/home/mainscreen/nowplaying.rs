@@ -8,6 +8,7 @@ use playing::Playing;
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Style, Stylize},
+    text::Line,
     widgets::Block,
     Frame,
 };
@@ -28,7 +29,7 @@ use crate::{
     },
     config::Config,
     osclient::response::getplaylist::Media,
-    playerworker::player::FromPlayerWorker,
+    playerworker::player::{FromPlayerWorker, PlayOrder, RepeatMode},
     queryworker::{highlevelquery::HighLevelQuery, query::QueryStatus},
 };
 
@@ -43,6 +44,8 @@ pub struct NowPlaying {
     config: Config,
     speed: Speed,
     volume: Volume,
+    repeat: RepeatMode,
+    order: PlayOrder,
 }
 
 impl PassKeySeq for NowPlaying {
@@ -74,8 +77,14 @@ impl NowPlaying {
             enabled,
             comp: Comp::Stopped(Stopped::new()),
             speed: Speed::new(1.0),
+            repeat: RepeatMode::default(),
+            order: PlayOrder::default(),
         }
     }
+    pub fn set_play_mode(&mut self, repeat: RepeatMode, order: PlayOrder) {
+        self.repeat = repeat;
+        self.order = order;
+    }
     fn gen_block(&self) -> Block<'static> {
         let style = if self.enabled {
             Style::new().white()
@@ -83,7 +92,34 @@ impl NowPlaying {
             Style::new().dark_gray()
         };
 
-        Block::bordered().border_style(style)
+        let block = Block::bordered().border_style(style);
+        // Indicators are only displayed if the mode is not the default one
+        let mut modes = vec![];
+        match self.repeat {
+            RepeatMode::Off => {}
+            RepeatMode::One => modes.push("Repeat one"),
+            RepeatMode::All => modes.push("Repeat all"),
+        };
+        match self.order {
+            PlayOrder::Normal => {}
+            PlayOrder::Random => modes.push("Random"),
+            PlayOrder::Reverse => modes.push("Reverse"),
+        };
+        if modes.is_empty() {
+            block
+        } else {
+            block.title_top(Line::from(format!("[{}]", modes.join(" | "))).right_aligned())
+        }
+    }
+    /// Display the given media, fetching its lyrics and cover art
+    fn show_media(&mut self, m: Media) -> Option<Action> {
+        if let Comp::Playing(p) = &mut self.comp {
+            Some(p.change_music(m))
+        } else {
+            let (comp, action) = Playing::new(m, self.config.clone());
+            self.comp = Comp::Playing(comp);
+            action
+        }
     }
 }
 
@@ -93,22 +129,16 @@ impl NowPlaying {
 
 impl HandleQuery for NowPlaying {
     fn handle_query(&mut self, dest: CompID, ticket: usize, res: QueryStatus) -> Option<Action> {
-        if let Comp::Playing(p) = &mut self.comp {
-            if let QueryStatus::Requested(HighLevelQuery::PlayMusicFromURL(m)) = res {
-                Some(p.change_music(m))
-            } else if let QueryStatus::Finished(_) = &res {
+        if let QueryStatus::Requested(HighLevelQuery::PlayMusicFromURL(m)) = res {
+            self.show_media(m)
+        } else if let Comp::Playing(p) = &mut self.comp {
+            if let QueryStatus::Finished(_) = &res {
                 p.handle_query(dest, ticket, res)
             } else {
                 None
             }
         } else {
-            if let QueryStatus::Requested(HighLevelQuery::PlayMusicFromURL(m)) = res {
-                let (comp, action) = Playing::new(m, self.config.clone());
-                self.comp = Comp::Playing(comp);
-                action
-            } else {
-                None
-            }
+            None
         }
     }
 }
@@ -138,6 +168,7 @@ impl HandlePlayer for NowPlaying {
                 }
             }
             FromPlayerWorker::Finished => None,
+            FromPlayerWorker::Advanced(m) => self.show_media(m),
             FromPlayerWorker::NowPlaying(_) => None,
             FromPlayerWorker::Complete => {
                 self.comp = Comp::Stopped(Stopped::new());
@@ -0,0 +1,305 @@
+use crate::{
+    action::{
+        action::{Action, QueueAction, SearchType, TargetedAction},
+        localaction::HistoryAction,
+    },
+    components::{
+        lib::{
+            scrollbar::ScrollBar,
+            visualtable::{VisualSelection, VisualTable},
+        },
+        traits::{
+            focusable::Focusable,
+            handlefilter::HandleFilter,
+            handlekeyseq::{ComponentKeyHelp, HandleKeySeq, KeySeqResult},
+            handlesearch::HandleSearch,
+            renderable::Renderable,
+        },
+    },
+    config::{keybindings::KeyBindings, Config},
+    datastore::PlayRecord,
+    osclient::response::getplaylist::Media,
+    playerworker::player::QueueLocation,
+};
+use crossterm::event::KeyEvent;
+use jiff::{tz::TimeZone, Timestamp};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::Span,
+    widgets::{Block, Row, Table},
+    Frame,
+};
+
+/// List of media played in this and the previous sessions, newest first
+pub struct PlayHistory {
+    enabled: bool,
+    items: Vec<PlayRecord>,
+    table: VisualTable,
+    keymap: KeyBindings<HistoryAction>,
+    filter: Option<(usize, String)>,
+    search: Option<(usize, String)>,
+    bar: ScrollBar,
+    orig_location: usize,
+    /// Maximum number of items, after which the oldest ones are dropped
+    limit: usize,
+}
+
+impl PlayHistory {
+    pub fn new(config: Config, items: Vec<PlayRecord>, enabled: bool) -> Self {
+        fn table_proc(table: Table<'static>) -> Table<'static> {
+            table
+                .highlight_symbol(">")
+                .row_highlight_style(Style::new().reversed())
+        }
+        let table = VisualTable::new(
+            config.clone(),
+            Self::gen_rows(&items),
+            [
+                Constraint::Length(11),
+                Constraint::Fill(1),
+                Constraint::Length(5),
+            ]
+            .to_vec(),
+            table_proc,
+        );
+        Self {
+            bar: ScrollBar::new(items.len() as u32, 0),
+            enabled,
+            items,
+            table,
+            limit: config.features.history.limit,
+            keymap: config.local.history,
+            filter: None,
+            search: None,
+            orig_location: 0,
+        }
+    }
+
+    fn gen_rows(items: &[PlayRecord]) -> Vec<Row<'static>> {
+        let tz = TimeZone::system();
+        items
+            .iter()
+            .map(|item| {
+                let secs = item.played.as_secs();
+                Row::new(vec![
+                    Timestamp::from_second(item.played_at)
+                        .map(|t| t.to_zoned(tz.clone()).strftime("%m-%d %H:%M").to_string())
+                        .unwrap_or_default(),
+                    match &item.media.artist {
+                        Some(artist) => format!("{} - {}", artist, item.media.title),
+                        None => item.media.title.clone(),
+                    },
+                    format!("{:02}:{:02}", secs / 60, secs % 60),
+                ])
+            })
+            .collect()
+    }
+
+    /// True if the title, artist or album of the media contains the given text
+    fn matches(item: &PlayRecord, text: &str) -> bool {
+        let text = text.to_lowercase();
+        let media = &item.media;
+        [
+            Some(&media.title),
+            media.artist.as_ref(),
+            media.album.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|s| s.to_lowercase().contains(&text))
+    }
+
+    /// Adds a media that has just finished playing to the top of the list
+    pub fn push(&mut self, record: PlayRecord) {
+        self.items.insert(0, record);
+        self.table.add_rows_at(Self::gen_rows(&self.items), 0, 1);
+        if self.items.len() > self.limit {
+            self.items.truncate(self.limit);
+            self.table.truncate(self.limit);
+        }
+        match self.filter.take() {
+            Some((_, filter)) => self.set_filter(filter),
+            None => self.bar.update_max(self.items.len() as u32),
+        }
+    }
+
+    /// Adds selected items into the queue, resetting the current selection.
+    fn add_selection_to_queue(
+        &mut self,
+        playpos: QueueLocation,
+        randomise: bool,
+    ) -> Option<Action> {
+        let (selection, action) = self.table.get_selection_reset();
+        let items: Vec<Media> = match selection {
+            VisualSelection::Single(index) => vec![self.items[index].media.clone()],
+            VisualSelection::Multiple { map, temp: _ } => self
+                .items
+                .iter()
+                .zip(map)
+                .filter(|(_, selected)| *selected)
+                .map(|(r, _)| r.media.clone())
+                .collect(),
+            VisualSelection::None => vec![],
+        };
+        let queue = if items.is_empty() {
+            None
+        } else {
+            Some(Action::Targeted(TargetedAction::Queue(if randomise {
+                QueueAction::RandomAdd(items, playpos)
+            } else {
+                QueueAction::Add(items, playpos)
+            })))
+        };
+        match (queue, action) {
+            (Some(a), Some(b)) => Some(Action::Multiple(vec![a, b])),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn gen_block(&self) -> Block<'static> {
+        let (len, extra) = match &self.filter {
+            Some((len, filter)) => (*len, format!(" (Filter: {})", filter)),
+            None => (self.items.len(), "".to_string()),
+        };
+        let title = match self.table.get_current() {
+            Some(pos) if pos < len => format!("History ({}/{}){}", pos + 1, len, extra),
+            _ => format!("History ({}){}", len, extra),
+        };
+        let style = if self.enabled {
+            Style::new().white()
+        } else {
+            Style::new().dark_gray()
+        };
+        let title = Span::styled(
+            title,
+            if self.enabled {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            },
+        );
+        Block::bordered().title(title).border_style(style)
+    }
+}
+
+impl Renderable for PlayHistory {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let border = self.gen_block();
+        let inner = border.inner(area);
+        frame.render_widget(border, area);
+        let [list, bar] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+        self.table.draw(frame, list);
+        self.bar.draw(frame, bar);
+    }
+}
+
+impl HandleKeySeq<HistoryAction> for PlayHistory {
+    fn get_other_helps(&self) -> Vec<ComponentKeyHelp> {
+        self.table.get_help()
+    }
+    fn get_name(&self) -> &str {
+        "History"
+    }
+    fn pass_to_lower_comp(&mut self, keyseq: &Vec<KeyEvent>) -> Option<KeySeqResult> {
+        let res = self.table.handle_key_seq(keyseq);
+        self.bar
+            .update_pos(self.table.get_current().unwrap_or(0) as u32);
+        res
+    }
+    fn handle_local_action(&mut self, action: HistoryAction) -> KeySeqResult {
+        let res = match action {
+            HistoryAction::Add(pos) => self.add_selection_to_queue(pos, false),
+            HistoryAction::RandomAdd(pos) => self.add_selection_to_queue(pos, true),
+            HistoryAction::ViewInfo => self.table.get_current().map(|pos| {
+                Action::Targeted(TargetedAction::ViewMediaInfo(self.items[pos].media.clone()))
+            }),
+        };
+        match res {
+            Some(a) => KeySeqResult::ActionNeeded(a),
+            None => KeySeqResult::NoActionNeeded,
+        }
+    }
+    fn get_keybinds(&self) -> &KeyBindings<HistoryAction> {
+        &self.keymap
+    }
+}
+
+impl Focusable for PlayHistory {
+    fn set_enabled(&mut self, enable: bool) {
+        if self.enabled != enable {
+            self.enabled = enable;
+            if enable {
+                self.table.bump_cursor_pos();
+            } else {
+                self.table.disable_visual_discard();
+            }
+        };
+    }
+}
+
+impl HandleSearch for PlayHistory {
+    fn init_search(&mut self) -> bool {
+        self.orig_location = self.table.get_current().unwrap_or(0);
+        true
+    }
+    fn test_search(&mut self, search: String, stype: SearchType) {
+        if search.is_empty() {
+            self.search = Some((0, search));
+            self.table.reset_highlight();
+            if stype == SearchType::Revert {
+                self.table.set_position(self.orig_location);
+            }
+            return;
+        }
+        let highlight: Vec<bool> = self
+            .items
+            .iter()
+            .map(|i| Self::matches(i, &search))
+            .collect();
+        let count = highlight.iter().filter(|h| **h).count();
+        if stype == SearchType::Revert {
+            self.table.set_position(self.orig_location);
+        } else {
+            let pos = self.table.get_current().unwrap_or(0);
+            // Next match at or after the cursor, wrapping around to the first match
+            let next = (pos..highlight.len())
+                .find(|i| highlight[*i])
+                .or_else(|| highlight.iter().position(|h| *h));
+            if let Some(idx) = next {
+                if stype == SearchType::Confirm {
+                    self.orig_location = idx;
+                }
+                self.table.set_position(idx);
+            }
+        }
+        self.search = Some((count, search));
+        self.table.set_highlight(&highlight);
+    }
+}
+
+impl HandleFilter for PlayHistory {
+    fn init_filter(&mut self) -> bool {
+        true
+    }
+    fn set_filter(&mut self, filter: String) {
+        if filter.is_empty() {
+            self.filter = None;
+            self.table.reset_visibility();
+            self.table.bump_cursor_pos();
+            self.bar.update_max(self.items.len() as u32);
+        } else {
+            let visibility: Vec<bool> = self
+                .items
+                .iter()
+                .map(|i| Self::matches(i, &filter))
+                .collect();
+            let count = visibility.iter().filter(|v| **v).count();
+            self.filter = Some((count, filter));
+            self.table.set_visibility(&visibility);
+            self.table.bump_cursor_pos();
+            self.bar.update_max(count as u32);
+        }
+    }
+}
@@ -112,6 +112,7 @@ impl HandleKeySeq<PopupAction> for PlaylistInfo {
             PopupAction::Down => self.state.select_next(),
             PopupAction::Top => self.state.select_first(),
             PopupAction::Bottom => self.state.select_last(),
+            PopupAction::Cancel => {}
             PopupAction::Close => {
                 return KeySeqResult::ActionNeeded(Action::Targeted(TargetedAction::ClosePopup));
             }
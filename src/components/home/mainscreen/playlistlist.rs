@@ -14,6 +14,7 @@ use crate::{
         },
     },
     config::Config,
+    osclient::response::getplaylists::SimplePlaylist,
     queryworker::{
         highlevelquery::HighLevelQuery,
         query::{QueryStatus, ResponseType, ToQueryWorker},
@@ -52,6 +53,19 @@ impl PlaylistList {
             Action::ToQuery(query),
         )
     }
+    /// Changes the details of a playlist if the list has been loaded
+    pub fn set_info(
+        &mut self,
+        playlist: SimplePlaylist,
+        name: Option<String>,
+        comment: Option<String>,
+        public: Option<bool>,
+    ) -> Option<Action> {
+        match &mut self.comp {
+            Comp::Loaded(comp) => Some(comp.set_info(&playlist, name, comment, public)),
+            _ => None,
+        }
+    }
     fn gen_block(&self) -> Block<'static> {
         let style = if self.enabled {
             Style::new().white()
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use crate::{
     action::{
-        action::{Action, QueueAction, TargetedAction},
+        action::{Action, PlaylistEdit, QueueAction, TargetedAction},
         localaction::PlaylistListAction,
     },
     compid::CompID,
@@ -15,12 +15,15 @@ use crate::{
         },
     },
     config::{keybindings::KeyBindings, Config},
+    helper::history::History,
     osclient::{response::getplaylists::SimplePlaylist, types::PlaylistID},
     playerworker::player::QueueLocation,
     queryworker::{
         highlevelquery::HighLevelQuery,
         query::{
+            exportplaylist::{ExportParams, ExportSource},
             getplaylist::{GetPlaylistParams, GetPlaylistResponse},
+            updateplaylist::UpdatePlaylistParams,
             QueryStatus, ResponseType, ToQueryWorker,
         },
     },
@@ -41,6 +44,10 @@ pub struct Loaded {
     list: Vec<SimplePlaylist>,
     callback: HashMap<usize, (PlaylistID, QueueLocation, bool)>,
     bar: ScrollBar,
+    /// Changes made to the details of the playlists
+    history: History<UpdatePlaylistParams>,
+    /// Directory the playlist files are exported to, which is suggested when importing
+    playlist_dir: PathBuf,
 }
 
 impl Loaded {
@@ -74,9 +81,63 @@ impl Loaded {
     }
 
     pub fn set_rows(&mut self, items: &Vec<SimplePlaylist>) {
+        self.list = items.clone();
+        self.bar.update_max(self.list.len() as u32);
         self.table.set_rows(Self::gen_rows(items));
     }
 
+    /// Changes the name, comment or visibility of the playlist, and remembers the values before
+    /// the change so that it can be undone
+    pub fn set_info(
+        &mut self,
+        playlist: &SimplePlaylist,
+        name: Option<String>,
+        comment: Option<String>,
+        public: Option<bool>,
+    ) -> Action {
+        let change = UpdatePlaylistParams {
+            playlist_id: playlist.id.clone(),
+            name,
+            comment,
+            public,
+            song_id_to_add: None,
+            song_index_to_remove: None,
+        };
+        let revert = UpdatePlaylistParams {
+            name: change.name.as_ref().map(|_| playlist.name.clone()),
+            comment: change
+                .comment
+                .as_ref()
+                .map(|_| playlist.comment.clone().unwrap_or_default()),
+            public: change.public.map(|_| playlist.public.unwrap_or(false)),
+            ..change.clone()
+        };
+        self.history.record(revert, change.clone());
+        Action::ToQuery(ToQueryWorker::new(HighLevelQuery::UpdatePlaylist(change)))
+    }
+
+    /// Sends the change that reverts or reapplies the last change
+    fn travel(&mut self, undo: bool) -> Action {
+        let state = if undo {
+            self.history.undo()
+        } else {
+            self.history.redo()
+        };
+        match state {
+            Some(params) => Action::ToQuery(ToQueryWorker::new(HighLevelQuery::UpdatePlaylist(
+                params.clone(),
+            ))),
+            None => Action::Targeted(TargetedAction::Info(
+                if undo {
+                    "Already at the oldest change"
+                } else {
+                    "Already at the newest change"
+                }
+                .to_string(),
+            )),
+        }
+    }
+
     pub fn new(config: Config, list: Vec<SimplePlaylist>) -> Self {
         fn table_proc(table: Table<'static>) -> Table<'static> {
             table
@@ -100,6 +161,8 @@ impl Loaded {
             autofocus: config.behaviour.auto_focus,
             keymap: config.local.playlistlist.clone(),
             bar: ScrollBar::new(len as u32, 0),
+            history: History::default(),
+            playlist_dir: config.features.playlist_file.dir.clone(),
         }
     }
     pub fn add_to_queue(&mut self, ql: QueueLocation, randomise: bool) -> Option<Action> {
@@ -191,6 +254,60 @@ impl HandleKeySeq<PlaylistListAction> for Loaded {
             PlaylistListAction::Refresh => KeySeqResult::ActionNeeded(Action::ToQuery(
                 ToQueryWorker::new(HighLevelQuery::ListPlaylists),
             )),
+            PlaylistListAction::Create => KeySeqResult::ActionNeeded(Action::Targeted(
+                TargetedAction::EditPlaylist(PlaylistEdit::Create),
+            )),
+            PlaylistListAction::Rename
+            | PlaylistListAction::EditComment
+            | PlaylistListAction::Delete => {
+                let Some(pos) = self.table.get_current() else {
+                    return KeySeqResult::NoActionNeeded;
+                };
+                let playlist = self.list[pos].clone();
+                KeySeqResult::ActionNeeded(Action::Targeted(TargetedAction::EditPlaylist(
+                    match action {
+                        PlaylistListAction::Rename => PlaylistEdit::Rename(playlist),
+                        PlaylistListAction::EditComment => PlaylistEdit::SetComment(playlist),
+                        _ => PlaylistEdit::Delete(playlist),
+                    },
+                )))
+            }
+            PlaylistListAction::TogglePublic => {
+                let Some(pos) = self.table.get_current() else {
+                    return KeySeqResult::NoActionNeeded;
+                };
+                let playlist = self.list[pos].clone();
+                let public = !playlist.public.unwrap_or(false);
+                KeySeqResult::ActionNeeded(self.set_info(&playlist, None, None, Some(public)))
+            }
+            PlaylistListAction::Export => {
+                let Some(pos) = self.table.get_current() else {
+                    return KeySeqResult::NoActionNeeded;
+                };
+                KeySeqResult::ActionNeeded(Action::ToQuery(ToQueryWorker::new(
+                    HighLevelQuery::ExportPlaylist(ExportParams {
+                        name: self.list[pos].name.clone(),
+                        source: ExportSource::Playlist(self.list[pos].id.clone()),
+                    }),
+                )))
+            }
+            PlaylistListAction::Import => KeySeqResult::ActionNeeded(Action::Targeted(
+                TargetedAction::EditPlaylist(PlaylistEdit::Import(self.playlist_dir.clone())),
+            )),
+            PlaylistListAction::Undo => KeySeqResult::ActionNeeded(self.travel(true)),
+            PlaylistListAction::Redo => KeySeqResult::ActionNeeded(self.travel(false)),
+            PlaylistListAction::Download => {
+                if let Some(pos) = self.table.get_current() {
+                    KeySeqResult::ActionNeeded(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::DownloadPlaylist(GetPlaylistParams {
+                            name: self.list[pos].name.clone(),
+                            id: self.list[pos].id.clone(),
+                        }),
+                    )))
+                } else {
+                    KeySeqResult::NoActionNeeded
+                }
+            }
         }
     }
 
@@ -6,6 +6,7 @@ mod notselected;
 
 use crate::{
     action::action::{Action, SearchType},
+    audiocache::AudioCache,
     compid::CompID,
     components::{
         home::mainscreen::playlistqueue::{empty::Empty, loading::Loading},
@@ -19,9 +20,18 @@ use crate::{
         },
     },
     config::Config,
+    osclient::{
+        response::getplaylist::Media,
+        types::{AlbumID, PlaylistID},
+    },
     queryworker::{
         highlevelquery::HighLevelQuery,
-        query::{getplaylist::GetPlaylistResponse, QueryStatus, ResponseType},
+        query::{
+            getalbum::{GetAlbumParams, GetAlbumResponse},
+            getplaylist::{GetPlaylistParams, GetPlaylistResponse},
+            setrating::SetRatingResponse,
+            QueryStatus, ResponseType, ToQueryWorker,
+        },
     },
 };
 use crossterm::event::KeyEvent;
@@ -36,9 +46,33 @@ use ratatui::{
     Frame,
 };
 
+/// Playlist queue is not limited to displaying playlists; Albums can be opened in it as well. This
+/// keeps track of where the displayed items came from so that they can be fetched again.
+#[derive(Clone)]
+pub enum QueueSource {
+    Playlist(PlaylistID),
+    Album(AlbumID),
+}
+
+impl QueueSource {
+    /// Creates a query that fetches the content from the source again
+    pub fn to_query(&self, name: String) -> HighLevelQuery {
+        match self {
+            QueueSource::Playlist(id) => HighLevelQuery::SelectPlaylist(GetPlaylistParams {
+                name,
+                id: id.clone(),
+            }),
+            QueueSource::Album(id) => HighLevelQuery::SelectAlbum(GetAlbumParams {
+                name,
+                id: id.clone(),
+            }),
+        }
+    }
+}
+
 enum Comp {
     Error(Error),
-    Loaded(Loaded),
+    Loaded(Box<Loaded>),
     Loading(Loading, usize),
     NotSelected(NotSelected),
     Empty(Empty),
@@ -48,17 +82,47 @@ pub struct PlaylistQueue {
     comp: Comp,
     enabled: bool,
     config: Config,
+    cache: Option<AudioCache>,
+    /// Only the cached media is displayed if this is set
+    offline: bool,
 }
 
 impl PlaylistQueue {
     pub fn new(config: Config, enabled: bool) -> Self {
         Self {
+            cache: AudioCache::new(&config),
+            offline: config.features.cache.offline,
             config,
             comp: Comp::NotSelected(NotSelected::new(enabled)),
             enabled,
         }
     }
 
+    /// Fetches the displayed items again so that the uncached items are hidden or shown
+    pub fn set_offline(&mut self, offline: bool) -> Option<Action> {
+        self.offline = offline;
+        match &self.comp {
+            Comp::Loaded(loaded) => {
+                Some(Action::ToQuery(ToQueryWorker::new(loaded.refresh_query())))
+            }
+            _ => None,
+        }
+    }
+
+    /// Removes the media that cannot be played in offline mode
+    fn playable(&self, items: Vec<Media>) -> Vec<Media> {
+        if !self.offline {
+            return items;
+        }
+        match &self.cache {
+            Some(cache) => items
+                .into_iter()
+                .filter(|m| cache.is_cached(&m.id))
+                .collect(),
+            None => vec![],
+        }
+    }
+
     fn gen_block(enabled: bool, title: String) -> Block<'static> {
         let style = if enabled {
             Style::new().white()
@@ -112,18 +176,67 @@ impl HandleQuery for PlaylistQueue {
             if let Comp::Loaded(loaded) = &mut self.comp {
                 loaded.set_star(media, star);
             }
+        } else if let QueryStatus::Requested(HighLevelQuery::SetRating { media, rating, .. }) = res
+        {
+            if let Comp::Loaded(loaded) = &mut self.comp {
+                loaded.set_rating(media, rating);
+            }
+        } else if let QueryStatus::Finished(ResponseType::SetRating(SetRatingResponse::Failure {
+            media,
+            previous,
+            ..
+        })) = res
+        {
+            if let Comp::Loaded(loaded) = &mut self.comp {
+                loaded.set_rating(media, previous);
+            }
         } else if let QueryStatus::Requested(HighLevelQuery::SelectPlaylist(params)) = res {
             self.comp = Comp::Loading(Loading::new(params.name, self.enabled), ticket);
-        } else if let QueryStatus::Finished(ResponseType::GetPlaylist(r)) = res {
+        } else if let QueryStatus::Requested(HighLevelQuery::SelectAlbum(params)) = res {
+            self.comp = Comp::Loading(Loading::new(params.name, self.enabled), ticket);
+        } else if let QueryStatus::Finished(ResponseType::UpdatePlaylist(r)) = &res {
+            if let Comp::Loaded(loaded) = &mut self.comp {
+                loaded.finish_edit(ticket, r);
+            }
+        } else if let QueryStatus::Finished(ResponseType::GetAlbum(r)) = res {
             match r {
-                GetPlaylistResponse::Success(full_playlist) => {
+                GetAlbumResponse::Success(full_album) => {
                     if let Comp::Loading(_, t) = self.comp {
                         if t == ticket {
-                            self.comp = Comp::Loaded(Loaded::new(
+                            self.comp = Comp::Loaded(Box::new(Loaded::new(
                                 self.config.clone(),
-                                full_playlist.name.clone(),
-                                full_playlist,
+                                full_album.name,
+                                QueueSource::Album(full_album.id),
+                                self.playable(full_album.song),
                                 self.enabled,
+                            )));
+                        }
+                    }
+                }
+                GetAlbumResponse::Failure { id, name, msg } => {
+                    self.comp = Comp::Error(Error::new(
+                        self.config.local.playlistqueue.clone(),
+                        QueueSource::Album(id),
+                        name,
+                        msg,
+                        self.enabled,
+                    ));
+                }
+            }
+        } else if let QueryStatus::Finished(ResponseType::GetPlaylist(r)) = res {
+            match r {
+                GetPlaylistResponse::Success(full_playlist) => {
+                    if let Comp::Loading(_, t) = self.comp {
+                        if t == ticket {
+                            self.comp = Comp::Loaded(Box::new(
+                                Loaded::new(
+                                    self.config.clone(),
+                                    full_playlist.name,
+                                    QueueSource::Playlist(full_playlist.id),
+                                    self.playable(full_playlist.entry),
+                                    self.enabled,
+                                )
+                                .partial(self.offline),
                             ));
                         }
                     }
@@ -132,8 +245,13 @@ impl HandleQuery for PlaylistQueue {
                     self.comp = Comp::Empty(Empty::new(simple_playlist.name, self.enabled));
                 }
                 GetPlaylistResponse::Failure { id, name, msg } => {
-                    self.comp =
-                        Comp::Error(Error::new(self.config.clone(), id, name, msg, self.enabled));
+                    self.comp = Comp::Error(Error::new(
+                        self.config.local.playlistqueue.clone(),
+                        QueueSource::Playlist(id),
+                        name,
+                        msg,
+                        self.enabled,
+                    ));
                 }
             }
         }
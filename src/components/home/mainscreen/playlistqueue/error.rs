@@ -1,7 +1,7 @@
 use crate::{
     action::{action::Action, localaction::PlaylistQueueAction},
     components::{
-        home::mainscreen::playlistqueue::PlaylistQueue,
+        home::mainscreen::playlistqueue::{PlaylistQueue, QueueSource},
         lib::centered::Centered,
         traits::{
             focusable::Focusable,
@@ -9,27 +9,29 @@ use crate::{
             renderable::Renderable,
         },
     },
-    config::{keybindings::KeyBindings, Config},
-    osclient::types::PlaylistID,
-    queryworker::{
-        highlevelquery::HighLevelQuery,
-        query::{getplaylist::GetPlaylistParams, ToQueryWorker},
-    },
+    config::keybindings::KeyBindings,
+    queryworker::query::ToQueryWorker,
 };
 use ratatui::{layout::Rect, Frame};
 
 pub struct Error {
-    id: PlaylistID,
+    source: QueueSource,
     name: String,
     comp: Centered,
     enabled: bool,
-    config: Config,
+    binds: KeyBindings<PlaylistQueueAction>,
 }
 
 impl Error {
-    pub fn new(config: Config, id: PlaylistID, name: String, error: String, enabled: bool) -> Self {
+    pub fn new(
+        binds: KeyBindings<PlaylistQueueAction>,
+        source: QueueSource,
+        name: String,
+        error: String,
+        enabled: bool,
+    ) -> Self {
         Self {
-            id,
+            source,
             name,
             comp: Centered::new(vec![
                 "Error!".to_string(),
@@ -37,7 +39,7 @@ impl Error {
                 "Reload with 'R'".to_string(),
             ]),
             enabled,
-            config,
+            binds,
         }
     }
 }
@@ -58,17 +60,14 @@ impl HandleKeySeq<PlaylistQueueAction> for Error {
     fn handle_local_action(&mut self, action: PlaylistQueueAction) -> KeySeqResult {
         match action {
             PlaylistQueueAction::Refresh => KeySeqResult::ActionNeeded(Action::ToQuery(
-                ToQueryWorker::new(HighLevelQuery::SelectPlaylist(GetPlaylistParams {
-                    name: self.name.clone(),
-                    id: self.id.clone(),
-                })),
+                ToQueryWorker::new(self.source.to_query(self.name.clone())),
             )),
             _ => KeySeqResult::NoActionNeeded,
         }
     }
 
     fn get_keybinds(&self) -> &KeyBindings<PlaylistQueueAction> {
-        &self.config.local.playlistqueue
+        &self.binds
     }
 }
 
@@ -4,7 +4,7 @@ use crate::{
         localaction::PlaylistQueueAction,
     },
     components::{
-        home::mainscreen::playlistqueue::PlaylistQueue,
+        home::mainscreen::playlistqueue::{PlaylistQueue, QueueSource},
         lib::{
             scrollbar::ScrollBar,
             visualtable::{VisualSelection, VisualTable},
@@ -18,15 +18,18 @@ use crate::{
         },
     },
     config::{keybindings::KeyBindings, Config},
-    helper::selection::Selection,
+    helper::{
+        history::History,
+        selection::{ModifiableList, Selection},
+    },
     osclient::{
-        response::getplaylist::{FullPlaylist, Media},
-        types::MediaID,
+        response::getplaylist::Media,
+        types::{MediaID, PlaylistID},
     },
     playerworker::player::QueueLocation,
     queryworker::{
         highlevelquery::HighLevelQuery,
-        query::{getplaylist::GetPlaylistParams, ToQueryWorker},
+        query::{download::DownloadParams, updateplaylist::UpdatePlaylistParams, ToQueryWorker},
     },
 };
 use crossterm::event::KeyEvent;
@@ -36,10 +39,12 @@ use ratatui::{
     widgets::{Row, Table, TableState},
     Frame,
 };
+use std::collections::HashMap;
 
 pub struct Loaded {
     name: String,
-    playlist: FullPlaylist,
+    source: QueueSource,
+    items: Vec<Media>,
     enabled: bool,
     table: VisualTable,
     keymap: KeyBindings<PlaylistQueueAction>,
@@ -47,6 +52,14 @@ pub struct Loaded {
     search: Option<(usize, String)>,
     bar: ScrollBar,
     orig_location: usize,
+    /// Only the items that are cached are shown, so the indices do not match the ones on the
+    /// server
+    partial: bool,
+    /// Changes sent to the server that have not been confirmed yet, and the items before each
+    /// change so that it can be undone if it fails
+    pending: HashMap<usize, Vec<Media>>,
+    /// Items before and after each change made to the playlist
+    history: History<Vec<Media>>,
 }
 
 impl Loaded {
@@ -59,7 +72,7 @@ impl Loaded {
         let (selection, action) = self.table.get_selection_reset();
         let first = match selection {
             VisualSelection::Single(index) => Some(Action::Targeted(TargetedAction::Queue({
-                let items = vec![self.playlist.entry[index].clone()];
+                let items = vec![self.items[index].clone()];
                 if randomise {
                     QueueAction::RandomAdd(items, playpos)
                 } else {
@@ -68,8 +81,7 @@ impl Loaded {
             }))),
             VisualSelection::Multiple { map, temp: _ } => {
                 let items = self
-                    .playlist
-                    .entry
+                    .items
                     .iter()
                     .zip(map)
                     .filter(|(_, selected)| *selected)
@@ -96,6 +108,38 @@ impl Loaded {
         }
     }
 
+    /// Downloads selected items, resetting the current selection.
+    fn download_selection(&mut self) -> Option<Action> {
+        let (selection, action) = self.table.get_selection_reset();
+        let items: Vec<Media> = match selection {
+            VisualSelection::Single(index) => vec![self.items[index].clone()],
+            VisualSelection::Multiple { map, temp: _ } => self
+                .items
+                .iter()
+                .zip(map)
+                .filter(|(_, selected)| *selected)
+                .map(|(m, _)| m.clone())
+                .collect(),
+            VisualSelection::None => vec![],
+        };
+        let download = if items.is_empty() {
+            None
+        } else {
+            let name = if items.len() == 1 {
+                items[0].title.clone()
+            } else {
+                format!("{} items from {}", items.len(), self.name)
+            };
+            Some(Action::ToQuery(ToQueryWorker::new(
+                HighLevelQuery::Download(DownloadParams { name, items }),
+            )))
+        };
+        match (download, action) {
+            (Some(a), Some(b)) => Some(Action::Multiple(vec![a, b])),
+            (a, b) => a.or(b),
+        }
+    }
+
     /// Generate rows so that they can be used by the table component
     pub fn gen_rows(items: &Vec<Media>) -> Vec<Row<'static>> {
         items
@@ -109,19 +153,26 @@ impl Loaded {
                     } else {
                         "".to_string()
                     },
+                    item.get_rating_marker(),
                     item.get_fav_marker(),
                 ])
             })
             .collect()
     }
 
-    pub fn new(config: Config, name: String, list: FullPlaylist, enabled: bool) -> Self {
+    pub fn new(
+        config: Config,
+        name: String,
+        source: QueueSource,
+        items: Vec<Media>,
+        enabled: bool,
+    ) -> Self {
         fn table_proc(table: Table<'static>) -> Table<'static> {
             table
                 .highlight_symbol(">")
                 .row_highlight_style(Style::new().reversed())
         }
-        let rows = Self::gen_rows(&list.entry);
+        let rows = Self::gen_rows(&items);
         let table = VisualTable::new(
             config.clone(),
             rows,
@@ -129,6 +180,7 @@ impl Loaded {
                 Constraint::Ratio(1, 3),
                 Constraint::Ratio(2, 3),
                 Constraint::Length(5),
+                Constraint::Length(3),
                 Constraint::Length(2),
             ]
             .to_vec(),
@@ -138,22 +190,193 @@ impl Loaded {
         tablestate.select(Some(0));
 
         Self {
-            bar: ScrollBar::new(list.entry.len() as u32, 0),
+            bar: ScrollBar::new(items.len() as u32, 0),
             keymap: config.local.playlistqueue,
             name,
             enabled,
-            playlist: list,
+            source,
+            items,
             table,
             filter: None,
             search: None,
             orig_location: 0,
+            partial: false,
+            pending: HashMap::new(),
+            history: History::default(),
+        }
+    }
+
+    /// Marks the items as a subset of the playlist, which prevents them from being edited
+    pub fn partial(mut self, partial: bool) -> Self {
+        self.partial = partial;
+        self
+    }
+
+    /// Removes or moves the selected items, and applies the same change to the playlist on the
+    /// server. The change is shown immediately, and undone if the server rejects it.
+    fn edit_playlist(&mut self, edit: PlaylistQueueAction) -> Option<Action> {
+        let id = match self.editable() {
+            Ok(id) => id,
+            Err(e) => return Some(Action::Targeted(TargetedAction::Err(e))),
+        };
+        let (vs, action) = self.table.get_selection_reset();
+        let selection = match vs {
+            VisualSelection::Single(index) => Selection::Single(index),
+            VisualSelection::Multiple { map, temp: _ } => Selection::Multiple(map),
+            VisualSelection::None => return action,
+        };
+        let cursor = self.table.get_current();
+        let backup = self.items.clone();
+        let mut list = ModifiableList::new(std::mem::take(&mut self.items));
+        let (params, cursor) = match edit {
+            PlaylistQueueAction::Delete => {
+                let removed = match &selection {
+                    Selection::Single(index) => vec![*index],
+                    Selection::Multiple(map) => map
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(i, selected)| selected.then_some(i))
+                        .collect(),
+                };
+                let cursor = cursor
+                    .and_then(|c| list.move_item_to(&selection, c))
+                    .map(|(c, _)| c);
+                list.delete(&selection);
+                (Some((removed, vec![])), cursor)
+            }
+            _ => {
+                let up = edit == PlaylistQueueAction::MoveUp;
+                // The server can only append items, so everything from the first moved item is
+                // removed and added again in the new order
+                let params = list.move_selected(&selection, up).map(|from| {
+                    (
+                        (from..list.len()).collect(),
+                        list[from..].iter().map(|m| m.id.clone()).collect(),
+                    )
+                });
+                let cursor = match (&selection, cursor) {
+                    (Selection::Single(_), Some(c)) if params.is_some() => {
+                        Some(if up { c - 1 } else { c + 1 })
+                    }
+                    (_, c) => c,
+                };
+                (params, cursor)
+            }
+        };
+        self.items = list.0;
+        let Some((removed, added)) = params else {
+            return action;
+        };
+        self.table.reset_rows(Self::gen_rows(&self.items), cursor);
+        self.bar.update_max(self.items.len() as u32);
+        self.history.record(backup.clone(), self.items.clone());
+        let query = self.send_edit(id, removed, added, backup);
+        Some(match action {
+            Some(a) => Action::Multiple(vec![query, a]),
+            None => query,
+        })
+    }
+
+    /// Returns the ID of the playlist if the displayed items can be edited, or the reason they cannot
+    /// be
+    fn editable(&self) -> Result<PlaylistID, String> {
+        let QueueSource::Playlist(id) = &self.source else {
+            return Err("Only playlists can be edited".to_string());
+        };
+        if self.partial {
+            return Err("Playlists cannot be edited in offline mode".to_string());
+        }
+        if self.filter.is_some() {
+            return Err("Clear the filter before editing the playlist".to_string());
+        }
+        // Edits refer to the positions on the server, which are only known once the previous
+        // edit is applied
+        if !self.pending.is_empty() {
+            return Err("Wait for the previous change to be saved".to_string());
+        }
+        Ok(id.clone())
+    }
+
+    /// Sends the change to the server, keeping the items before the change in case it fails
+    fn send_edit(
+        &mut self,
+        id: PlaylistID,
+        removed: Vec<usize>,
+        added: Vec<MediaID>,
+        backup: Vec<Media>,
+    ) -> Action {
+        let req = ToQueryWorker::new(HighLevelQuery::UpdatePlaylist(UpdatePlaylistParams {
+            playlist_id: id,
+            name: None,
+            comment: None,
+            public: None,
+            song_id_to_add: Some(added),
+            song_index_to_remove: Some(removed),
+        }));
+        self.pending.insert(req.ticket, backup);
+        Action::ToQuery(req)
+    }
+
+    /// Restores the items saved before or after a change, and applies the same to the server
+    fn travel(&mut self, undo: bool) -> Action {
+        let id = match self.editable() {
+            Ok(id) => id,
+            Err(e) => return Action::Targeted(TargetedAction::Err(e)),
+        };
+        let state = if undo {
+            self.history.undo()
+        } else {
+            self.history.redo()
+        };
+        let Some(items) = state.cloned() else {
+            return Action::Targeted(TargetedAction::Info(
+                if undo {
+                    "Already at the oldest change"
+                } else {
+                    "Already at the newest change"
+                }
+                .to_string(),
+            ));
+        };
+        // Everything after the first item that differs is removed and added again, as the server
+        // can only append items
+        let from = self
+            .items
+            .iter()
+            .zip(&items)
+            .take_while(|(a, b)| a.id == b.id)
+            .count();
+        let removed = (from..self.items.len()).collect();
+        let added = items[from..].iter().map(|m| m.id.clone()).collect();
+        let backup = std::mem::replace(&mut self.items, items);
+        self.table
+            .reset_rows(Self::gen_rows(&self.items), self.table.get_current());
+        self.bar.update_max(self.items.len() as u32);
+        self.send_edit(id, removed, added, backup)
+    }
+
+    /// Called once the server responds to the change made by [`Self::edit_playlist`]
+    pub fn finish_edit(&mut self, ticket: usize, res: &Result<(), String>) {
+        let Some(backup) = self.pending.remove(&ticket) else {
+            return;
+        };
+        if res.is_err() {
+            // The recorded changes lead to the items that do not exist on the server
+            self.history.clear();
+            self.items = backup;
+            self.table
+                .reset_rows(Self::gen_rows(&self.items), self.table.get_current());
+            self.bar.update_max(self.items.len() as u32);
         }
     }
 
+    /// Query that fetches the displayed items again
+    pub fn refresh_query(&self) -> HighLevelQuery {
+        self.source.to_query(self.name.to_string())
+    }
     pub fn set_star(&mut self, media: MediaID, star: bool) -> Option<Action> {
         let updated = self
-            .playlist
-            .entry
+            .items
             .clone()
             .into_iter()
             .map(|mut m| {
@@ -167,11 +390,20 @@ impl Loaded {
                 m
             })
             .collect();
-        self.playlist.entry = updated;
-        let rows = Self::gen_rows(&self.playlist.entry);
+        self.items = updated;
+        let rows = Self::gen_rows(&self.items);
         self.table.set_rows(rows);
         None
     }
+    pub fn set_rating(&mut self, media: MediaID, rating: u8) {
+        for m in self.items.iter_mut() {
+            if m.id == media {
+                m.set_rating(rating);
+            }
+        }
+        let rows = Self::gen_rows(&self.items);
+        self.table.set_rows(rows);
+    }
 }
 
 impl Renderable for Loaded {
@@ -179,7 +411,7 @@ impl Renderable for Loaded {
         let title = if let Some(pos) = self.table.get_current() {
             let (len, extra) = match &self.filter {
                 Some((len, filter)) => (*len, format!(" (Filter: {})", filter)),
-                None => (self.playlist.entry.len(), "".to_string()),
+                None => (self.items.len(), "".to_string()),
             };
             format!(
                 "{} ({}/{}){}",
@@ -195,7 +427,7 @@ impl Renderable for Loaded {
         } else {
             let (len, extra) = match &self.filter {
                 Some((len, filter)) => (*len, format!(" (Filter: {})", filter)),
-                None => (self.playlist.entry.len(), "".to_string()),
+                None => (self.items.len(), "".to_string()),
             };
             format!("{} ({}){}", self.name, len, extra)
         };
@@ -228,7 +460,7 @@ impl HandleKeySeq<PlaylistQueueAction> for Loaded {
             PlaylistQueueAction::ViewInfo => {
                 if let Some(pos) = self.table.get_current() {
                     KeySeqResult::ActionNeeded(Action::Targeted(TargetedAction::ViewMediaInfo(
-                        self.playlist.entry[pos].clone(),
+                        self.items[pos].clone(),
                     )))
                 } else {
                     KeySeqResult::NoActionNeeded
@@ -238,12 +470,11 @@ impl HandleKeySeq<PlaylistQueueAction> for Loaded {
                 let (selection, action) = self.table.get_selection_reset();
                 let mut items: Vec<Action> = match selection {
                     VisualSelection::Single(idx) => {
-                        let item = self.playlist.entry[idx].clone();
+                        let item = self.items[idx].clone();
                         vec![(item.id, item.starred == None)]
                     }
                     VisualSelection::Multiple { map, temp: _ } => self
-                        .playlist
-                        .entry
+                        .items
                         .iter()
                         .zip(map)
                         .filter_map(|(m, selected)| {
@@ -271,6 +502,37 @@ impl HandleKeySeq<PlaylistQueueAction> for Loaded {
 
                 KeySeqResult::ActionNeeded(Action::Multiple(items))
             }
+            PlaylistQueueAction::SetRating(rating) => {
+                let rating = rating.min(5);
+                let (selection, action) = self.table.get_selection_reset();
+                let media: Vec<(MediaID, u8)> = match selection {
+                    VisualSelection::Single(idx) => {
+                        vec![(self.items[idx].id.clone(), self.items[idx].get_rating())]
+                    }
+                    VisualSelection::Multiple { map, temp: _ } => self
+                        .items
+                        .iter()
+                        .zip(map)
+                        .filter(|(_, selected)| *selected)
+                        .map(|(m, _)| (m.id.clone(), m.get_rating()))
+                        .collect(),
+                    VisualSelection::None => vec![],
+                };
+                let mut items: Vec<Action> = media
+                    .into_iter()
+                    .map(|(id, previous)| {
+                        Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SetRating {
+                            media: id,
+                            rating,
+                            previous,
+                        }))
+                    })
+                    .collect();
+                if let Some(a) = action {
+                    items.push(a);
+                }
+                KeySeqResult::ActionNeeded(Action::Multiple(items))
+            }
             PlaylistQueueAction::Add(pos) => match self.add_selection_to_queue(pos, false) {
                 Some(a) => KeySeqResult::ActionNeeded(a),
                 None => KeySeqResult::NoActionNeeded,
@@ -278,16 +540,25 @@ impl HandleKeySeq<PlaylistQueueAction> for Loaded {
             PlaylistQueueAction::Refresh => {
                 self.table.bump_cursor_pos();
                 KeySeqResult::ActionNeeded(Action::ToQuery(ToQueryWorker::new(
-                    HighLevelQuery::SelectPlaylist(GetPlaylistParams {
-                        name: self.name.to_string(),
-                        id: self.playlist.id.clone(),
-                    }),
+                    self.refresh_query(),
                 )))
             }
             PlaylistQueueAction::RandomAdd(pos) => match self.add_selection_to_queue(pos, true) {
                 Some(a) => KeySeqResult::ActionNeeded(a),
                 None => KeySeqResult::NoActionNeeded,
             },
+            PlaylistQueueAction::Delete
+            | PlaylistQueueAction::MoveUp
+            | PlaylistQueueAction::MoveDown => match self.edit_playlist(action) {
+                Some(a) => KeySeqResult::ActionNeeded(a),
+                None => KeySeqResult::NoActionNeeded,
+            },
+            PlaylistQueueAction::Undo => KeySeqResult::ActionNeeded(self.travel(true)),
+            PlaylistQueueAction::Redo => KeySeqResult::ActionNeeded(self.travel(false)),
+            PlaylistQueueAction::Download => match self.download_selection() {
+                Some(a) => KeySeqResult::ActionNeeded(a),
+                None => KeySeqResult::NoActionNeeded,
+            },
             PlaylistQueueAction::AddToPlaylist => {
                 let (vs, action) = self.table.get_selection_reset();
 
@@ -303,10 +574,9 @@ impl HandleKeySeq<PlaylistQueueAction> for Loaded {
                 };
 
                 let ids = match selection {
-                    Selection::Single(i) => vec![self.playlist.entry[i].id.clone()],
+                    Selection::Single(i) => vec![self.items[i].id.clone()],
                     Selection::Multiple(items) => self
-                        .playlist
-                        .entry
+                        .items
                         .iter()
                         .zip(items)
                         .filter(|(_, bool)| *bool)
@@ -359,8 +629,7 @@ impl HandleSearch for Loaded {
         } else {
             let mut count = 0;
             let highlight: Vec<bool> = self
-                .playlist
-                .entry
+                .items
                 .iter()
                 .map(|i| {
                     let a = i.title.to_lowercase().contains(&search.to_lowercase());
@@ -408,12 +677,11 @@ impl HandleFilter for Loaded {
             self.filter = None;
             self.table.reset_visibility();
             self.table.bump_cursor_pos();
-            self.bar.update_max(self.playlist.entry.len() as u32);
+            self.bar.update_max(self.items.len() as u32);
         } else {
             let mut count = 0;
             let visibility: Vec<bool> = self
-                .playlist
-                .entry
+                .items
                 .iter()
                 .map(|i| {
                     let a = i.title.to_lowercase().contains(&filter.to_lowercase());
@@ -1,5 +1,7 @@
+use std::{collections::VecDeque, time::Duration};
+
 use crossterm::event::KeyEvent;
-use rand::{rng, seq::SliceRandom};
+use rand::{rng, seq::SliceRandom, Rng};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
@@ -13,6 +15,7 @@ use crate::{
         action::{Action, QueueAction, TargetedAction},
         localaction::PlayQueueAction,
     },
+    audiocache::AudioCache,
     compid::CompID,
     components::{
         lib::{
@@ -28,13 +31,32 @@ use crate::{
             renderable::Renderable,
         },
     },
-    config::{keybindings::KeyBindings, Config},
-    helper::selection::{ModifiableList, Selection},
-    osclient::{response::getplaylist::Media, types::MediaID},
-    playerworker::player::{FromPlayerWorker, QueueLocation, ToPlayerWorker},
+    config::{
+        keybindings::KeyBindings,
+        radioconfig::{RadioConfig, RadioSource},
+        Config,
+    },
+    datastore::{QueueCursor, QueueState},
+    helper::{
+        history::History,
+        selection::{ModifiableList, Selection},
+    },
+    osclient::{
+        response::{getplaylist::Media, getplayqueue::RemotePlayQueue},
+        types::{ArtistID, MediaID},
+    },
+    playerworker::player::{
+        FromPlayerWorker, PlayOrder, QueueLocation, RepeatMode, ToPlayerWorker,
+    },
     queryworker::{
         highlevelquery::HighLevelQuery,
-        query::{QueryStatus, ToQueryWorker},
+        query::{
+            download::DownloadParams,
+            exportplaylist::{ExportParams, ExportSource},
+            radio::RadioParams,
+            setrating::SetRatingResponse,
+            QueryStatus, ResponseType, ToQueryWorker,
+        },
     },
 };
 
@@ -65,6 +87,28 @@ enum CurrentItem {
     InQueue(usize),
 }
 
+impl From<QueueCursor> for CurrentItem {
+    fn from(value: QueueCursor) -> Self {
+        match value {
+            QueueCursor::BeforeFirst => CurrentItem::BeforeFirst,
+            QueueCursor::AfterLast => CurrentItem::AfterLast,
+            QueueCursor::NotInQueue(idx) => CurrentItem::NotInQueue(idx),
+            QueueCursor::InQueue(idx) => CurrentItem::InQueue(idx),
+        }
+    }
+}
+
+impl From<&CurrentItem> for QueueCursor {
+    fn from(value: &CurrentItem) -> Self {
+        match value {
+            CurrentItem::BeforeFirst => QueueCursor::BeforeFirst,
+            CurrentItem::AfterLast => QueueCursor::AfterLast,
+            CurrentItem::NotInQueue(idx) => QueueCursor::NotInQueue(*idx),
+            CurrentItem::InQueue(idx) => QueueCursor::InQueue(*idx),
+        }
+    }
+}
+
 pub struct PlayQueue {
     list: ModifiableList<Media>,
     now_playing: CurrentItem,
@@ -72,6 +116,23 @@ pub struct PlayQueue {
     enabled: bool,
     config: Config,
     bar: ScrollBar,
+    repeat: RepeatMode,
+    order: PlayOrder,
+    /// Item picked to be played next in [`PlayOrder::Random`], along with the index of the item
+    /// that was being played when it was picked. This is kept so that the preloaded item and the
+    /// item played next are the same.
+    random_next: Option<(Option<usize>, usize)>,
+    cache: Option<AudioCache>,
+    /// Uncached items are skipped if this is set
+    offline: bool,
+    /// Changes made to the queue by the user, along with the play cursor at the time
+    history: History<(Vec<Media>, CurrentItem)>,
+    /// Radio settings, which can be changed while the program is running
+    radio: RadioConfig,
+    /// True if the radio is looking for more media, in which case it does not look again
+    radio_pending: bool,
+    /// Media played recently, newest first. These are not added to the queue again by the radio.
+    recent: VecDeque<Media>,
 }
 
 /// There are 4 unique states each item in the list can have:
@@ -92,6 +153,9 @@ impl PlayQueue {
         }
         let empty = vec![];
         Self {
+            cache: AudioCache::new(&config),
+            offline: config.features.cache.offline,
+            radio: config.features.radio.clone(),
             config: config.clone(),
             enabled,
             table: VisualTable::new(
@@ -100,6 +164,7 @@ impl PlayQueue {
                 [
                     Constraint::Max(1),
                     Constraint::Min(0),
+                    Constraint::Length(3),
                     Constraint::Length(2),
                 ]
                 .to_vec(),
@@ -108,8 +173,19 @@ impl PlayQueue {
             list: ModifiableList::new(empty),
             now_playing: CurrentItem::InQueue(0),
             bar: ScrollBar::new(0, 0),
+            repeat: RepeatMode::default(),
+            order: PlayOrder::default(),
+            random_next: None,
+            history: History::default(),
+            radio_pending: false,
+            recent: VecDeque::new(),
         }
     }
+    /// Sets the media played in the previous sessions, newest first
+    pub fn with_recent(mut self, items: Vec<Media>) -> Self {
+        self.recent = items.into_iter().take(self.radio.dedup).collect();
+        self
+    }
     pub fn set_star(&mut self, media: &MediaID, star: bool) {
         self.list.0 = self
             .list
@@ -129,6 +205,156 @@ impl PlayQueue {
             .collect();
         self.regen_rows();
     }
+    pub fn set_rating(&mut self, media: &MediaID, rating: u8) {
+        for m in self.list.0.iter_mut() {
+            if m.id == *media {
+                m.set_rating(rating);
+            }
+        }
+        self.regen_rows();
+    }
+
+    /// Returns the items in the queue along with the play cursor so that they can be saved
+    pub fn get_state(&self) -> (Vec<Media>, QueueCursor) {
+        (self.list.0.clone(), (&self.now_playing).into())
+    }
+
+    /// Replace the entire queue. The item under the play cursor is loaded paused at the given
+    /// position.
+    fn replace_queue(
+        &mut self,
+        items: Vec<Media>,
+        cursor: CurrentItem,
+        position: Duration,
+    ) -> Vec<Action> {
+        self.list = ModifiableList::new(items);
+        self.now_playing = cursor;
+        self.history.clear();
+        self.bar.update_max(self.list.len() as u32);
+        self.table.reset_selections();
+        self.regen_rows();
+        let current = match self.now_playing {
+            CurrentItem::InQueue(idx) => self.list.0.get(idx),
+            _ => None,
+        };
+        match current {
+            Some(media) => vec![
+                Action::ToPlayer(ToPlayerWorker::ResumeFrom(position)),
+                Action::ToPlayer(ToPlayerWorker::PlayMedia {
+                    media: media.clone(),
+                }),
+            ],
+            None => vec![Action::ToPlayer(ToPlayerWorker::Stop)],
+        }
+    }
+
+    /// Replace the queue with the state saved in the last session
+    pub fn restore(&mut self, state: QueueState) -> Action {
+        let mut actions = vec![
+            Action::ToPlayer(ToPlayerWorker::SetVolume(state.volume)),
+            Action::ToPlayer(ToPlayerWorker::SetSpeed(state.speed)),
+        ];
+        actions.append(&mut self.replace_queue(state.items, state.cursor.into(), state.position));
+        Action::Multiple(actions)
+    }
+
+    /// Replace the queue with the one saved on the server
+    fn resume_remote(&mut self, remote: RemotePlayQueue) -> Action {
+        let cursor = match remote
+            .current
+            .and_then(|id| remote.entry.iter().position(|m| m.id == id))
+        {
+            Some(idx) => CurrentItem::InQueue(idx),
+            None => CurrentItem::BeforeFirst,
+        };
+        let len = remote.entry.len();
+        let mut actions = self.replace_queue(
+            remote.entry,
+            cursor,
+            Duration::from_millis(remote.position.unwrap_or(0)),
+        );
+        actions.push(Action::Targeted(TargetedAction::Info(
+            match (remote.changed_by, remote.changed) {
+                (Some(by), Some(at)) => {
+                    format!("Resumed {len} items saved by {by} at {at}")
+                }
+                _ => format!("Resumed {len} items saved on the server"),
+            },
+        )));
+        Action::Multiple(actions)
+    }
+
+    /// Asks where the items from the given index onwards should be saved as a playlist
+    fn save_as_playlist(&self, from: usize) -> Action {
+        let ids: Vec<MediaID> = self.list.0[from.min(self.list.len())..]
+            .iter()
+            .map(|m| m.id.clone())
+            .collect();
+        if ids.is_empty() {
+            Action::Targeted(TargetedAction::Err("Nothing to save".to_string()))
+        } else {
+            Action::Targeted(TargetedAction::PrepareSaveAsPlaylist(ids))
+        }
+    }
+
+    /// Items in the queue and the play cursor, saved before and after each change so that it can be
+    /// undone
+    fn snapshot(&self) -> (Vec<Media>, CurrentItem) {
+        (self.list.0.clone(), self.now_playing.clone())
+    }
+
+    /// Replace the queue with the one saved before or after a change. The play cursor follows the
+    /// item being played if it is in the restored queue, and the playback is not interrupted.
+    fn travel(&mut self, undo: bool) -> Action {
+        let state = if undo {
+            self.history.undo()
+        } else {
+            self.history.redo()
+        };
+        let Some((items, saved)) = state.cloned() else {
+            return Action::Targeted(TargetedAction::Info(
+                if undo {
+                    "Already at the oldest change"
+                } else {
+                    "Already at the newest change"
+                }
+                .to_string(),
+            ));
+        };
+        let playing = match self.now_playing {
+            CurrentItem::InQueue(idx) => self.list.0.get(idx).map(|m| (idx, m.id.clone())),
+            _ => None,
+        };
+        self.now_playing = match (playing, &self.now_playing) {
+            (Some((idx, id)), _) => {
+                let saved_idx = match saved {
+                    CurrentItem::InQueue(i) | CurrentItem::NotInQueue(i) => Some(i),
+                    _ => None,
+                };
+                match saved_idx
+                    .filter(|&i| items.get(i).is_some_and(|m| m.id == id))
+                    .or_else(|| items.iter().position(|m| m.id == id))
+                {
+                    Some(i) => CurrentItem::InQueue(i),
+                    None if items.is_empty() => CurrentItem::BeforeFirst,
+                    None => CurrentItem::NotInQueue(idx.min(items.len() - 1)),
+                }
+            }
+            // The item being played was deleted, and this may have brought it back
+            (None, CurrentItem::NotInQueue(idx)) => match saved {
+                CurrentItem::InQueue(_) | CurrentItem::NotInQueue(_) => saved,
+                _ if items.is_empty() => CurrentItem::BeforeFirst,
+                _ => CurrentItem::NotInQueue((*idx).min(items.len() - 1)),
+            },
+            (None, current) => current.clone(),
+        };
+        self.list = ModifiableList::new(items);
+        self.random_next = None;
+        self.bar.update_max(self.list.len() as u32);
+        let rows = Self::gen_rows_from(&self.list.0, &self.now_playing);
+        self.table.reset_rows(rows, self.table.get_current());
+        self.preload_next()
+    }
 
     /// Regenerate all rows based on the current state, and rerender the table in full
     fn regen_rows(&mut self) {
@@ -161,35 +387,268 @@ impl PlayQueue {
         action
     }
 
-    fn skip(&mut self, skip_by: i32) -> Action {
-        let max_len = self.list.0.len() as i32;
+    /// Whether the item can be played. Only cached items can be played in offline mode.
+    fn playable(&self, idx: usize) -> bool {
+        if !self.offline {
+            return true;
+        }
+        match (&self.cache, self.list.0.get(idx)) {
+            (Some(cache), Some(m)) => cache.is_cached(&m.id),
+            _ => false,
+        }
+    }
 
-        fn clean(index: i32, max_len: i32) -> CurrentItem {
-            if index >= 0 {
-                if index >= max_len {
-                    CurrentItem::AfterLast
+    /// Index of the first playable item right after (or before if `forward` is false) the given
+    /// position. Wraps around if the whole queue is being repeated.
+    fn step(&self, from: &CurrentItem, forward: bool) -> Option<usize> {
+        let len = self.list.len();
+        if len == 0 {
+            return None;
+        }
+        let wrap = self.repeat != RepeatMode::Off;
+        let next = |idx: usize| {
+            if forward {
+                if idx + 1 < len {
+                    Some(idx + 1)
                 } else {
-                    CurrentItem::InQueue(index as usize)
+                    wrap.then_some(0)
                 }
+            } else if idx > 0 {
+                Some(idx.min(len) - 1)
             } else {
-                CurrentItem::BeforeFirst
+                wrap.then_some(len - 1)
             }
+        };
+        let mut idx = match (from, forward) {
+            (CurrentItem::BeforeFirst, true) => Some(0),
+            (CurrentItem::AfterLast, false) => Some(len - 1),
+            (CurrentItem::AfterLast, true) => wrap.then_some(0),
+            (CurrentItem::BeforeFirst, false) => wrap.then_some(len - 1),
+            // The item before the deleted one has not been played again yet
+            (CurrentItem::NotInQueue(idx), false) => Some((*idx).min(len - 1)),
+            (CurrentItem::InQueue(idx) | CurrentItem::NotInQueue(idx), _) => next(*idx),
+        };
+        // Each item is checked at most once so that this ends even if nothing can be played
+        for _ in 0..len {
+            let i = idx?;
+            if self.playable(i) {
+                return Some(i);
+            }
+            idx = next(i);
         }
+        None
+    }
 
-        let ci = match &self.now_playing {
-            CurrentItem::BeforeFirst => clean(skip_by - 1, max_len),
-            CurrentItem::AfterLast => clean(max_len + skip_by, max_len),
-            CurrentItem::InQueue(index) => clean(*index as i32 + skip_by, max_len),
-            CurrentItem::NotInQueue(index) => {
-                if skip_by <= 0 {
-                    clean(*index as i32 + skip_by + 1, max_len)
+    /// Picks a random item other than the current one, and keeps it until the current item changes
+    fn random_index(&mut self) -> Option<usize> {
+        let current = match self.now_playing {
+            CurrentItem::InQueue(idx) | CurrentItem::NotInQueue(idx) => Some(idx),
+            _ => None,
+        };
+        if let Some((from, idx)) = self.random_next {
+            if from == current && idx < self.list.len() && self.playable(idx) {
+                return Some(idx);
+            }
+        }
+        let candidates: Vec<usize> = (0..self.list.len()).filter(|i| self.playable(*i)).collect();
+        let others: Vec<usize> = candidates
+            .iter()
+            .copied()
+            .filter(|i| Some(*i) != current)
+            .collect();
+        let idx = match (others.len(), candidates.first()) {
+            (0, None) => return None,
+            // The current item is only played again if there is nothing else
+            (0, Some(only)) => *only,
+            (len, _) => others[rng().random_range(0..len)],
+        };
+        self.random_next = Some((current, idx));
+        Some(idx)
+    }
+
+    /// Index of the item that plays after the current one. `natural` is true if the current item
+    /// has been played to the end, in which case the current item is played again if
+    /// [`RepeatMode::One`] is set.
+    fn next_index(&mut self, natural: bool) -> Option<usize> {
+        if natural && self.repeat == RepeatMode::One {
+            if let CurrentItem::InQueue(idx) = self.now_playing {
+                if self.playable(idx) {
+                    return Some(idx);
+                }
+            }
+        }
+        match self.order {
+            PlayOrder::Normal => self.step(&self.now_playing, true),
+            PlayOrder::Random => self.random_index(),
+            // Reverse order starts from the last item
+            PlayOrder::Reverse => match self.now_playing {
+                CurrentItem::BeforeFirst => self.step(&CurrentItem::AfterLast, false),
+                _ => self.step(&self.now_playing, false),
+            },
+        }
+    }
+
+    /// Index of the item that plays when the user goes back
+    fn prev_index(&self) -> Option<usize> {
+        match self.order {
+            PlayOrder::Reverse => self.step(&self.now_playing, true),
+            PlayOrder::Normal | PlayOrder::Random => self.step(&self.now_playing, false),
+        }
+    }
+
+    /// Media under the play cursor, or the one played last if there is none
+    fn current_media(&self) -> Option<&Media> {
+        match self.now_playing {
+            CurrentItem::InQueue(idx) => self.list.0.get(idx),
+            _ => None,
+        }
+        .or(self.recent.front())
+    }
+
+    /// Parameters of the query that finds more media from the current radio source
+    fn radio_query(&self) -> Result<RadioParams, String> {
+        let count = self.radio.count;
+        match self.radio.source {
+            RadioSource::Similar => {
+                let mut artists: Vec<ArtistID> = vec![];
+                for id in self.recent.iter().filter_map(|m| m.artist_id.clone()) {
+                    if artists.len() == self.radio.seeds {
+                        break;
+                    }
+                    if !artists.iter().any(|a| a.0 == id) {
+                        artists.push(ArtistID(id));
+                    }
+                }
+                if artists.is_empty() {
+                    Err("Play something first so that the radio can find similar media".to_string())
                 } else {
-                    clean(*index as i32 + skip_by, max_len)
+                    Ok(RadioParams::Similar { artists, count })
                 }
             }
+            RadioSource::TopSongs => match self.current_media().and_then(|m| m.artist.clone()) {
+                Some(artist) => Ok(RadioParams::TopSongs { artist, count }),
+                None => Err("Play something first so that the radio knows the artist".to_string()),
+            },
+            RadioSource::Random => Ok(RadioParams::Random {
+                count,
+                genre: self.radio.genre.clone(),
+                from_year: self.radio.from_year,
+                to_year: self.radio.to_year,
+            }),
+        }
+    }
+
+    /// Looks for more media if the radio is on and the queue is about to run out
+    fn radio_refill(&mut self) -> Option<Result<Action, String>> {
+        if !self.radio.enable || self.radio_pending || self.get_time_left().0 > self.radio.threshold
+        {
+            return None;
+        }
+        Some(self.radio_query().map(|params| {
+            self.radio_pending = true;
+            Action::ToQuery(ToQueryWorker::new(HighLevelQuery::Radio(params)))
+        }))
+    }
+
+    /// Shows a message about a change to the radio settings, and looks for more media under the
+    /// new settings
+    fn with_radio_refill(&mut self, msg: String) -> Action {
+        let info = Action::Targeted(TargetedAction::Info(msg));
+        match self.radio_refill() {
+            Some(Ok(query)) => Action::Multiple(vec![info, query]),
+            Some(Err(msg)) => {
+                Action::Multiple(vec![info, Action::Targeted(TargetedAction::Err(msg))])
+            }
+            None => info,
+        }
+    }
+
+    /// Appends the media found by the radio, skipping the ones that have been played recently or
+    /// are already in the queue
+    fn add_radio(&mut self, mut items: Vec<Media>) -> Action {
+        items.shuffle(&mut rng());
+        let mut added: Vec<Media> = vec![];
+        for m in items {
+            if added.len() == self.radio.count {
+                break;
+            }
+            if !self
+                .recent
+                .iter()
+                .chain(self.list.0.iter())
+                .chain(added.iter())
+                .any(|i| i.id == m.id)
+            {
+                added.push(m);
+            }
+        }
+        if added.is_empty() {
+            return Action::Targeted(TargetedAction::Info(
+                "The radio could not find any media that has not been played recently".to_string(),
+            ));
+        }
+        let action = self.add_to_queue(added, QueueLocation::Last);
+        self.with_preload(action)
+    }
+
+    /// Let the player load the next item ahead of time so that there is no gap between them
+    fn preload_next(&mut self) -> Action {
+        Action::ToPlayer(ToPlayerWorker::Preload(
+            self.next_index(true).map(|idx| self.list.0[idx].clone()),
+        ))
+    }
+
+    /// The next item may have changed after the queue is modified, so the player is notified
+    /// alongside the given action
+    fn with_preload(&mut self, action: Option<Action>) -> Action {
+        match action {
+            Some(a) => Action::Multiple(vec![a, self.preload_next()]),
+            None => self.preload_next(),
+        }
+    }
+
+    /// Skip to the next item. The cursor is placed after the last item if there are none left.
+    fn skip_next(&mut self, natural: bool) -> Action {
+        let to = match self.next_index(natural) {
+            Some(idx) => CurrentItem::InQueue(idx),
+            None if self.order == PlayOrder::Reverse => CurrentItem::BeforeFirst,
+            None => CurrentItem::AfterLast,
         };
+        self.skip_to(to)
+    }
 
-        self.skip_to(ci)
+    /// Skip to the previous item. The cursor is placed before the first item if there are none
+    /// left.
+    fn skip_previous(&mut self) -> Action {
+        let to = match self.prev_index() {
+            Some(idx) => CurrentItem::InQueue(idx),
+            None if self.order == PlayOrder::Reverse => CurrentItem::AfterLast,
+            None => CurrentItem::BeforeFirst,
+        };
+        self.skip_to(to)
+    }
+
+    /// Switch to or from offline mode. The next item may be different as a result.
+    pub fn set_offline(&mut self, offline: bool) -> Action {
+        self.offline = offline;
+        self.random_next = None;
+        self.preload_next()
+    }
+
+    /// Load the next item again, such as when the way it is streamed has changed
+    pub fn reload_next(&mut self) -> Action {
+        self.preload_next()
+    }
+
+    /// Change the repeat mode and the play order, and let other components know about it
+    fn set_play_mode(&mut self, repeat: RepeatMode, order: PlayOrder) -> Action {
+        self.repeat = repeat;
+        self.order = order;
+        self.random_next = None;
+        self.with_preload(Some(Action::Targeted(TargetedAction::PlayModeChanged {
+            repeat,
+            order,
+        })))
     }
 
     /// Generate all the rows that appears in the table.
@@ -207,15 +666,26 @@ impl PlayQueue {
         fn gen_rows_part(ms: &[Media], style: Style) -> Vec<Row<'static>> {
             ms.iter()
                 .map(|m| {
-                    Row::new(vec![" ".to_string(), m.title.clone(), m.get_fav_marker()])
-                        .style(style)
+                    Row::new(vec![
+                        " ".to_string(),
+                        m.title.clone(),
+                        m.get_rating_marker(),
+                        m.get_fav_marker(),
+                    ])
+                    .style(style)
                 })
                 .collect()
         }
 
         /// Function that generates a single row with a specified cursor and style
         fn gen_playing_item(ms: &Media, cursor: String, style: Style) -> Row<'static> {
-            Row::new(vec![cursor, ms.title.clone(), ms.get_fav_marker()]).style(style)
+            Row::new(vec![
+                cursor,
+                ms.title.clone(),
+                ms.get_rating_marker(),
+                ms.get_fav_marker(),
+            ])
+            .style(style)
         }
 
         /// Function that generates the entire list given the list of media to show in the table
@@ -309,28 +779,30 @@ impl PlayQueue {
             QueueLocation::Last => max,
         };
         let len = items.len();
+        let before = self.snapshot();
         self.list.add_rows_at(items, idx);
         self.bar.update_max(self.list.len() as u32);
 
         let rows = Self::gen_rows_from(&self.list.0, &self.now_playing);
         self.table.add_rows_at(rows, idx, len);
 
-        if len != 0 {
-            if max == 0 {
-                // If the queue was empty and items were added to the queue, play them
-                Some(self.skip_to(CurrentItem::InQueue(0)))
-            } else if matches!(self.now_playing, CurrentItem::AfterLast) {
-                // If the new item was added after the last item, then play the new item
-                Some(self.skip_to(CurrentItem::InQueue(max)))
-            } else if matches!(at, QueueLocation::Front) {
-                // If the new item overwrote the current item, then play the new item
-                Some(self.skip_to(self.now_playing.clone()))
-            } else {
-                None
-            }
+        if len == 0 {
+            return None;
+        }
+        let action = if max == 0 {
+            // If the queue was empty and items were added to the queue, play them
+            Some(self.skip_to(CurrentItem::InQueue(0)))
+        } else if matches!(self.now_playing, CurrentItem::AfterLast) {
+            // If the new item was added after the last item, then play the new item
+            Some(self.skip_to(CurrentItem::InQueue(max)))
+        } else if matches!(at, QueueLocation::Front) {
+            // If the new item overwrote the current item, then play the new item
+            Some(self.skip_to(self.now_playing.clone()))
         } else {
             None
-        }
+        };
+        self.history.record(before, self.snapshot());
+        action
     }
     fn gen_block(&self, title: String) -> Block<'static> {
         let style = if self.enabled {
@@ -349,7 +821,7 @@ impl PlayQueue {
         let (count, length) = self.get_time_left();
         let minute = length / 60;
         let secs = length % 60;
-        let bottom_title = if minute >= 60 {
+        let mut bottom_title = if minute >= 60 {
             let hours = minute / 60;
             format!(
                 "Remaining: {} ({:02}:{:02}:{:02})",
@@ -361,6 +833,9 @@ impl PlayQueue {
         } else {
             format!("Remaining: {} ({:02}:{:02})", count, minute, secs)
         };
+        if self.radio.enable {
+            bottom_title.push_str(&format!(" | Radio: {}", self.radio.source));
+        }
         Block::bordered()
             .title(title)
             .title_bottom(bottom_title)
@@ -376,13 +851,56 @@ impl PlayQueue {
             CurrentItem::NotInQueue(i) | CurrentItem::InQueue(i) => i + 1,
         };
         if shuffle_from < self.list.len() {
+            let before = self.snapshot();
             self.table.reset_selections();
             let slice = &mut self.list.0[shuffle_from..];
             let mut rng = rng();
             slice.shuffle(&mut rng);
+            self.history.record(before, self.snapshot());
             self.regen_rows();
         }
     }
+
+    /// Move the selected items to a new position with the given function, which receives the
+    /// indices of all items in the queue. The cursor and the currently playing item keep pointing
+    /// at the same items after the move.
+    fn move_selection<F>(&mut self, reorder: F) -> KeySeqResult
+    where
+        F: FnOnce(&mut ModifiableList<usize>, &Selection),
+    {
+        let (vs, action) = self.table.get_selection_reset();
+        let selection = match vs {
+            VisualSelection::Single(index) => Selection::Single(index),
+            VisualSelection::Multiple { map, temp: _ } => Selection::Multiple(map),
+            VisualSelection::None => {
+                return match action {
+                    Some(a) => KeySeqResult::ActionNeeded(a),
+                    None => KeySeqResult::NoActionNeeded,
+                }
+            }
+        };
+        let cursor = self.table.get_current();
+        let before = self.snapshot();
+
+        let mut order = ModifiableList::new((0..self.list.len()).collect());
+        reorder(&mut order, &selection);
+        let new_pos = |old: usize| order.iter().position(|&i| i == old).unwrap_or(old);
+
+        self.now_playing = match self.now_playing {
+            CurrentItem::InQueue(idx) => CurrentItem::InQueue(new_pos(idx)),
+            CurrentItem::NotInQueue(idx) => CurrentItem::NotInQueue(new_pos(idx)),
+            ref other => other.clone(),
+        };
+        let cursor = cursor.map(new_pos);
+        self.list.0 = order.iter().map(|&i| self.list.0[i].clone()).collect();
+        self.random_next = None;
+        self.history.record(before, self.snapshot());
+
+        let rows = Self::gen_rows_from(&self.list.0, &self.now_playing);
+        self.table.reset_rows(rows, cursor);
+
+        KeySeqResult::ActionNeeded(self.with_preload(action))
+    }
 }
 
 impl Renderable for PlayQueue {
@@ -417,10 +935,37 @@ impl Renderable for PlayQueue {
 
 impl HandlePlayer for PlayQueue {
     fn handle_player(&mut self, pw: FromPlayerWorker) -> Option<Action> {
-        if let FromPlayerWorker::Finished = pw {
-            Some(self.skip(1))
-        } else {
-            None
+        match pw {
+            FromPlayerWorker::Finished => Some(self.skip_next(true)),
+            FromPlayerWorker::Advanced(media) => match self.next_index(true) {
+                Some(idx) if self.list.0[idx].id == media.id => {
+                    self.now_playing = CurrentItem::InQueue(idx);
+                    self.regen_rows();
+                    None
+                }
+                // The queue has been modified after the media was preloaded
+                _ => Some(self.skip_next(true)),
+            },
+            FromPlayerWorker::NowPlaying(Some(media)) => {
+                let preload = match self.now_playing {
+                    CurrentItem::InQueue(idx)
+                        if self.list.0.get(idx).is_some_and(|m| m.id == media.id) =>
+                    {
+                        Some(self.preload_next())
+                    }
+                    _ => None,
+                };
+                if self.recent.front().is_none_or(|m| m.id != media.id) {
+                    self.recent.push_front(media);
+                    self.recent.truncate(self.radio.dedup);
+                }
+                // The radio stays quiet until the user toggles it if it cannot find anything
+                match (preload, self.radio_refill().and_then(Result::ok)) {
+                    (Some(a), Some(b)) => Some(Action::Multiple(vec![a, b])),
+                    (a, b) => a.or(b),
+                }
+            }
+            _ => None,
         }
     }
 }
@@ -430,16 +975,58 @@ impl HandleAction for PlayQueue {
         let a = match action {
             TargetedAction::Shuffle => {
                 self.shuffle();
-                None
+                Some(self.preload_next())
+            }
+            TargetedAction::Skip => Some(self.skip_next(false)),
+            TargetedAction::ResumeRemoteQueue => Some(Action::ToQuery(ToQueryWorker::new(
+                HighLevelQuery::GetPlayQueue,
+            ))),
+            TargetedAction::Previous => Some(self.skip_previous()),
+            TargetedAction::CycleRepeat => Some(self.set_play_mode(
+                match self.repeat {
+                    RepeatMode::Off => RepeatMode::All,
+                    RepeatMode::All => RepeatMode::One,
+                    RepeatMode::One => RepeatMode::Off,
+                },
+                self.order,
+            )),
+            TargetedAction::SetRepeat(repeat) => Some(self.set_play_mode(repeat, self.order)),
+            TargetedAction::CyclePlayOrder => Some(self.set_play_mode(
+                self.repeat,
+                match self.order {
+                    PlayOrder::Normal => PlayOrder::Random,
+                    PlayOrder::Random => PlayOrder::Reverse,
+                    PlayOrder::Reverse => PlayOrder::Normal,
+                },
+            )),
+            TargetedAction::SetPlayOrder(order) => Some(self.set_play_mode(self.repeat, order)),
+            TargetedAction::ToggleRadio => {
+                self.radio.enable = !self.radio.enable;
+                let msg = if self.radio.enable {
+                    format!("Radio enabled ({})", self.radio.source)
+                } else {
+                    "Radio disabled".to_string()
+                };
+                Some(self.with_radio_refill(msg))
+            }
+            TargetedAction::CycleRadioSource => {
+                self.radio.source = self.radio.source.next();
+                Some(self.with_radio_refill(format!("Radio source: {}", self.radio.source)))
+            }
+            TargetedAction::SetRadioSource(source) => {
+                self.radio.source = source;
+                Some(self.with_radio_refill(format!("Radio source: {}", self.radio.source)))
             }
-            TargetedAction::Skip => Some(self.skip(1)),
-            TargetedAction::Previous => Some(self.skip(-1)),
             TargetedAction::Queue(a) => match a {
-                QueueAction::Add(items, at) => self.add_to_queue(items, at),
+                QueueAction::Add(items, at) => {
+                    let action = self.add_to_queue(items, at);
+                    Some(self.with_preload(action))
+                }
                 QueueAction::RandomAdd(mut items, at) => {
                     let mut rng = rand::rng();
                     items.shuffle(&mut rng);
-                    self.add_to_queue(items, at)
+                    let action = self.add_to_queue(items, at);
+                    Some(self.with_preload(action))
                 }
             },
             _ => None,
@@ -477,6 +1064,7 @@ impl HandleKeySeq<PlayQueueAction> for PlayQueue {
                         }
                     }
                 };
+                let before = self.snapshot();
 
                 match self.now_playing {
                     CurrentItem::InQueue(idx) => {
@@ -501,13 +1089,46 @@ impl HandleKeySeq<PlayQueueAction> for PlayQueue {
                 }
 
                 self.list.delete(&selection);
+                self.history.record(before, self.snapshot());
                 self.bar.update_max(self.list.len() as u32);
                 self.regen_rows();
 
-                match action {
-                    Some(a) => KeySeqResult::ActionNeeded(a),
-                    None => KeySeqResult::NoActionNeeded,
-                }
+                KeySeqResult::ActionNeeded(self.with_preload(action))
+            }
+            PlayQueueAction::SaveAsPlaylist => KeySeqResult::ActionNeeded(self.save_as_playlist(0)),
+            PlayQueueAction::SaveRemainingAsPlaylist => {
+                let from = match self.now_playing {
+                    CurrentItem::BeforeFirst => 0,
+                    CurrentItem::AfterLast => self.list.len(),
+                    CurrentItem::NotInQueue(i) | CurrentItem::InQueue(i) => i + 1,
+                };
+                KeySeqResult::ActionNeeded(self.save_as_playlist(from))
+            }
+            PlayQueueAction::Export => KeySeqResult::ActionNeeded(Action::ToQuery(
+                ToQueryWorker::new(HighLevelQuery::ExportPlaylist(ExportParams {
+                    name: "Queue".to_string(),
+                    source: ExportSource::Items(self.list.0.clone()),
+                })),
+            )),
+            PlayQueueAction::Undo => KeySeqResult::ActionNeeded(self.travel(true)),
+            PlayQueueAction::Redo => KeySeqResult::ActionNeeded(self.travel(false)),
+            PlayQueueAction::MoveUp => self.move_selection(|order, selection| {
+                order.move_selected(selection, true);
+            }),
+            PlayQueueAction::MoveDown => self.move_selection(|order, selection| {
+                order.move_selected(selection, false);
+            }),
+            PlayQueueAction::MoveNext => {
+                let at = match self.now_playing {
+                    CurrentItem::BeforeFirst => 0,
+                    CurrentItem::AfterLast => self.list.len(),
+                    CurrentItem::NotInQueue(i) | CurrentItem::InQueue(i) => i + 1,
+                };
+                self.move_selection(|order, selection| order.move_selected_to(selection, at))
+            }
+            PlayQueueAction::MoveToEnd => {
+                let at = self.list.len();
+                self.move_selection(|order, selection| order.move_selected_to(selection, at))
             }
             PlayQueueAction::ToggleStar => {
                 let (selection, action) = self.table.get_selection_reset();
@@ -546,6 +1167,37 @@ impl HandleKeySeq<PlayQueueAction> for PlayQueue {
 
                 KeySeqResult::ActionNeeded(Action::Multiple(items))
             }
+            PlayQueueAction::SetRating(rating) => {
+                let rating = rating.min(5);
+                let (selection, action) = self.table.get_selection_reset();
+                let media: Vec<(MediaID, u8)> = match selection {
+                    VisualSelection::Single(idx) => {
+                        vec![(self.list.0[idx].id.clone(), self.list.0[idx].get_rating())]
+                    }
+                    VisualSelection::Multiple { map, temp: _ } => self
+                        .list
+                        .iter()
+                        .zip(map)
+                        .filter(|(_, selected)| *selected)
+                        .map(|(m, _)| (m.id.clone(), m.get_rating()))
+                        .collect(),
+                    VisualSelection::None => vec![],
+                };
+                let mut items: Vec<Action> = media
+                    .into_iter()
+                    .map(|(id, previous)| {
+                        Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SetRating {
+                            media: id,
+                            rating,
+                            previous,
+                        }))
+                    })
+                    .collect();
+                if let Some(a) = action {
+                    items.push(a);
+                }
+                KeySeqResult::ActionNeeded(Action::Multiple(items))
+            }
             PlayQueueAction::PlaySelected => match self.table.get_current() {
                 Some(idx) => KeySeqResult::ActionNeeded(self.skip_to(CurrentItem::InQueue(idx))),
                 None => KeySeqResult::NoActionNeeded,
@@ -558,7 +1210,7 @@ impl HandleKeySeq<PlayQueueAction> for PlayQueue {
             }
             PlayQueueAction::Randomise => {
                 self.shuffle();
-                KeySeqResult::NoActionNeeded
+                KeySeqResult::ActionNeeded(self.preload_next())
             }
             PlayQueueAction::ViewInfo => match self.table.get_current() {
                 Some(idx) => KeySeqResult::ActionNeeded(Action::Targeted(
@@ -600,6 +1252,35 @@ impl HandleKeySeq<PlayQueueAction> for PlayQueue {
 
                 KeySeqResult::ActionNeeded(actions)
             }
+            PlayQueueAction::Download => {
+                let (selection, action) = self.table.get_selection_reset();
+                let items: Vec<Media> = match selection {
+                    VisualSelection::Single(idx) => vec![self.list.0[idx].clone()],
+                    VisualSelection::Multiple { map, temp: _ } => self
+                        .list
+                        .iter()
+                        .zip(map)
+                        .filter(|(_, selected)| *selected)
+                        .map(|(m, _)| m.clone())
+                        .collect(),
+                    VisualSelection::None => vec![],
+                };
+                let mut actions = vec![];
+                if !items.is_empty() {
+                    let name = if items.len() == 1 {
+                        items[0].title.clone()
+                    } else {
+                        format!("{} items from the queue", items.len())
+                    };
+                    actions.push(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::Download(DownloadParams { name, items }),
+                    )));
+                }
+                if let Some(a) = action {
+                    actions.push(a);
+                }
+                KeySeqResult::ActionNeeded(Action::Multiple(actions))
+            }
         }
     }
 
@@ -621,9 +1302,48 @@ impl Focusable for PlayQueue {
 
 impl HandleQuery for PlayQueue {
     fn handle_query(&mut self, _dest: CompID, _ticket: usize, res: QueryStatus) -> Option<Action> {
-        if let QueryStatus::Requested(HighLevelQuery::SetStar { media, star }) = res {
-            self.set_star(&media, star);
+        match res {
+            QueryStatus::Requested(HighLevelQuery::SetStar { media, star }) => {
+                self.set_star(&media, star);
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SetRating { media, rating, .. }) => {
+                self.set_rating(&media, rating);
+                None
+            }
+            QueryStatus::Finished(ResponseType::SetRating(SetRatingResponse::Failure {
+                media,
+                previous,
+                msg,
+            })) => {
+                self.set_rating(&media, previous);
+                Some(Action::Targeted(TargetedAction::Err(format!(
+                    "Failed to rate the media: {msg}"
+                ))))
+            }
+            QueryStatus::Finished(ResponseType::GetPlayQueue(res)) => match res {
+                Ok(Some(remote)) => Some(self.resume_remote(remote)),
+                Ok(None) => Some(Action::Targeted(TargetedAction::Info(
+                    "There is no queue saved on the server.".to_string(),
+                ))),
+                Err(msg) => Some(Action::Targeted(TargetedAction::Err(format!(
+                    "Failed to fetch the queue from the server: {msg}"
+                )))),
+            },
+            QueryStatus::Finished(ResponseType::Radio(res)) => {
+                self.radio_pending = false;
+                match res {
+                    Ok(items) if self.radio.enable => Some(self.add_radio(items)),
+                    Ok(_) => None,
+                    Err(msg) => Some(Action::Targeted(TargetedAction::Err(format!(
+                        "The radio failed to find more media: {msg}"
+                    )))),
+                }
+            }
+            QueryStatus::Finished(ResponseType::SavePlayQueue(Err(msg))) => Some(Action::Targeted(
+                TargetedAction::Err(format!("Failed to save the queue to the server: {msg}")),
+            )),
+            _ => None,
         }
-        None
     }
 }
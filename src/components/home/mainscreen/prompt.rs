@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use directories::BaseDirs;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear},
+    Frame,
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::{
+    action::action::{Action, Mode, PlaylistEdit, TargetedAction},
+    components::traits::{handleraw::HandleRaw, renderable::Renderable},
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{
+            saveplaylist::{SavePlaylistParams, SaveTarget},
+            ToQueryWorker,
+        },
+    },
+};
+
+/// Replaces the leading `~` with the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), BaseDirs::new()) {
+        (Some(rest), Some(dirs)) => dirs.home_dir().join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Asks for a line of text, such as the name of a playlist
+pub struct Prompt {
+    input: TextArea<'static>,
+    edit: PlaylistEdit,
+}
+
+impl Prompt {
+    pub fn new(edit: PlaylistEdit) -> Self {
+        let (title, initial) = match &edit {
+            PlaylistEdit::Create => ("New playlist".to_string(), String::new()),
+            PlaylistEdit::Rename(p) => (format!("Rename {}", p.name), p.name.clone()),
+            PlaylistEdit::SetComment(p) => (
+                format!("Comment of {}", p.name),
+                p.comment.clone().unwrap_or_default(),
+            ),
+            PlaylistEdit::CreateWith(items) => (
+                format!("Save {} items as a new playlist", items.len()),
+                String::new(),
+            ),
+            PlaylistEdit::Delete(p) => (format!("Delete {}", p.name), String::new()),
+            PlaylistEdit::Import(dir) => (
+                "Playlist file to import".to_string(),
+                format!("{}{}", dir.display(), std::path::MAIN_SEPARATOR),
+            ),
+            PlaylistEdit::Replace { name, .. } => (format!("Replace {}", name), String::new()),
+        };
+        let mut input = TextArea::new(vec![initial]);
+        input.move_cursor(CursorMove::End);
+        input.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default())
+                .title(title),
+        );
+        Self { input, edit }
+    }
+
+    fn close() -> Vec<Action> {
+        vec![
+            Action::Targeted(TargetedAction::ClosePopup),
+            Action::ChangeMode(Mode::Normal),
+        ]
+    }
+
+    /// Action that applies the text to the playlist. Comment may be empty, but names may not.
+    fn submit(&self, text: String) -> Option<Action> {
+        match &self.edit {
+            PlaylistEdit::Create if !text.is_empty() => Some(Action::ToQuery(ToQueryWorker::new(
+                HighLevelQuery::CreatePlaylist(text),
+            ))),
+            PlaylistEdit::CreateWith(items) if !text.is_empty() => Some(Action::ToQuery(
+                ToQueryWorker::new(HighLevelQuery::SavePlaylist(SavePlaylistParams {
+                    target: SaveTarget::New(text),
+                    items: items.clone(),
+                })),
+            )),
+            PlaylistEdit::Import(_) if !text.is_empty() => Some(Action::ToQuery(
+                ToQueryWorker::new(HighLevelQuery::ImportPlaylist(expand_home(&text))),
+            )),
+            PlaylistEdit::Rename(p) if !text.is_empty() && text != p.name => {
+                Some(Action::Targeted(TargetedAction::SetPlaylistInfo {
+                    playlist: p.clone(),
+                    name: Some(text),
+                    comment: None,
+                    public: None,
+                }))
+            }
+            PlaylistEdit::SetComment(p) => {
+                Some(Action::Targeted(TargetedAction::SetPlaylistInfo {
+                    playlist: p.clone(),
+                    name: None,
+                    comment: Some(text),
+                    public: None,
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Renderable for Prompt {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let vertical = Layout::vertical([Constraint::Length(3)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(&self.input, area);
+    }
+}
+
+impl HandleRaw for Prompt {
+    fn handle_raw(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => Some(Action::Multiple(Self::close())),
+            KeyCode::Enter => {
+                let text = self.input.lines()[0].trim().to_string();
+                let mut actions = Self::close();
+                if let Some(action) = self.submit(text) {
+                    actions.push(action);
+                }
+                Some(Action::Multiple(actions))
+            }
+            _ => {
+                self.input.input(key);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    action::action::{Action, Mode, TargetedAction},
+    components::traits::{handleraw::HandleRaw, renderable::Renderable},
+    osclient::response::{getgenres::Genre, getmusicfolders::MusicFolder},
+    playerworker::player::QueueLocation,
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{randommix::RandomMixParams, ToQueryWorker},
+    },
+};
+
+/// Number of media picked unless the user changes it
+const DEFAULT_COUNT: usize = 50;
+
+#[derive(PartialEq, Clone, Copy)]
+enum Field {
+    Genre,
+    FromYear,
+    ToYear,
+    Folder,
+    Count,
+    Location,
+}
+
+const FIELDS: [Field; 6] = [
+    Field::Genre,
+    Field::FromYear,
+    Field::ToYear,
+    Field::Folder,
+    Field::Count,
+    Field::Location,
+];
+
+/// Asks for the filters of the random media to add to the queue. Genres and music folders are
+/// fetched from the server when the popup is opened, and either can be left as "Any".
+pub struct RandomMix {
+    /// None until the server replies
+    genres: Option<Vec<Genre>>,
+    /// Index of the selected genre, where 0 is any genre
+    genre: usize,
+    /// None until the server replies
+    folders: Option<Vec<MusicFolder>>,
+    /// Index of the selected folder, where 0 is any folder
+    folder: usize,
+    from_year: String,
+    to_year: String,
+    count: String,
+    location: QueueLocation,
+    /// Index of the field being edited
+    selected: usize,
+}
+
+impl RandomMix {
+    pub fn new() -> (Self, Action) {
+        (
+            Self {
+                genres: None,
+                genre: 0,
+                folders: None,
+                folder: 0,
+                from_year: String::new(),
+                to_year: String::new(),
+                count: DEFAULT_COUNT.to_string(),
+                location: QueueLocation::Last,
+                selected: 0,
+            },
+            Action::Multiple(vec![
+                Action::ChangeMode(Mode::Insert),
+                Action::ToQuery(ToQueryWorker::new(HighLevelQuery::ListGenres)),
+                Action::ToQuery(ToQueryWorker::new(HighLevelQuery::ListMusicFolders)),
+            ]),
+        )
+    }
+
+    pub fn update_genres(&mut self, mut genres: Vec<Genre>) {
+        genres.sort_by_key(|g| g.value.to_lowercase());
+        self.genres = Some(genres);
+    }
+
+    pub fn update_folders(&mut self, folders: Vec<MusicFolder>) {
+        self.folders = Some(folders);
+    }
+
+    fn close() -> Vec<Action> {
+        vec![
+            Action::Targeted(TargetedAction::ClosePopup),
+            Action::ChangeMode(Mode::Normal),
+        ]
+    }
+
+    /// Moves to the next or previous option of the selected field. Years and count go up or down
+    /// by one.
+    fn change(&mut self, forward: bool) {
+        /// Index of the next option, where 0 is "Any"
+        fn cycle(current: usize, len: usize, forward: bool) -> usize {
+            let total = len + 1;
+            if forward {
+                (current + 1) % total
+            } else {
+                (current + total - 1) % total
+            }
+        }
+        fn step(value: &mut String, forward: bool) {
+            if let Ok(n) = value.parse::<i32>() {
+                *value = if forward { n + 1 } else { (n - 1).max(0) }.to_string();
+            }
+        }
+        match FIELDS[self.selected] {
+            Field::Genre => {
+                if let Some(genres) = &self.genres {
+                    self.genre = cycle(self.genre, genres.len(), forward);
+                }
+            }
+            Field::Folder => {
+                if let Some(folders) = &self.folders {
+                    self.folder = cycle(self.folder, folders.len(), forward);
+                }
+            }
+            Field::Location => {
+                self.location = match (&self.location, forward) {
+                    (QueueLocation::Front, true) | (QueueLocation::Last, false) => {
+                        QueueLocation::Next
+                    }
+                    (QueueLocation::Next, true) | (QueueLocation::Front, false) => {
+                        QueueLocation::Last
+                    }
+                    (QueueLocation::Last, true) | (QueueLocation::Next, false) => {
+                        QueueLocation::Front
+                    }
+                }
+            }
+            Field::FromYear => step(&mut self.from_year, forward),
+            Field::ToYear => step(&mut self.to_year, forward),
+            Field::Count => step(&mut self.count, forward),
+        }
+    }
+
+    /// Text field of the selected field, if it takes a number
+    fn number_field(&mut self) -> Option<&mut String> {
+        match FIELDS[self.selected] {
+            Field::FromYear => Some(&mut self.from_year),
+            Field::ToYear => Some(&mut self.to_year),
+            Field::Count => Some(&mut self.count),
+            _ => None,
+        }
+    }
+
+    fn params(&self) -> Result<RandomMixParams, String> {
+        fn year(text: &str, name: &str) -> Result<Option<i32>, String> {
+            if text.is_empty() {
+                Ok(None)
+            } else {
+                text.parse()
+                    .map(Some)
+                    .map_err(|_| format!("{} is not a valid year", name))
+            }
+        }
+        let count = match self.count.parse::<usize>() {
+            Ok(c) if c > 0 => c,
+            _ => return Err("Number of media must be at least 1".to_string()),
+        };
+        Ok(RandomMixParams {
+            count,
+            genre: self
+                .genres
+                .as_ref()
+                .and_then(|g| g.get(self.genre.checked_sub(1)?))
+                .map(|g| g.value.clone()),
+            from_year: year(&self.from_year, "Start year")?,
+            to_year: year(&self.to_year, "End year")?,
+            folder: self
+                .folders
+                .as_ref()
+                .and_then(|f| f.get(self.folder.checked_sub(1)?))
+                .map(|f| f.id.clone()),
+            location: self.location.clone(),
+        })
+    }
+
+    fn value(&self, field: Field) -> String {
+        match field {
+            Field::Genre => match &self.genres {
+                None => "Loading...".to_string(),
+                Some(genres) => match self.genre.checked_sub(1).and_then(|i| genres.get(i)) {
+                    Some(g) => match g.song_count {
+                        Some(count) => format!("{} ({})", g.value, count),
+                        None => g.value.clone(),
+                    },
+                    None => "Any".to_string(),
+                },
+            },
+            Field::Folder => match &self.folders {
+                None => "Loading...".to_string(),
+                Some(folders) => match self.folder.checked_sub(1).and_then(|i| folders.get(i)) {
+                    Some(f) => f.name.clone().unwrap_or_else(|| f.id.to_string()),
+                    None => "Any".to_string(),
+                },
+            },
+            Field::FromYear | Field::ToYear if self.number_value(field).is_empty() => {
+                "Any".to_string()
+            }
+            Field::FromYear | Field::ToYear | Field::Count => self.number_value(field).to_string(),
+            Field::Location => self.location.to_string(),
+        }
+    }
+
+    fn number_value(&self, field: Field) -> &str {
+        match field {
+            Field::FromYear => &self.from_year,
+            Field::ToYear => &self.to_year,
+            _ => &self.count,
+        }
+    }
+}
+
+impl Renderable for RandomMix {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let vertical =
+            Layout::vertical([Constraint::Length(FIELDS.len() as u16 + 4)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        let title = Span::styled("Random mix", Style::default().add_modifier(Modifier::BOLD));
+        let block = Block::bordered()
+            .title(title)
+            .border_style(Style::new().white());
+        let mut lines: Vec<Line> = FIELDS
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                let name = match field {
+                    Field::Genre => "Genre",
+                    Field::FromYear => "From year",
+                    Field::ToYear => "To year",
+                    Field::Folder => "Music folder",
+                    Field::Count => "Count",
+                    Field::Location => "Add to",
+                };
+                let line = Line::from(format!("{:<14}< {} >", name, self.value(*field)));
+                if idx == self.selected {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect();
+        lines.push(Line::default());
+        lines.push(
+            Line::from("Up/Down: Select, Left/Right: Change, Enter: Add, Esc: Cancel").dark_gray(),
+        );
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+impl HandleRaw for RandomMix {
+    fn handle_raw(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => return Some(Action::Multiple(Self::close())),
+            KeyCode::Enter => {
+                return Some(match self.params() {
+                    Ok(params) => {
+                        let mut actions = Self::close();
+                        actions.push(Action::ToQuery(ToQueryWorker::new(
+                            HighLevelQuery::RandomMix(params),
+                        )));
+                        Action::Multiple(actions)
+                    }
+                    Err(msg) => Action::Targeted(TargetedAction::Err(msg)),
+                });
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                self.selected = (self.selected + FIELDS.len() - 1) % FIELDS.len();
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.selected = (self.selected + 1) % FIELDS.len();
+            }
+            KeyCode::Left => self.change(false),
+            KeyCode::Right => self.change(true),
+            KeyCode::Backspace => {
+                if let Some(value) = self.number_field() {
+                    value.pop();
+                }
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                if let Some(value) = self.number_field() {
+                    if value.len() < 4 {
+                        value.push(c);
+                    }
+                }
+            }
+            _ => {}
+        };
+        None
+    }
+}
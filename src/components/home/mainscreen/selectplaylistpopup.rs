@@ -9,7 +9,7 @@ use ratatui::{
 
 use crate::{
     action::{
-        action::{Action, TargetedAction},
+        action::{Action, PlaylistEdit, TargetedAction},
         localaction::SelectPlaylistPopupAction,
     },
     components::{
@@ -42,6 +42,9 @@ pub struct SelectPlaylistPopup {
     items: Vec<MediaID>,
     state: Option<State>,
     quick_select: List<'static>,
+    /// The items replace everything in the selected playlist instead of being added to it, and an
+    /// option to create a new playlist is shown at the top
+    replace: bool,
 }
 
 impl SelectPlaylistPopup {
@@ -49,6 +52,7 @@ impl SelectPlaylistPopup {
         items: Vec<MediaID>,
         binds: KeyBindings<SelectPlaylistPopupAction>,
         title: String,
+        replace: bool,
     ) -> (Self, Action) {
         let list: Vec<String> = binds
             .0
@@ -72,6 +76,7 @@ impl SelectPlaylistPopup {
                 },
                 state: None,
                 quick_select: List::new(list),
+                replace,
             },
             Action::ToQuery(ToQueryWorker::new(HighLevelQuery::ListPlaylistsPopup(
                 false,
@@ -80,7 +85,10 @@ impl SelectPlaylistPopup {
     }
 
     pub fn update_playlist(&mut self, p: Vec<SimplePlaylist>) {
-        let list: Vec<String> = p.iter().map(|p| p.name.clone()).collect();
+        let mut list: Vec<String> = p.iter().map(|p| p.name.clone()).collect();
+        if self.replace {
+            list.insert(0, "(New playlist)".to_string());
+        }
         self.state = Some(State {
             playlists: p,
             list: List::new(list)
@@ -99,6 +107,23 @@ impl SelectPlaylistPopup {
         let playlist = state.playlists.get(idx)?;
         Some(playlist.id.clone())
     }
+
+    /// Closes the popup, and asks for the name of the new playlist or a confirmation before
+    /// replacing the content of the selected playlist
+    fn save_as(&self, target: Option<(PlaylistID, String)>) -> Action {
+        let edit = match target {
+            Some((id, name)) => PlaylistEdit::Replace {
+                id,
+                name,
+                items: self.items.clone(),
+            },
+            None => PlaylistEdit::CreateWith(self.items.clone()),
+        };
+        Action::Multiple(vec![
+            Action::Targeted(TargetedAction::ClosePopup),
+            Action::Targeted(TargetedAction::EditPlaylist(edit)),
+        ])
+    }
 }
 
 impl Renderable for SelectPlaylistPopup {
@@ -152,6 +177,21 @@ impl HandleKeySeq<SelectPlaylistPopupAction> for SelectPlaylistPopup {
             SelectPlaylistPopupAction::Cancel => {
                 return KeySeqResult::ActionNeeded(Action::Targeted(TargetedAction::ClosePopup))
             }
+            SelectPlaylistPopupAction::Confirm if self.replace => {
+                let Some(state) = &self.state else {
+                    return KeySeqResult::NoActionNeeded;
+                };
+                // The first item is for creating a new playlist
+                let target = match state.liststate.selected() {
+                    Some(0) => None,
+                    Some(idx) => match state.playlists.get(idx - 1) {
+                        Some(p) => Some((p.id.clone(), p.name.clone())),
+                        None => return KeySeqResult::NoActionNeeded,
+                    },
+                    None => return KeySeqResult::NoActionNeeded,
+                };
+                return KeySeqResult::ActionNeeded(self.save_as(target));
+            }
             SelectPlaylistPopupAction::Confirm => {
                 let Some(id) = self.get_id() else {
                     return KeySeqResult::ActionNeeded(Action::Targeted(
@@ -173,6 +213,9 @@ impl HandleKeySeq<SelectPlaylistPopupAction> for SelectPlaylistPopup {
                     ))),
                 ]));
             }
+            SelectPlaylistPopupAction::SelectID { id, name } if self.replace => {
+                return KeySeqResult::ActionNeeded(self.save_as(Some((id, name))));
+            }
             SelectPlaylistPopupAction::SelectID { id, name: _ } => {
                 return KeySeqResult::ActionNeeded(Action::Multiple(vec![
                     Action::Targeted(TargetedAction::ClosePopup),
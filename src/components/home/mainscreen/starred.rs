@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::Span,
+    widgets::{Block, List, ListState},
+    Frame,
+};
+
+use crate::{
+    action::{
+        action::{Action, QueueAction, TargetedAction},
+        localaction::StarredAction,
+    },
+    compid::CompID,
+    components::{
+        lib::centered::Centered,
+        traits::{
+            focusable::Focusable,
+            handlekeyseq::{HandleKeySeq, KeySeqResult},
+            handlequery::HandleQuery,
+            renderable::Renderable,
+        },
+    },
+    config::{keybindings::KeyBindings, Config},
+    osclient::{response::getstarred2::Starred2, types::MediaID},
+    playerworker::player::QueueLocation,
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{
+            getalbum::{GetAlbumParams, GetAlbumResponse},
+            getartist::GetArtistParams,
+            setrating::SetRatingResponse,
+            QueryStatus, ResponseType, ToQueryWorker,
+        },
+    },
+};
+
+#[derive(PartialEq, Clone, Copy)]
+enum Section {
+    Artists,
+    Albums,
+    Songs,
+}
+
+impl Section {
+    fn next(&self) -> Self {
+        match self {
+            Section::Artists => Section::Albums,
+            Section::Albums => Section::Songs,
+            Section::Songs => Section::Artists,
+        }
+    }
+    fn prev(&self) -> Self {
+        match self {
+            Section::Artists => Section::Songs,
+            Section::Albums => Section::Artists,
+            Section::Songs => Section::Albums,
+        }
+    }
+}
+
+enum State {
+    Loading,
+    Error(String),
+    Loaded(Starred2),
+}
+
+/// Lists the artists, albums and songs starred by the user. Items unstarred anywhere else are
+/// removed immediately, and the list is fetched again whenever something new is starred.
+pub struct Starred {
+    state: State,
+    section: Section,
+    artists: ListState,
+    albums: ListState,
+    songs: ListState,
+    enabled: bool,
+    keymap: KeyBindings<StarredAction>,
+    autofocus: bool,
+    /// Starred items are not fetched until the user opens this view for the first time
+    initialised: bool,
+    /// Where the albums and the artists should be added once their songs are fetched
+    callback: HashMap<usize, QueueLocation>,
+    /// Star requests that have not finished yet, and whether they star or unstar the item
+    pending: HashMap<usize, bool>,
+}
+
+impl Starred {
+    pub fn new(config: Config, enabled: bool) -> Self {
+        Self {
+            state: State::Loading,
+            section: Section::Songs,
+            artists: ListState::default(),
+            albums: ListState::default(),
+            songs: ListState::default(),
+            enabled,
+            keymap: config.local.starred,
+            autofocus: config.behaviour.auto_focus,
+            initialised: false,
+            callback: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Returns the query that fetches the starred items if it has not been sent yet
+    pub fn init(&mut self) -> Option<Action> {
+        if self.initialised {
+            None
+        } else {
+            self.initialised = true;
+            Some(Self::refresh())
+        }
+    }
+
+    /// Fetches the starred items again if they have been fetched before
+    pub fn reload(&self) -> Option<Action> {
+        self.initialised.then(Self::refresh)
+    }
+
+    fn set_rating(&mut self, media: &MediaID, rating: u8) {
+        if let State::Loaded(starred) = &mut self.state {
+            for m in starred.song.iter_mut().filter(|m| m.id == *media) {
+                m.set_rating(rating);
+            }
+        }
+    }
+
+    fn refresh() -> Action {
+        Action::ToQuery(ToQueryWorker::new(HighLevelQuery::ListStarred))
+    }
+
+    /// Keeps the cursor of each list within the list after the items change
+    fn fix_cursors(&mut self) {
+        let State::Loaded(starred) = &self.state else {
+            return;
+        };
+        for (state, len) in [
+            (&mut self.artists, starred.artist.len()),
+            (&mut self.albums, starred.album.len()),
+            (&mut self.songs, starred.song.len()),
+        ] {
+            let selected = match state.selected() {
+                _ if len == 0 => None,
+                Some(idx) => Some(idx.min(len - 1)),
+                None => Some(0),
+            };
+            state.select(selected);
+        }
+    }
+
+    fn gen_list(&self, items: Vec<String>, title: &str, section: Section) -> List<'static> {
+        let focused = self.enabled && self.section == section;
+        let title = Span::styled(
+            format!("{} ({})", title, items.len()),
+            if focused {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            },
+        );
+        List::new(items)
+            .block(Block::bordered().title(title).border_style(if focused {
+                Style::new().white()
+            } else {
+                Style::new().dark_gray()
+            }))
+            .highlight_style(Style::new().reversed())
+            .highlight_symbol(">")
+            .scroll_padding(1)
+    }
+
+    /// Runs the given action against the item under the cursor of the focused section
+    fn on_selected(&mut self, action: StarredAction) -> Option<Action> {
+        let State::Loaded(starred) = &self.state else {
+            return None;
+        };
+        match self.section {
+            Section::Artists => {
+                let artist = starred.artist.get(self.artists.selected()?)?;
+                let params = GetArtistParams {
+                    name: artist.name.clone(),
+                    id: artist.id.clone(),
+                };
+                match action {
+                    StarredAction::Open => Some(Action::Multiple(vec![
+                        Action::Targeted(TargetedAction::FocusArtistList),
+                        Action::ToQuery(ToQueryWorker::new(HighLevelQuery::SelectArtist(params))),
+                    ])),
+                    StarredAction::Add(ql) => {
+                        let req = ToQueryWorker::new(HighLevelQuery::AddArtistToQueue(params));
+                        self.callback.insert(req.ticket, ql);
+                        Some(Action::ToQuery(req))
+                    }
+                    StarredAction::Unstar => Some(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::SetArtistStar {
+                            artist: artist.id.clone(),
+                            star: false,
+                        },
+                    ))),
+                    StarredAction::ViewInfo => Some(Action::Targeted(TargetedAction::Info(
+                        match artist.album_count {
+                            Some(count) => format!("{} ({} albums)", artist.name, count),
+                            None => artist.name.clone(),
+                        },
+                    ))),
+                    _ => None,
+                }
+            }
+            Section::Albums => {
+                let album = starred.album.get(self.albums.selected()?)?;
+                let params = GetAlbumParams {
+                    name: album.name.clone(),
+                    id: album.id.clone(),
+                };
+                match action {
+                    StarredAction::Open => {
+                        let mut actions = vec![Action::ToQuery(ToQueryWorker::new(
+                            HighLevelQuery::SelectAlbum(params),
+                        ))];
+                        if self.autofocus {
+                            actions.push(Action::Targeted(TargetedAction::FocusPlaylistQueue));
+                        }
+                        Some(Action::Multiple(actions))
+                    }
+                    StarredAction::Add(ql) => {
+                        let req = ToQueryWorker::new(HighLevelQuery::AddAlbumToQueue(params));
+                        self.callback.insert(req.ticket, ql);
+                        Some(Action::ToQuery(req))
+                    }
+                    StarredAction::Unstar => Some(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::SetAlbumStar {
+                            album: album.id.clone(),
+                            star: false,
+                        },
+                    ))),
+                    StarredAction::ViewInfo => {
+                        let mut info = format!(
+                            "{} by {}",
+                            album.name,
+                            album.artist.clone().unwrap_or("Unknown".to_string())
+                        );
+                        if let Some(year) = album.year {
+                            info.push_str(&format!(" ({})", year));
+                        }
+                        info.push_str(&format!(
+                            ", {} songs, {:02}:{:02}",
+                            album.song_count,
+                            album.duration / 60,
+                            album.duration % 60
+                        ));
+                        if let Some(genre) = &album.genre {
+                            info.push_str(&format!(", {}", genre));
+                        }
+                        Some(Action::Targeted(TargetedAction::Info(info)))
+                    }
+                    _ => None,
+                }
+            }
+            Section::Songs => {
+                let song = starred.song.get(self.songs.selected()?)?;
+                match action {
+                    StarredAction::Open => Some(Action::Targeted(TargetedAction::Queue(
+                        QueueAction::Add(vec![song.clone()], QueueLocation::Front),
+                    ))),
+                    StarredAction::Add(ql) => Some(Action::Targeted(TargetedAction::Queue(
+                        QueueAction::Add(vec![song.clone()], ql),
+                    ))),
+                    StarredAction::Unstar => Some(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::SetStar {
+                            media: song.id.clone(),
+                            star: false,
+                        },
+                    ))),
+                    StarredAction::ViewInfo => Some(Action::Targeted(
+                        TargetedAction::ViewMediaInfo(song.clone()),
+                    )),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl Renderable for Starred {
+    fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let State::Loaded(starred) = &self.state else {
+            let style = if self.enabled {
+                Style::new().white()
+            } else {
+                Style::new().dark_gray()
+            };
+            let block = Block::bordered().title("Starred").border_style(style);
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+            match &self.state {
+                State::Error(msg) => {
+                    let mut msg = vec!["Error!".to_string(), msg.clone()];
+                    if let Some(keyseq) = self.keymap.find_action_str(StarredAction::Refresh) {
+                        msg.push(format!("Reload with {}", keyseq));
+                    }
+                    Centered::new(msg).draw(frame, inner)
+                }
+                _ => Centered::new(vec!["Loading...".to_string()]).draw(frame, inner),
+            }
+            return;
+        };
+        let [artist_area, album_area, song_area] = Layout::vertical([
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+        ])
+        .areas(area);
+        let artist_list = self.gen_list(
+            starred.artist.iter().map(|a| a.name.clone()).collect(),
+            "Artists",
+            Section::Artists,
+        );
+        let album_list = self.gen_list(
+            starred
+                .album
+                .iter()
+                .map(|a| {
+                    format!(
+                        "{} - {}",
+                        a.name,
+                        a.artist.clone().unwrap_or("Unknown".to_string())
+                    )
+                })
+                .collect(),
+            "Albums",
+            Section::Albums,
+        );
+        let song_list = self.gen_list(
+            starred
+                .song
+                .iter()
+                .map(|m| {
+                    format!(
+                        "{} - {}",
+                        m.title,
+                        m.artist.clone().unwrap_or("Unknown".to_string())
+                    )
+                })
+                .collect(),
+            "Songs",
+            Section::Songs,
+        );
+        frame.render_stateful_widget(artist_list, artist_area, &mut self.artists);
+        frame.render_stateful_widget(album_list, album_area, &mut self.albums);
+        frame.render_stateful_widget(song_list, song_area, &mut self.songs);
+    }
+}
+
+impl HandleQuery for Starred {
+    fn handle_query(&mut self, _dest: CompID, ticket: usize, res: QueryStatus) -> Option<Action> {
+        match res {
+            QueryStatus::Finished(ResponseType::GetStarred(res)) => {
+                self.state = match res {
+                    Ok(starred) => State::Loaded(starred),
+                    Err(msg) => State::Error(msg),
+                };
+                self.fix_cursors();
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SetStar { media, star }) => {
+                self.pending.insert(ticket, star);
+                if let (State::Loaded(starred), false) = (&mut self.state, star) {
+                    starred.song.retain(|m| m.id != media);
+                    self.fix_cursors();
+                }
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SetRating { media, rating, .. }) => {
+                self.set_rating(&media, rating);
+                None
+            }
+            QueryStatus::Finished(ResponseType::SetRating(SetRatingResponse::Failure {
+                media,
+                previous,
+                ..
+            })) => {
+                self.set_rating(&media, previous);
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SetAlbumStar { album, star }) => {
+                self.pending.insert(ticket, star);
+                if let (State::Loaded(starred), false) = (&mut self.state, star) {
+                    starred.album.retain(|a| a.id != album);
+                    self.fix_cursors();
+                }
+                None
+            }
+            QueryStatus::Requested(HighLevelQuery::SetArtistStar { artist, star }) => {
+                self.pending.insert(ticket, star);
+                if let (State::Loaded(starred), false) = (&mut self.state, star) {
+                    starred.artist.retain(|a| a.id != artist);
+                    self.fix_cursors();
+                }
+                None
+            }
+            QueryStatus::Finished(ResponseType::Star(res)) => {
+                let star = self.pending.remove(&ticket)?;
+                // Newly starred items are only known after fetching the list again, and the items
+                // removed ahead of time must be brought back if unstarring them failed
+                if self.initialised && (star || res.is_err()) {
+                    Some(Self::refresh())
+                } else {
+                    None
+                }
+            }
+            QueryStatus::Finished(ResponseType::GetAlbum(res)) => {
+                let ql = self.callback.remove(&ticket)?;
+                Some(Action::Targeted(match res {
+                    GetAlbumResponse::Success(full_album) => {
+                        TargetedAction::Queue(QueueAction::Add(full_album.song, ql))
+                    }
+                    GetAlbumResponse::Failure { id: _, name, msg } => {
+                        TargetedAction::Err(format!("Failed to add {name} to the queue: {msg}"))
+                    }
+                }))
+            }
+            QueryStatus::Finished(ResponseType::GetArtistSongs(res)) => {
+                let ql = self.callback.remove(&ticket)?;
+                Some(Action::Targeted(match res {
+                    Ok(songs) => TargetedAction::Queue(QueueAction::Add(songs, ql)),
+                    Err(msg) => {
+                        TargetedAction::Err(format!("Failed to add the artist to the queue: {msg}"))
+                    }
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl HandleKeySeq<StarredAction> for Starred {
+    fn get_name(&self) -> &str {
+        "Starred"
+    }
+
+    fn handle_local_action(&mut self, action: StarredAction) -> KeySeqResult {
+        match action {
+            StarredAction::Refresh => {
+                self.initialised = true;
+                return KeySeqResult::ActionNeeded(Self::refresh());
+            }
+            StarredAction::Up
+            | StarredAction::Down
+            | StarredAction::Top
+            | StarredAction::Bottom => {
+                let list = match self.section {
+                    Section::Artists => &mut self.artists,
+                    Section::Albums => &mut self.albums,
+                    Section::Songs => &mut self.songs,
+                };
+                match action {
+                    StarredAction::Up => list.select_previous(),
+                    StarredAction::Down => list.select_next(),
+                    StarredAction::Top => list.select_first(),
+                    _ => list.select_last(),
+                }
+            }
+            StarredAction::NextSection => self.section = self.section.next(),
+            StarredAction::PrevSection => self.section = self.section.prev(),
+            _ => {
+                if let Some(a) = self.on_selected(action) {
+                    return KeySeqResult::ActionNeeded(a);
+                }
+            }
+        };
+        KeySeqResult::NoActionNeeded
+    }
+
+    fn get_keybinds(&self) -> &KeyBindings<StarredAction> {
+        &self.keymap
+    }
+}
+
+impl Focusable for Starred {
+    fn set_enabled(&mut self, enable: bool) {
+        self.enabled = enable;
+    }
+}
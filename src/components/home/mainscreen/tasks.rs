@@ -1,11 +1,11 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use ratatui::{
     layout::{Constraint, Flex, Layout},
     prelude::Rect,
     style::{Modifier, Style, Stylize},
     text::Span,
-    widgets::{Block, Clear, Row, Table},
+    widgets::{Block, Clear, Row, Table, TableState},
     Frame,
 };
 
@@ -19,29 +19,40 @@ use crate::{
         renderable::Renderable,
     },
     config::keybindings::KeyBindings,
-    queryworker::query::QueryStatus,
+    queryworker::{
+        highlevelquery::HighLevelQuery,
+        query::{QueryStatus, ToQueryWorker},
+    },
 };
 
 #[derive(Clone)]
 enum Status {
     Running,
+    /// Bytes received so far, and the total number of bytes if known
+    Progress(u64, Option<u64>),
     Failed,
 }
 
 impl ToString for Status {
     fn to_string(&self) -> String {
+        const MIB: f64 = 1024.0 * 1024.0;
         match self {
-            Status::Running => "Running",
-            Status::Failed => "Failed!",
+            Status::Running => "Running".to_string(),
+            Status::Progress(done, Some(total)) => {
+                format!("{:.1}/{:.1} MiB", *done as f64 / MIB, *total as f64 / MIB)
+            }
+            Status::Progress(done, None) => format!("{:.1} MiB", *done as f64 / MIB),
+            Status::Failed => "Failed!".to_string(),
         }
-        .to_string()
     }
 }
 
 pub struct Tasks {
     border: Block<'static>,
     table: Table<'static>,
-    tasks: HashMap<usize, (String, Status)>,
+    state: TableState,
+    /// Description and status of each task, along with whether it can be cancelled
+    tasks: BTreeMap<usize, (String, Status, bool)>,
     show_internal: bool,
     binds: KeyBindings<PopupAction>,
 }
@@ -51,11 +62,9 @@ impl Tasks {
         Self {
             binds,
             border: Self::gen_block(),
-            table: Table::new(
-                [Row::new(vec!["ID", "Task", "Status"])],
-                [Constraint::Max(4), Constraint::Min(1), Constraint::Max(10)],
-            ),
-            tasks: HashMap::new(),
+            table: Self::gen_table(vec![]),
+            state: TableState::new().with_selected(Some(0)),
+            tasks: BTreeMap::new(),
             show_internal,
         }
     }
@@ -66,15 +75,22 @@ impl Tasks {
         Block::bordered().title(title).border_style(style)
     }
 
+    fn gen_table(rows: Vec<Row<'static>>) -> Table<'static> {
+        Table::new(
+            rows,
+            [Constraint::Max(6), Constraint::Min(1), Constraint::Max(20)],
+        )
+        .header(Row::new(vec!["ID", "Task", "Status"]))
+        .row_highlight_style(Style::new().reversed())
+    }
+
     fn gen_rows(&self) -> Vec<Row<'static>> {
-        let mut rows: Vec<Row<'static>> = self
-            .tasks
-            .clone()
-            .into_iter()
-            .map(|(id, (msg, status))| Row::new(vec![id.to_string(), msg, status.to_string()]))
-            .collect();
-        rows.insert(0, Row::new(vec!["ID", "Task", "Status"]));
-        rows
+        self.tasks
+            .iter()
+            .map(|(id, (msg, status, _))| {
+                Row::new(vec![id.to_string(), msg.clone(), status.to_string()])
+            })
+            .collect()
     }
 
     pub fn update_task(&mut self, ticket: &usize, status: &QueryStatus) {
@@ -83,7 +99,7 @@ impl Tasks {
                 self.tasks.remove(ticket);
             }
             QueryStatus::Aborted(cancelled) => {
-                if let Some((_, status)) = self.tasks.get_mut(ticket) {
+                if let Some((_, status, _)) = self.tasks.get_mut(ticket) {
                     if *cancelled {
                         self.tasks.remove(ticket);
                     } else {
@@ -93,14 +109,23 @@ impl Tasks {
             }
             QueryStatus::Requested(q) => {
                 if q.show_task() {
-                    self.tasks.insert(*ticket, (q.to_string(), Status::Running));
+                    self.tasks
+                        .insert(*ticket, (q.to_string(), Status::Running, q.can_cancel()));
+                }
+            }
+            QueryStatus::Progress { done, total } => {
+                if let Some((_, status, _)) = self.tasks.get_mut(ticket) {
+                    *status = Status::Progress(*done, *total);
                 }
             }
         };
-        self.table = Table::new(
-            self.gen_rows(),
-            [Constraint::Max(4), Constraint::Min(1), Constraint::Max(10)],
-        );
+        self.table = Self::gen_table(self.gen_rows());
+    }
+
+    /// Ticket of the selected task, if it can be cancelled
+    fn selected_cancellable(&self) -> Option<usize> {
+        let (ticket, (_, status, cancellable)) = self.tasks.iter().nth(self.state.selected()?)?;
+        (*cancellable && !matches!(status, Status::Failed)).then_some(*ticket)
     }
 
     pub fn get_task_count(&self) -> usize {
@@ -116,7 +141,7 @@ impl Renderable for Tasks {
         let [area] = horizontal.areas(area);
         frame.render_widget(Clear, area);
         frame.render_widget(&self.border, area);
-        frame.render_widget(&self.table, self.border.inner(area));
+        frame.render_stateful_widget(&self.table, self.border.inner(area), &mut self.state);
     }
 }
 
@@ -127,11 +152,22 @@ impl HandleKeySeq<PopupAction> for Tasks {
 
     fn handle_local_action(&mut self, action: PopupAction) -> KeySeqResult {
         match action {
+            PopupAction::Up => self.state.select_previous(),
+            PopupAction::Down => self.state.select_next(),
+            PopupAction::Top => self.state.select_first(),
+            PopupAction::Bottom => self.state.select_last(),
             PopupAction::Close => {
-                KeySeqResult::ActionNeeded(Action::Targeted(TargetedAction::ClosePopup))
+                return KeySeqResult::ActionNeeded(Action::Targeted(TargetedAction::ClosePopup));
             }
-            _ => KeySeqResult::NoActionNeeded,
-        }
+            PopupAction::Cancel => {
+                if let Some(ticket) = self.selected_cancellable() {
+                    return KeySeqResult::ActionNeeded(Action::ToQuery(ToQueryWorker::new(
+                        HighLevelQuery::CancelTask(ticket),
+                    )));
+                }
+            }
+        };
+        KeySeqResult::NoActionNeeded
     }
 
     fn get_keybinds(&self) -> &KeyBindings<PopupAction> {
@@ -248,6 +248,20 @@ impl VisualTable {
         self.table = self.regen_table();
     }
 
+    /// Replace all rows after they have been removed or reordered, placing the cursor at the given
+    /// position. Selections, highlights and filters are cleared as they no longer point at the same
+    /// rows.
+    pub fn reset_rows(&mut self, rows: Vec<Row<'static>>, cursor: Option<usize>) {
+        self.state = ModifiableList::new(vec![RowState::default(); rows.len()]);
+        self.mode = VisualMode::Off;
+        self.tablestate.select(
+            cursor
+                .filter(|_| !rows.is_empty())
+                .map(|c| c.min(rows.len() - 1)),
+        );
+        self.set_rows(rows);
+    }
+
     /// Function that should be called if the cursor position is missing
     pub fn bump_cursor_pos(&mut self) {
         if self.rows.len() != 0 && self.tablestate.selected() == None {
@@ -278,6 +292,21 @@ impl VisualTable {
         self.set_rows(rows);
     }
 
+    /// Removes the rows from the given position onwards, keeping the state of the remaining rows
+    pub fn truncate(&mut self, len: usize) {
+        self.state.0.truncate(len);
+        if let VisualMode::Select(start) | VisualMode::Deselect(start) = self.mode {
+            if start.0 >= len {
+                self.mode = VisualMode::Off;
+            }
+        }
+        if self.get_current().is_some_and(|cur| cur >= len) {
+            self.tablestate.select(len.checked_sub(1));
+        }
+        self.rows.0.truncate(len);
+        self.table = self.regen_table();
+    }
+
     /// Same as [`Self::get_selection`], except the selections are reset.
     pub fn get_selection_reset(&mut self) -> (VisualSelection, Option<Action>) {
         let selection = self.get_selection();
@@ -2,14 +2,24 @@ mod appconfig;
 mod authconfig;
 mod behaviourconfig;
 mod bpmtoyconfig;
+mod cacheconfig;
 mod coverartconfig;
+mod downloadconfig;
 mod featuresconfig;
+mod historyconfig;
 pub mod keybindings;
 pub mod keyparser;
+mod listenbrainzconfig;
 pub mod localkeybinds;
 mod lyricsconfig;
 pub mod pathconfig;
+pub mod playlistfileconfig;
 mod playlistsconfig;
+mod queuesyncconfig;
+pub mod radioconfig;
+pub mod replaygainconfig;
+mod scrobbleconfig;
+pub mod streamingconfig;
 mod styleconfig;
 
 use keybindings::KeyBindings;
@@ -80,8 +90,9 @@ const CONFIG: &str = include_str!("../.config/config.json5");
 impl Config {
     pub fn new(paths: PathConfig) -> Result<Self, config::ConfigError> {
         let default_config: Config = json5::from_str(CONFIG).unwrap();
-        let mut builder =
-            config::Config::builder().set_default("data_dir", paths.data.to_str().unwrap())?;
+        let mut builder = config::Config::builder()
+            .set_default("data_dir", paths.data.to_str().unwrap())?
+            .set_override("persist_data", paths.persist)?;
         if let Some(c) = paths.config {
             builder = builder.set_default("config_dir", c.to_str().unwrap())?;
 
@@ -131,10 +142,15 @@ impl Config {
         insert_keybinds!(playqueue);
         insert_keybinds!(lyrics);
         insert_keybinds!(playlistlist);
+        insert_keybinds!(albumlist);
+        insert_keybinds!(artistlist);
         insert_keybinds!(playlistqueue);
         insert_keybinds!(help);
         insert_keybinds!(popup);
         insert_keybinds!(select_playlist_popup);
+        insert_keybinds!(global_search);
+        insert_keybinds!(history);
+        insert_keybinds!(starred);
 
         for (key, cmd) in default_config.local.playqueue.iter() {
             cfg.local
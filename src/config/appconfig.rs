@@ -10,4 +10,7 @@ pub struct AppConfig {
     pub config_dir: PathBuf,
     #[serde(default)]
     pub use_legacy_auth: bool,
+    /// Set to false by --no-data
+    #[serde(default)]
+    pub persist_data: bool,
 }
@@ -4,6 +4,10 @@ fn default_true() -> bool {
     true
 }
 
+fn default_crossfade_duration() -> f32 {
+    5.0
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct BehaviourConfig {
     /// Automatically changes the currently focused items
@@ -12,6 +16,13 @@ pub struct BehaviourConfig {
     /// Show some internal tasks in the tasks view that may not be very interesting
     #[serde(default)]
     pub show_internal_tasks: bool,
+    /// Fade out the end of the current media while the next one fades in. This is not done for
+    /// media from the same album. Can be toggled at runtime.
+    #[serde(default)]
+    pub crossfade: bool,
+    /// Length of the crossfade in seconds
+    #[serde(default = "default_crossfade_duration")]
+    pub crossfade_duration: f32,
 }
 
 impl Default for BehaviourConfig {
@@ -19,6 +30,8 @@ impl Default for BehaviourConfig {
         Self {
             auto_focus: true,
             show_internal_tasks: false,
+            crossfade: false,
+            crossfade_duration: 5.0,
         }
     }
 }
@@ -0,0 +1,35 @@
+use serde::Deserialize;
+
+fn default_enable() -> bool {
+    true
+}
+
+fn default_max_size() -> u64 {
+    2048
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CacheConfig {
+    /// Keep the played media in the data directory so that it does not have to be downloaded
+    /// again
+    #[serde(default = "default_enable")]
+    pub enable: bool,
+    /// Maximum size of the cache in MiB. Least recently played media is removed first once this is
+    /// exceeded.
+    #[serde(default = "default_max_size")]
+    pub max_size: u64,
+    /// Start in offline mode, where only the cached media is played and shown. The server is not
+    /// contacted at startup, but the credentials in the config are still needed.
+    #[serde(default)]
+    pub offline: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_enable(),
+            max_size: default_max_size(),
+            offline: false,
+        }
+    }
+}
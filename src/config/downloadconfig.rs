@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use directories::UserDirs;
+use serde::Deserialize;
+
+fn default_dir() -> PathBuf {
+    UserDirs::new()
+        .and_then(|d| d.audio_dir().map(|p| p.join("Ampterm")))
+        .unwrap_or_else(|| PathBuf::from(".").join("Downloads"))
+}
+
+fn default_template() -> String {
+    "{artist}/{album}/{track} - {title}.{suffix}".to_string()
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DownloadConfig {
+    /// Directory the downloaded media is saved in
+    #[serde(default = "default_dir")]
+    pub dir: PathBuf,
+    /// Path of each downloaded file relative to the directory above. {artist}, {album}, {track},
+    /// {title} and {suffix} are replaced with the information of the media.
+    #[serde(default = "default_template")]
+    pub template: String,
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        Self {
+            dir: default_dir(),
+            template: default_template(),
+        }
+    }
+}
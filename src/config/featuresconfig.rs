@@ -1,7 +1,12 @@
 use serde::Deserialize;
 
 use crate::config::{
-    bpmtoyconfig::BPMToyConfig, coverartconfig::CoverArtConfig, lyricsconfig::LyricsConfig,
+    bpmtoyconfig::BPMToyConfig, cacheconfig::CacheConfig, coverartconfig::CoverArtConfig,
+    downloadconfig::DownloadConfig, historyconfig::HistoryConfig,
+    listenbrainzconfig::ListenBrainzConfig, lyricsconfig::LyricsConfig,
+    playlistfileconfig::PlaylistFileConfig, queuesyncconfig::QueueSyncConfig,
+    radioconfig::RadioConfig, replaygainconfig::ReplayGainConfig, scrobbleconfig::ScrobbleConfig,
+    streamingconfig::StreamingConfig,
 };
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -12,4 +17,24 @@ pub struct FeaturesConfig {
     pub bpmtoy: BPMToyConfig,
     #[serde(default)]
     pub cover_art: CoverArtConfig,
+    #[serde(default)]
+    pub queue_sync: QueueSyncConfig,
+    #[serde(default)]
+    pub scrobble: ScrobbleConfig,
+    #[serde(default)]
+    pub listenbrainz: ListenBrainzConfig,
+    #[serde(default)]
+    pub replay_gain: ReplayGainConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub download: DownloadConfig,
+    #[serde(default)]
+    pub streaming: StreamingConfig,
+    #[serde(default)]
+    pub playlist_file: PlaylistFileConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub radio: RadioConfig,
 }
@@ -0,0 +1,28 @@
+use serde::Deserialize;
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_limit() -> usize {
+    1000
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HistoryConfig {
+    /// Save every media that has been played into the database
+    #[serde(default = "default_true")]
+    pub enable: bool,
+    /// Number of the most recent plays shown in the history list
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            limit: 1000,
+        }
+    }
+}
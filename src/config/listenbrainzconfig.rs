@@ -0,0 +1,27 @@
+use serde::Deserialize;
+
+fn default_url() -> String {
+    "https://api.listenbrainz.org".to_string()
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ListenBrainzConfig {
+    /// Submit listens to ListenBrainz, or a server compatible with it
+    #[serde(default)]
+    pub enable: bool,
+    #[serde(default = "default_url")]
+    pub url: String,
+    /// User token, which can be found in the ListenBrainz settings page
+    #[serde(default)]
+    pub token: String,
+}
+
+impl Default for ListenBrainzConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            url: default_url(),
+            token: String::new(),
+        }
+    }
+}
@@ -2,8 +2,9 @@ use serde::Deserialize;
 
 use crate::{
     action::localaction::{
-        HelpAction, ListAction, LyricsAction, PlayQueueAction, PlaylistListAction,
-        PlaylistQueueAction, PopupAction, SelectPlaylistPopupAction,
+        AlbumListAction, ArtistListAction, GlobalSearchAction, HelpAction, HistoryAction,
+        ListAction, LyricsAction, PlayQueueAction, PlaylistListAction, PlaylistQueueAction,
+        PopupAction, SelectPlaylistPopupAction, StarredAction,
     },
     config::keybindings::KeyBindings,
 };
@@ -29,12 +30,27 @@ pub struct LocalKeyBinds {
     #[serde(default)]
     pub playlistlist: KeyBindings<PlaylistListAction>,
 
+    #[serde(default)]
+    pub albumlist: KeyBindings<AlbumListAction>,
+
+    #[serde(default)]
+    pub artistlist: KeyBindings<ArtistListAction>,
+
     #[serde(default)]
     pub playlistqueue: KeyBindings<PlaylistQueueAction>,
 
+    #[serde(default)]
+    pub history: KeyBindings<HistoryAction>,
+
+    #[serde(default)]
+    pub starred: KeyBindings<StarredAction>,
+
     #[serde(default)]
     pub popup: KeyBindings<PopupAction>,
 
+    #[serde(default)]
+    pub global_search: KeyBindings<GlobalSearchAction>,
+
     #[serde(default)]
     pub select_playlist_popup: KeyBindings<SelectPlaylistPopupAction>,
 }
@@ -17,6 +17,8 @@ lazy_static! {
 
 pub struct PathConfig {
     pub data: PathBuf,
+    /// False if the data directory is a throwaway temporary directory
+    pub persist: bool,
     pub config: Option<PathBuf>,
 }
 
@@ -55,6 +57,7 @@ impl PathConfig {
         ProjectDirs::from("ch", "skew", env!("CARGO_PKG_NAME"))
     }
     pub fn new(data_str: PathType, config_str: PathType) -> Self {
+        let persist = !matches!(data_str, PathType::None);
         let data = match data_str {
             PathType::Custom(p) => PathBuf::from(p),
             PathType::Default => Self::get_data_dir(),
@@ -67,7 +70,11 @@ impl PathConfig {
             PathType::Default => Some(Self::get_config_dir()),
             PathType::None => None,
         };
-        Self { data, config }
+        Self {
+            data,
+            persist,
+            config,
+        }
     }
 }
 
@@ -75,6 +82,7 @@ impl Default for PathConfig {
     fn default() -> Self {
         Self {
             data: Self::get_data_dir(),
+            persist: true,
             config: None,
         }
     }
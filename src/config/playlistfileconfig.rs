@@ -0,0 +1,67 @@
+use std::{fmt::Display, path::Path, path::PathBuf};
+
+use directories::UserDirs;
+use serde::Deserialize;
+
+/// File formats playlists can be exported to and imported from
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaylistFormat {
+    /// Extended M3U, saved with UTF-8 encoding
+    #[default]
+    M3u,
+    Xspf,
+}
+
+impl PlaylistFormat {
+    /// Guesses the format from the extension of the file
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "m3u" | "m3u8" => Some(Self::M3u),
+            "xspf" => Some(Self::Xspf),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::M3u => "m3u8",
+            Self::Xspf => "xspf",
+        }
+    }
+}
+
+impl Display for PlaylistFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::M3u => write!(f, "M3U"),
+            Self::Xspf => write!(f, "XSPF"),
+        }
+    }
+}
+
+fn default_dir() -> PathBuf {
+    UserDirs::new()
+        .and_then(|d| d.audio_dir().map(|p| p.join("Ampterm").join("Playlists")))
+        .unwrap_or_else(|| PathBuf::from(".").join("Playlists"))
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PlaylistFileConfig {
+    /// Directory exported playlists are saved in
+    #[serde(default = "default_dir")]
+    pub dir: PathBuf,
+    /// Format playlists are exported in
+    #[serde(default)]
+    pub format: PlaylistFormat,
+}
+
+impl Default for PlaylistFileConfig {
+    fn default() -> Self {
+        Self {
+            dir: default_dir(),
+            format: PlaylistFormat::default(),
+        }
+    }
+}
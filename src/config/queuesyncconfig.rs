@@ -0,0 +1,28 @@
+use serde::Deserialize;
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_interval() -> u64 {
+    30
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct QueueSyncConfig {
+    /// Upload the queue to the server periodically and on quit
+    #[serde(default = "default_true")]
+    pub enable: bool,
+    /// Seconds between each upload. The queue is only uploaded if it has changed.
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+}
+
+impl Default for QueueSyncConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            interval: 30,
+        }
+    }
+}
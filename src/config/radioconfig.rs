@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Where the media added by radio mode come from
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RadioSource {
+    /// Media similar to the artists of the last few played media
+    #[default]
+    Similar,
+    /// Most popular media of the artist being played
+    TopSongs,
+    /// Random media, optionally limited by the genre and the years in the config
+    Random,
+}
+
+impl RadioSource {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Similar => Self::TopSongs,
+            Self::TopSongs => Self::Random,
+            Self::Random => Self::Similar,
+        }
+    }
+}
+
+impl Display for RadioSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Similar => write!(f, "Similar"),
+            Self::TopSongs => write!(f, "Top songs"),
+            Self::Random => write!(f, "Random"),
+        }
+    }
+}
+
+fn default_seeds() -> usize {
+    3
+}
+
+fn default_count() -> usize {
+    10
+}
+
+fn default_threshold() -> usize {
+    2
+}
+
+fn default_dedup() -> usize {
+    100
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RadioConfig {
+    /// Add more media to the queue automatically before it runs out
+    #[serde(default)]
+    pub enable: bool,
+    #[serde(default)]
+    pub source: RadioSource,
+    /// Number of the last played media whose artists are used to find similar media
+    #[serde(default = "default_seeds")]
+    pub seeds: usize,
+    /// Number of media added at once
+    #[serde(default = "default_count")]
+    pub count: usize,
+    /// More media is added once the number of media left in the queue is this or less
+    #[serde(default = "default_threshold")]
+    pub threshold: usize,
+    /// Media that is one of this many most recently played ones is not added again
+    #[serde(default = "default_dedup")]
+    pub dedup: usize,
+    /// Only used by [`RadioSource::Random`]
+    pub genre: Option<String>,
+    /// Only used by [`RadioSource::Random`]
+    pub from_year: Option<i32>,
+    /// Only used by [`RadioSource::Random`]
+    pub to_year: Option<i32>,
+}
+
+impl Default for RadioConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            source: RadioSource::default(),
+            seeds: 3,
+            count: 10,
+            threshold: 2,
+            dedup: 100,
+            genre: None,
+            from_year: None,
+            to_year: None,
+        }
+    }
+}
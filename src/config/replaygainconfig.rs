@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+pub enum ReplayGainMode {
+    /// Normalise each media on its own
+    #[default]
+    Track,
+    /// Keep the relative loudness of the media within the same album
+    Album,
+    Off,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ReplayGainConfig {
+    #[serde(default)]
+    pub mode: ReplayGainMode,
+    /// Gain in dB added to every media with the ReplayGain information
+    #[serde(default)]
+    pub preamp: f32,
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Deserializer};
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_threshold() -> f32 {
+    0.5
+}
+
+/// Keeps the threshold between 0 and 1, as anything outside cannot be reached or is meaningless
+fn clamp_threshold<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    let threshold = f32::deserialize(deserializer)?;
+    Ok(if threshold.is_nan() {
+        default_threshold()
+    } else {
+        threshold.clamp(0.0, 1.0)
+    })
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScrobbleConfig {
+    /// Report played media to the Subsonic server
+    #[serde(default = "default_true")]
+    pub enable: bool,
+    /// Fraction of the media that needs to be played before it is submitted as played
+    #[serde(default = "default_threshold", deserialize_with = "clamp_threshold")]
+    pub threshold: f32,
+}
+
+impl Default for ScrobbleConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            threshold: default_threshold(),
+        }
+    }
+}
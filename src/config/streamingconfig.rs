@@ -0,0 +1,86 @@
+use std::fmt::Display;
+
+use serde::Deserialize;
+
+/// Transcoding parameters sent along with the stream request
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct StreamProfile {
+    pub name: String,
+    /// Format the server should transcode the media into, such as `mp3` or `opus`. `raw` disables
+    /// transcoding, and leaving it empty lets the server decide.
+    #[serde(default)]
+    pub format: Option<String>,
+    /// Maximum bit rate in kbps. Leaving it empty lets the server decide.
+    #[serde(default)]
+    pub max_bit_rate: Option<u32>,
+}
+
+impl Display for StreamProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        match (&self.format, self.max_bit_rate) {
+            (Some(format), Some(rate)) => write!(f, " ({format}, {rate} kbps)"),
+            (Some(format), None) => write!(f, " ({format})"),
+            (None, Some(rate)) => write!(f, " ({rate} kbps)"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+fn default_profile() -> String {
+    "server".to_string()
+}
+
+fn default_profiles() -> Vec<StreamProfile> {
+    vec![
+        StreamProfile {
+            name: "server".to_string(),
+            format: None,
+            max_bit_rate: None,
+        },
+        StreamProfile {
+            name: "wifi".to_string(),
+            format: None,
+            max_bit_rate: Some(320),
+        },
+        StreamProfile {
+            name: "mobile".to_string(),
+            format: Some("mp3".to_string()),
+            max_bit_rate: Some(128),
+        },
+        StreamProfile {
+            name: "lossless".to_string(),
+            format: Some("raw".to_string()),
+            max_bit_rate: None,
+        },
+    ]
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct StreamingConfig {
+    /// Name of the profile used on startup
+    #[serde(default = "default_profile")]
+    pub profile: String,
+    /// Profiles that can be switched between with `CycleStreamProfile`, in that order
+    #[serde(default = "default_profiles")]
+    pub profiles: Vec<StreamProfile>,
+}
+
+impl StreamingConfig {
+    /// Index of the profile used on startup, which is the first one if the name does not match
+    pub fn initial(&self) -> usize {
+        self.profiles
+            .iter()
+            .position(|p| p.name == self.profile)
+            .unwrap_or(0)
+    }
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            profile: default_profile(),
+            profiles: default_profiles(),
+        }
+    }
+}
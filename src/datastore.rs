@@ -0,0 +1,464 @@
+use std::{fs, path::Path, time::Duration};
+
+use color_eyre::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    osclient::{response::getplaylist::Media, types::MediaID},
+    scrobbler::listen::Listen,
+};
+
+const DB_FILE: &str = "ampterm.db";
+
+/// Bump this and add a new step to [`DataStore::migrate`] whenever the schema changes
+const SCHEMA_VERSION: i32 = 4;
+
+/// Position of the play cursor within the saved queue
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueueCursor {
+    BeforeFirst,
+    AfterLast,
+    NotInQueue(usize),
+    InQueue(usize),
+}
+
+impl QueueCursor {
+    fn to_row(self) -> (&'static str, Option<i64>) {
+        match self {
+            QueueCursor::BeforeFirst => ("BeforeFirst", None),
+            QueueCursor::AfterLast => ("AfterLast", None),
+            QueueCursor::NotInQueue(idx) => ("NotInQueue", Some(idx as i64)),
+            QueueCursor::InQueue(idx) => ("InQueue", Some(idx as i64)),
+        }
+    }
+
+    fn from_row(kind: &str, idx: Option<i64>) -> Self {
+        match (kind, idx) {
+            ("AfterLast", _) => QueueCursor::AfterLast,
+            ("NotInQueue", Some(idx)) => QueueCursor::NotInQueue(idx as usize),
+            ("InQueue", Some(idx)) => QueueCursor::InQueue(idx as usize),
+            _ => QueueCursor::BeforeFirst,
+        }
+    }
+}
+
+/// Everything needed to bring the play queue back to where the user left off
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueState {
+    pub items: Vec<Media>,
+    pub cursor: QueueCursor,
+    pub position: Duration,
+    pub volume: f32,
+    pub speed: f32,
+}
+
+/// Media that has been played, and when
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayRecord {
+    /// Seconds since the UNIX epoch at the time the media started playing
+    pub played_at: i64,
+    pub media: Media,
+    /// How far into the media the user listened before it stopped or was replaced
+    pub played: Duration,
+}
+
+/// Local SQLite database stored in the data directory
+pub struct DataStore {
+    conn: Connection,
+}
+
+impl DataStore {
+    pub fn open(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        let mut store = Self {
+            conn: Connection::open(dir.join(DB_FILE))?,
+        };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    /// Database that is discarded once the program exits. Holds the ListenBrainz backlog if data
+    /// is not persisted or the database in the data directory cannot be opened.
+    pub fn open_in_memory() -> Result<Self> {
+        let mut store = Self {
+            conn: Connection::open_in_memory()?,
+        };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    fn migrate(&mut self) -> Result<()> {
+        let version: i32 = self
+            .conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version >= SCHEMA_VERSION {
+            return Ok(());
+        }
+        let tx = self.conn.transaction()?;
+        if version < 1 {
+            tx.execute_batch(
+                "CREATE TABLE queue (
+                    idx INTEGER PRIMARY KEY,
+                    media TEXT NOT NULL
+                );
+                CREATE TABLE player_state (
+                    id INTEGER PRIMARY KEY CHECK (id = 0),
+                    cursor TEXT NOT NULL,
+                    cursor_idx INTEGER,
+                    position_ms INTEGER NOT NULL,
+                    volume REAL NOT NULL,
+                    speed REAL NOT NULL
+                );",
+            )?;
+        }
+        if version < 2 {
+            tx.execute_batch(
+                "CREATE TABLE listen_backlog (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    listen TEXT NOT NULL
+                );",
+            )?;
+        }
+        if version < 3 {
+            tx.execute_batch(
+                "CREATE TABLE play_history (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    played_at INTEGER NOT NULL,
+                    media TEXT NOT NULL,
+                    played_ms INTEGER NOT NULL
+                );",
+            )?;
+        }
+        if version < 4 {
+            tx.execute_batch(
+                "CREATE TABLE scrobble_backlog (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    media TEXT NOT NULL,
+                    time INTEGER NOT NULL
+                );",
+            )?;
+        }
+        tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replaces the saved queue with the given state
+    pub fn save_queue(&mut self, state: &QueueState) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM queue", [])?;
+        {
+            let mut stmt = tx.prepare("INSERT INTO queue (idx, media) VALUES (?1, ?2)")?;
+            for (idx, media) in state.items.iter().enumerate() {
+                stmt.execute(params![idx as i64, serde_json::to_string(media)?])?;
+            }
+        }
+        let (cursor, cursor_idx) = state.cursor.to_row();
+        tx.execute(
+            "INSERT OR REPLACE INTO player_state (id, cursor, cursor_idx, position_ms, volume, speed)
+            VALUES (0, ?1, ?2, ?3, ?4, ?5)",
+            params![
+                cursor,
+                cursor_idx,
+                state.position.as_millis() as i64,
+                state.volume,
+                state.speed
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Returns the queue saved in the last session, if any
+    pub fn load_queue(&self) -> Result<Option<QueueState>> {
+        let Some((cursor, cursor_idx, position_ms, volume, speed)) = self
+            .conn
+            .query_row(
+                "SELECT cursor, cursor_idx, position_ms, volume, speed FROM player_state WHERE id = 0",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<i64>>(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, f32>(3)?,
+                        row.get::<_, f32>(4)?,
+                    ))
+                },
+            )
+            .optional()?
+        else {
+            return Ok(None);
+        };
+        let mut stmt = self.conn.prepare("SELECT media FROM queue ORDER BY idx")?;
+        let items = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .map(|json| Ok(serde_json::from_str::<Media>(&json?)?))
+            .collect::<Result<Vec<Media>>>()?;
+        let cursor = match QueueCursor::from_row(&cursor, cursor_idx) {
+            // The saved index may be stale if the queue was modified without updating the state
+            QueueCursor::InQueue(idx) | QueueCursor::NotInQueue(idx) if idx >= items.len() => {
+                QueueCursor::BeforeFirst
+            }
+            c => c,
+        };
+        Ok(Some(QueueState {
+            items,
+            cursor,
+            position: Duration::from_millis(position_ms.max(0) as u64),
+            volume,
+            speed,
+        }))
+    }
+
+    /// Save a listen that could not be submitted to the external scrobbler
+    pub fn push_listen(&self, listen: &Listen) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO listen_backlog (listen) VALUES (?1)",
+            params![serde_json::to_string(listen)?],
+        )?;
+        Ok(())
+    }
+
+    /// Returns all listens that have not been submitted yet, oldest first
+    pub fn get_listens(&self) -> Result<Vec<(i64, Listen)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, listen FROM listen_backlog ORDER BY id")?;
+        let listens = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .map(|row| {
+                let (id, json) = row?;
+                Ok((id, serde_json::from_str::<Listen>(&json)?))
+            })
+            .collect::<Result<Vec<(i64, Listen)>>>()?;
+        Ok(listens)
+    }
+
+    pub fn remove_listen(&self, id: i64) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM listen_backlog WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Save a submission that has not reached the Subsonic server yet. The time is in
+    /// milliseconds since the UNIX epoch.
+    pub fn push_scrobble(&self, media: &MediaID, time: u64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO scrobble_backlog (media, time) VALUES (?1, ?2)",
+            params![media.0, time as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Returns all submissions that have not reached the Subsonic server yet, oldest first
+    pub fn get_scrobbles(&self) -> Result<Vec<(i64, MediaID, u64)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, media, time FROM scrobble_backlog ORDER BY id")?;
+        let scrobbles = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    MediaID(row.get::<_, String>(1)?),
+                    row.get::<_, i64>(2)?.max(0) as u64,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(scrobbles)
+    }
+
+    pub fn remove_scrobbles(&mut self, ids: &[i64]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare("DELETE FROM scrobble_backlog WHERE id = ?1")?;
+            for id in ids {
+                stmt.execute(params![id])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Add a media to the play history, and remove the oldest ones so that at most `limit` of them
+    /// are kept
+    pub fn push_play(&self, record: &PlayRecord, limit: usize) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO play_history (played_at, media, played_ms) VALUES (?1, ?2, ?3)",
+            params![
+                record.played_at,
+                serde_json::to_string(&record.media)?,
+                record.played.as_millis() as i64
+            ],
+        )?;
+        self.conn.execute(
+            "DELETE FROM play_history WHERE id NOT IN
+                (SELECT id FROM play_history ORDER BY id DESC LIMIT ?1)",
+            params![limit as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` of the most recently played media, newest first. Rows that cannot be
+    /// read are skipped.
+    pub fn get_plays(&self, limit: usize) -> Result<Vec<PlayRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT played_at, media, played_ms FROM play_history ORDER BY id DESC LIMIT ?1",
+        )?;
+        let plays = stmt
+            .query_map(params![limit as i64], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })?
+            .map(|row| -> Result<PlayRecord> {
+                let (played_at, json, played_ms) = row?;
+                Ok(PlayRecord {
+                    played_at,
+                    media: serde_json::from_str::<Media>(&json)?,
+                    played: Duration::from_millis(played_ms.max(0) as u64),
+                })
+            })
+            .filter_map(|record| {
+                record
+                    .inspect_err(|e| tracing::warn!("Skipping a broken play history entry: {e}"))
+                    .ok()
+            })
+            .collect();
+        Ok(plays)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use stream_download::storage::temp::tempfile::TempDir;
+
+    use crate::osclient::types::MediaID;
+
+    use super::*;
+
+    fn media(id: &str) -> Media {
+        serde_json::from_str(&format!(
+            r#"{{"id": "{id}", "isDir": false, "title": "Title {id}", "duration": 120}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_queue_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let mut store = DataStore::open(dir.path()).unwrap();
+        assert_eq!(store.load_queue().unwrap(), None);
+
+        let state = QueueState {
+            items: vec![media("1"), media("2"), media("3")],
+            cursor: QueueCursor::InQueue(1),
+            position: Duration::from_millis(42_500),
+            volume: 0.25,
+            speed: 1.5,
+        };
+        store.save_queue(&state).unwrap();
+        drop(store);
+
+        let store = DataStore::open(dir.path()).unwrap();
+        let loaded = store.load_queue().unwrap().unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.items[2].id, MediaID("3".to_string()));
+    }
+
+    #[test]
+    fn test_listen_backlog() {
+        let store = DataStore::open_in_memory().unwrap();
+        let first = Listen::new(&media("1"), Some(100));
+        let second = Listen::new(&media("2"), Some(200));
+        store.push_listen(&first).unwrap();
+        store.push_listen(&second).unwrap();
+        let listens = store.get_listens().unwrap();
+        assert_eq!(
+            listens.iter().map(|(_, l)| l.clone()).collect::<Vec<_>>(),
+            vec![first, second.clone()]
+        );
+        store.remove_listen(listens[0].0).unwrap();
+        let listens = store.get_listens().unwrap();
+        assert_eq!(listens.len(), 1);
+        assert_eq!(listens[0].1, second);
+    }
+
+    #[test]
+    fn test_scrobble_backlog() {
+        let mut store = DataStore::open_in_memory().unwrap();
+        for (id, time) in [("1", 100), ("2", 200), ("3", 300)] {
+            store.push_scrobble(&MediaID(id.to_string()), time).unwrap();
+        }
+        let scrobbles = store.get_scrobbles().unwrap();
+        assert_eq!(
+            scrobbles
+                .iter()
+                .map(|(_, id, time)| (id.0.as_str(), *time))
+                .collect::<Vec<_>>(),
+            vec![("1", 100), ("2", 200), ("3", 300)]
+        );
+        store
+            .remove_scrobbles(&[scrobbles[0].0, scrobbles[2].0])
+            .unwrap();
+        let scrobbles = store.get_scrobbles().unwrap();
+        assert_eq!(scrobbles.len(), 1);
+        assert_eq!(scrobbles[0].1, MediaID("2".to_string()));
+    }
+
+    #[test]
+    fn test_play_history() {
+        let store = DataStore::open_in_memory().unwrap();
+        let plays: Vec<PlayRecord> = (1..=3)
+            .map(|i| PlayRecord {
+                played_at: 1_700_000_000 + i,
+                media: media(&i.to_string()),
+                played: Duration::from_millis(i as u64 * 1000),
+            })
+            .collect();
+        for play in &plays {
+            store.push_play(play, 10).unwrap();
+        }
+        assert_eq!(
+            store.get_plays(2).unwrap(),
+            vec![plays[2].clone(), plays[1].clone()]
+        );
+        assert_eq!(store.get_plays(10).unwrap().len(), 3);
+        // Broken rows are skipped rather than failing the whole history
+        store
+            .conn
+            .execute(
+                "INSERT INTO play_history (played_at, media, played_ms) VALUES (0, 'broken', 0)",
+                [],
+            )
+            .unwrap();
+        assert_eq!(store.get_plays(10).unwrap().len(), 3);
+        // Only the most recent plays are kept
+        store.push_play(&plays[0], 2).unwrap();
+        assert_eq!(store.get_plays(10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_queue_overwrite() {
+        let dir = TempDir::new().unwrap();
+        let mut store = DataStore::open(dir.path()).unwrap();
+        let mut state = QueueState {
+            items: vec![media("1"), media("2")],
+            cursor: QueueCursor::AfterLast,
+            position: Duration::default(),
+            volume: 0.5,
+            speed: 1.0,
+        };
+        store.save_queue(&state).unwrap();
+        state.items = vec![media("3")];
+        state.cursor = QueueCursor::NotInQueue(0);
+        store.save_queue(&state).unwrap();
+        assert_eq!(store.load_queue().unwrap().unwrap(), state);
+    }
+}
@@ -0,0 +1,70 @@
+/// Number of changes that are remembered before the oldest one is forgotten
+const LIMIT: usize = 100;
+
+/// List of changes that can be undone and redone. Each change is saved as the state before and
+/// after the change, so that undoing returns the former and redoing returns the latter.
+pub struct History<T> {
+    undo: Vec<(T, T)>,
+    redo: Vec<(T, T)>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            undo: vec![],
+            redo: vec![],
+        }
+    }
+}
+
+impl<T> History<T> {
+    /// Save a new change. Changes that have been undone can no longer be redone after this.
+    pub fn record(&mut self, before: T, after: T) {
+        self.redo.clear();
+        if self.undo.len() == LIMIT {
+            self.undo.remove(0);
+        }
+        self.undo.push((before, after));
+    }
+
+    /// Returns the state before the last change
+    pub fn undo(&mut self) -> Option<&T> {
+        let change = self.undo.pop()?;
+        self.redo.push(change);
+        self.redo.last().map(|(before, _)| before)
+    }
+
+    /// Returns the state after the last change that has been undone
+    pub fn redo(&mut self) -> Option<&T> {
+        let change = self.redo.pop()?;
+        self.undo.push(change);
+        self.undo.last().map(|(_, after)| after)
+    }
+
+    /// Forget all changes, such as when the states saved no longer apply
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history() {
+        let mut history = History::default();
+        history.record(0, 1);
+        history.record(1, 2);
+        assert_eq!(history.undo(), Some(&1));
+        assert_eq!(history.undo(), Some(&0));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some(&1));
+        history.record(1, 3);
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.undo(), Some(&1));
+        assert_eq!(history.undo(), Some(&0));
+        assert_eq!(history.undo(), None);
+    }
+}
@@ -109,4 +109,105 @@ impl<T: Clone> ModifiableList<T> {
             }
         };
     }
+
+    /// Move the selected items up or down by one. Selected items that are already at the edge of
+    /// the list stay where they are, and so do the ones right next to them.
+    ///
+    /// Returns the index of the first item whose position has changed, or None if nothing moved.
+    pub fn move_selected(&mut self, selection: &Selection, up: bool) -> Option<usize> {
+        let mut map = match selection {
+            Selection::Single(index) => {
+                let mut map = vec![false; self.len()];
+                map[*index] = true;
+                map
+            }
+            Selection::Multiple(map) => map.clone(),
+        };
+        let mut first = None;
+        let len = self.len();
+        if up {
+            for i in 1..len {
+                if map[i] && !map[i - 1] {
+                    self.swap(i - 1, i);
+                    map.swap(i - 1, i);
+                    first = first.or(Some(i - 1));
+                }
+            }
+        } else {
+            for i in (0..len.saturating_sub(1)).rev() {
+                if map[i] && !map[i + 1] {
+                    self.swap(i, i + 1);
+                    map.swap(i, i + 1);
+                    first = Some(i);
+                }
+            }
+        }
+        first
+    }
+
+    /// Move the selected items so that they come right before the item at the given index, keeping
+    /// their order. The index is counted before the items are moved, and may be equal to the length
+    /// of the list to move the items to the end.
+    pub fn move_selected_to(&mut self, selection: &Selection, at: usize) {
+        let map = match selection {
+            Selection::Single(index) => {
+                let mut map = vec![false; self.len()];
+                map[*index] = true;
+                map
+            }
+            Selection::Multiple(map) => map.clone(),
+        };
+        let (mut moved, mut rest) = (vec![], vec![]);
+        let mut insert_at = 0;
+        for (i, (item, selected)) in self.0.drain(..).zip(map).enumerate() {
+            if selected {
+                moved.push(item);
+            } else {
+                if i < at {
+                    insert_at += 1;
+                }
+                rest.push(item);
+            }
+        }
+        rest.splice(insert_at..insert_at, moved);
+        self.0 = rest;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_selected() {
+        let mut list = ModifiableList::new(vec![0, 1, 2, 3, 4]);
+        let selection = Selection::Multiple(vec![true, false, true, true, false]);
+        assert_eq!(list.move_selected(&selection, true), Some(1));
+        assert_eq!(list.0, vec![0, 2, 3, 1, 4]);
+
+        let mut list = ModifiableList::new(vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.move_selected(&selection, false), Some(0));
+        assert_eq!(list.0, vec![1, 0, 4, 2, 3]);
+
+        let mut list = ModifiableList::new(vec![0, 1, 2]);
+        assert_eq!(list.move_selected(&Selection::Single(2), false), None);
+        assert_eq!(list.move_selected(&Selection::Single(2), true), Some(1));
+        assert_eq!(list.0, vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_move_selected_to() {
+        let selection = Selection::Multiple(vec![true, false, true, false, false]);
+        let mut list = ModifiableList::new(vec![0, 1, 2, 3, 4]);
+        list.move_selected_to(&selection, 4);
+        assert_eq!(list.0, vec![1, 3, 0, 2, 4]);
+
+        let mut list = ModifiableList::new(vec![0, 1, 2, 3, 4]);
+        list.move_selected_to(&selection, 5);
+        assert_eq!(list.0, vec![1, 3, 4, 0, 2]);
+
+        let mut list = ModifiableList::new(vec![0, 1, 2, 3, 4]);
+        list.move_selected_to(&Selection::Single(3), 0);
+        assert_eq!(list.0, vec![3, 0, 1, 2, 4]);
+    }
 }
@@ -26,10 +26,12 @@ use crate::{
 
 mod action;
 mod app;
+mod audiocache;
 mod cli;
 mod compid;
 mod components;
 mod config;
+mod datastore;
 mod errors;
 mod helper;
 mod logging;
@@ -37,7 +39,9 @@ mod lyricsclient;
 mod mpris;
 mod osclient;
 mod playerworker;
+mod playlistfile;
 mod queryworker;
+mod scrobbler;
 #[cfg(test)]
 mod tests;
 mod tui;
@@ -80,20 +84,36 @@ pub fn start_workers(
     #[cfg(test)] debug_tx: UnboundedSender<bool>,
 ) -> Result<(App, JoinSet<Result<()>>)> {
     let mut set = JoinSet::new();
-    let mut pw = PlayerWorker::new(playerstatus, action_tx.clone(), config.clone());
+    let mut pw = PlayerWorker::new(playerstatus.clone(), action_tx.clone(), config.clone());
     let mut qw = QueryWorker::new(action_tx.clone(), config.clone());
     let player_tx = pw.get_tx();
     let query_tx = qw.get_tx();
 
     #[cfg(test)]
     let app = App::new(
-        config, action_tx, action_rx, mpris_tx, query_tx, player_tx, tick_rate, frame_rate,
+        config,
+        action_tx,
+        action_rx,
+        mpris_tx,
+        query_tx,
+        player_tx,
+        playerstatus,
+        tick_rate,
+        frame_rate,
         debug_tx,
     )?;
 
     #[cfg(not(test))]
     let app = App::new(
-        config, action_tx, action_rx, mpris_tx, query_tx, player_tx, tick_rate, frame_rate,
+        config,
+        action_tx,
+        action_rx,
+        mpris_tx,
+        query_tx,
+        player_tx,
+        playerstatus,
+        tick_rate,
+        frame_rate,
     )?;
 
     // Start query worker
@@ -134,6 +154,9 @@ async fn main() -> Result<()> {
     #[allow(unused_variables)]
     {
         let config = Config::new(PathConfig::new(data_str, config_str))?;
+        if let Some(command) = args.command {
+            return command.run(config).await;
+        }
 
         let playerstatus = Arc::from(RwLock::from(PlayerStatus::default()));
         let (action_tx, action_rx) = unbounded_channel::<Action>();
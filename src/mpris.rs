@@ -13,7 +13,10 @@ use tokio::sync::{
 use crate::{
     action::action::{Action, TargetedAction},
     osclient::response::getplaylist::Media,
-    playerworker::playerstatus::PlayerStatus,
+    playerworker::{
+        player::{PlayOrder, RepeatMode},
+        playerstatus::PlayerStatus,
+    },
 };
 
 pub struct AmptermMpris {
@@ -26,6 +29,8 @@ pub enum MprisSignal {
     Playing(bool),
     Volume(f32),
     Speed(f32),
+    LoopStatus(RepeatMode),
+    Shuffle(PlayOrder),
 }
 
 impl RootInterface for AmptermMpris {
@@ -92,6 +97,13 @@ impl AmptermMpris {
             metadata.title("Nothing in queue").build()
         }
     }
+    fn loop_status_of(repeat: RepeatMode) -> LoopStatus {
+        match repeat {
+            RepeatMode::Off => LoopStatus::None,
+            RepeatMode::One => LoopStatus::Track,
+            RepeatMode::All => LoopStatus::Playlist,
+        }
+    }
     fn send(&self, ta: TargetedAction) {
         let _ = self.action_tx.send(Action::Targeted(ta));
     }
@@ -139,6 +151,16 @@ impl AmptermMpris {
                     MprisSignal::Speed(s) => {
                         let _ = server.properties_changed([Property::Rate(s.into())]).await;
                     }
+                    MprisSignal::LoopStatus(r) => {
+                        let _ = server
+                            .properties_changed([Property::LoopStatus(Self::loop_status_of(r))])
+                            .await;
+                    }
+                    MprisSignal::Shuffle(o) => {
+                        let _ = server
+                            .properties_changed([Property::Shuffle(o == PlayOrder::Random)])
+                            .await;
+                    }
                 }
             }
         }
@@ -199,10 +221,16 @@ impl PlayerInterface for AmptermMpris {
     }
 
     async fn loop_status(&self) -> fdo::Result<LoopStatus> {
-        Ok(LoopStatus::None)
+        let lock = self.playerstatus.read().await;
+        Ok(Self::loop_status_of(lock.repeat))
     }
 
-    async fn set_loop_status(&self, _loop_status: LoopStatus) -> Result<()> {
+    async fn set_loop_status(&self, loop_status: LoopStatus) -> Result<()> {
+        self.send(TargetedAction::SetRepeat(match loop_status {
+            LoopStatus::None => RepeatMode::Off,
+            LoopStatus::Track => RepeatMode::One,
+            LoopStatus::Playlist => RepeatMode::All,
+        }));
         Ok(())
     }
 
@@ -216,9 +244,11 @@ impl PlayerInterface for AmptermMpris {
     }
 
     async fn set_shuffle(&self, shuffle: bool) -> Result<()> {
-        if shuffle {
-            self.send(TargetedAction::Shuffle);
-        }
+        self.send(TargetedAction::SetPlayOrder(if shuffle {
+            PlayOrder::Random
+        } else {
+            PlayOrder::Normal
+        }));
         Ok(())
     }
 
@@ -274,6 +304,7 @@ impl PlayerInterface for AmptermMpris {
     }
 
     async fn shuffle(&self) -> fdo::Result<bool> {
-        Ok(false)
+        let lock = self.playerstatus.read().await;
+        Ok(lock.order == PlayOrder::Random)
     }
 }
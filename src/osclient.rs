@@ -5,8 +5,20 @@ use rand::{rng, Rng};
 use reqwest::{Client, Url};
 use reqwest::{Method, Response};
 use response::empty::Empty;
+use response::getalbum::GetAlbum;
+use response::getalbumlist2::GetAlbumList2;
+use response::getartist::GetArtist;
+use response::getartists::GetArtists;
+use response::getgenres::GetGenres;
+use response::getmusicfolders::GetMusicFolders;
 use response::getplaylist::GetPlaylist;
 use response::getplaylists::GetPlaylists;
+use response::getplayqueue::GetPlayQueue;
+use response::getrandomsongs::GetRandomSongs;
+use response::getsimilarsongs2::GetSimilarSongs2;
+use response::getstarred2::GetStarred2;
+use response::gettopsongs::GetTopSongs;
+use response::search3::Search3;
 use response::wrapper::Wrapper;
 use serde::de::DeserializeOwned;
 use serde_json::from_str;
@@ -15,7 +27,7 @@ use std::fmt::Debug;
 use stream_download::http::ClientResponse;
 
 use crate::osclient::response::empty::AlwaysError;
-use crate::osclient::types::{MediaID, PlaylistID};
+use crate::osclient::types::{AlbumID, AlbumListType, ArtistID, FolderID, MediaID, PlaylistID};
 use crate::trace_dbg;
 mod error;
 pub mod response;
@@ -92,7 +104,7 @@ impl OSClient {
             args.push(("comment", n))
         };
         if let Some(n) = public {
-            args.push(("name", if n { "true" } else { "false" }))
+            args.push(("public", if n { "true" } else { "false" }))
         };
         if let Some(sids) = &song_id_to_add {
             for id in sids {
@@ -110,8 +122,67 @@ impl OSClient {
         self.query_auth_text::<Empty>(Method::GET, "updatePlaylist", Some(args))
             .await
     }
-    pub fn stream_link(&self, id: MediaID) -> Url {
-        self.get_path("stream", Some(vec![("id", &id)]))
+    pub async fn create_playlist(&self, name: String) -> Result<Empty, ExternalError> {
+        self.query_auth_text::<Empty>(Method::GET, "createPlaylist", Some(vec![("name", &name)]))
+            .await
+    }
+    /// Creates a playlist with the given media, or replaces the media in the playlist if the ID is
+    /// given instead of the name. The media are sent in the body as the list can be long.
+    pub async fn save_playlist(
+        &self,
+        id: Option<PlaylistID>,
+        name: Option<String>,
+        songs: Vec<MediaID>,
+    ) -> Result<Empty, ExternalError> {
+        let mut args: Vec<(&str, &str)> = vec![];
+        if let Some(n) = &id {
+            args.push(("playlistId", n))
+        };
+        if let Some(n) = &name {
+            args.push(("name", n))
+        };
+        for song in &songs {
+            args.push(("songId", &song.0));
+        }
+        self.query_auth_text::<Empty>(Method::POST, "createPlaylist", Some(args))
+            .await
+    }
+    pub async fn delete_playlist(&self, id: PlaylistID) -> Result<Empty, ExternalError> {
+        self.query_auth_text::<Empty>(Method::GET, "deletePlaylist", Some(vec![("id", &id)]))
+            .await
+    }
+    /// Fetches the original file of the media. The response is returned as is so that the file can
+    /// be written as it arrives instead of being held in the memory.
+    pub async fn download(
+        &self,
+        id: MediaID,
+    ) -> Result<Result<Response, AlwaysError>, ExternalError> {
+        let res = self
+            .query_auth(Method::GET, "download", Some(vec![("id", &id)]))
+            .await?;
+        if res
+            .content_type()
+            .is_some_and(|t| t.starts_with("application/json"))
+        {
+            let body = res.text().await.map_err(ExternalError::req)?;
+            let data = from_str::<Wrapper<AlwaysError>>(&body).map_err(ExternalError::decode)?;
+            Ok(Err(data.subsonic_response))
+        } else {
+            Ok(Ok(res))
+        }
+    }
+    /// Link to stream the media from. The server transcodes the media if `format` or
+    /// `max_bit_rate` is given.
+    pub fn stream_link(&self, id: MediaID, format: Option<&str>, max_bit_rate: Option<u32>) -> Url {
+        let rate = max_bit_rate.map(|r| r.to_string());
+        let mut params = vec![("id", id.as_str())];
+        if let Some(format) = format {
+            params.push(("format", format));
+        }
+        if let Some(rate) = &rate {
+            params.push(("maxBitRate", rate));
+        }
+        self.get_path("stream", Some(params))
     }
     pub async fn get_playlist(&self, id: PlaylistID) -> Result<GetPlaylist, ExternalError> {
         self.query_auth_text::<GetPlaylist>(Method::GET, "getPlaylist", Some(vec![("id", &id)]))
@@ -121,6 +192,163 @@ impl OSClient {
         self.query_auth_text::<GetPlaylists>(Method::GET, "getPlaylists", None)
             .await
     }
+    pub async fn get_album_list2(
+        &self,
+        list_type: &AlbumListType,
+        size: usize,
+        offset: usize,
+    ) -> Result<GetAlbumList2, ExternalError> {
+        let mut params: Vec<(&str, String)> = vec![
+            ("type", list_type.as_param().to_string()),
+            ("size", size.to_string()),
+            ("offset", offset.to_string()),
+        ];
+        match list_type {
+            AlbumListType::ByYear { from, to } => {
+                params.push(("fromYear", from.to_string()));
+                params.push(("toYear", to.to_string()));
+            }
+            AlbumListType::ByGenre(genre) => params.push(("genre", genre.clone())),
+            _ => {}
+        };
+        let args = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        self.query_auth_text::<GetAlbumList2>(Method::GET, "getAlbumList2", Some(args))
+            .await
+    }
+    pub async fn get_album(&self, id: AlbumID) -> Result<GetAlbum, ExternalError> {
+        self.query_auth_text::<GetAlbum>(Method::GET, "getAlbum", Some(vec![("id", &id)]))
+            .await
+    }
+    pub async fn get_artists(&self) -> Result<GetArtists, ExternalError> {
+        self.query_auth_text::<GetArtists>(Method::GET, "getArtists", None)
+            .await
+    }
+    pub async fn get_genres(&self) -> Result<GetGenres, ExternalError> {
+        self.query_auth_text::<GetGenres>(Method::GET, "getGenres", None)
+            .await
+    }
+    pub async fn get_music_folders(&self) -> Result<GetMusicFolders, ExternalError> {
+        self.query_auth_text::<GetMusicFolders>(Method::GET, "getMusicFolders", None)
+            .await
+    }
+    pub async fn get_artist(&self, id: ArtistID) -> Result<GetArtist, ExternalError> {
+        self.query_auth_text::<GetArtist>(Method::GET, "getArtist", Some(vec![("id", &id)]))
+            .await
+    }
+    pub async fn search3(
+        &self,
+        query: String,
+        artist_count: usize,
+        album_count: usize,
+        song_count: usize,
+    ) -> Result<Search3, ExternalError> {
+        let artist_count = artist_count.to_string();
+        let album_count = album_count.to_string();
+        let song_count = song_count.to_string();
+        self.query_auth_text::<Search3>(
+            Method::GET,
+            "search3",
+            Some(vec![
+                ("query", &query),
+                ("artistCount", &artist_count),
+                ("albumCount", &album_count),
+                ("songCount", &song_count),
+            ]),
+        )
+        .await
+    }
+    /// Media similar to the given artist, including the media of the artist itself
+    pub async fn get_similar_songs2(
+        &self,
+        id: ArtistID,
+        count: usize,
+    ) -> Result<GetSimilarSongs2, ExternalError> {
+        let count = count.to_string();
+        self.query_auth_text::<GetSimilarSongs2>(
+            Method::GET,
+            "getSimilarSongs2",
+            Some(vec![("id", &id), ("count", &count)]),
+        )
+        .await
+    }
+    /// Most popular media of the artist with the given name
+    pub async fn get_top_songs(
+        &self,
+        artist: String,
+        count: usize,
+    ) -> Result<GetTopSongs, ExternalError> {
+        let count = count.to_string();
+        self.query_auth_text::<GetTopSongs>(
+            Method::GET,
+            "getTopSongs",
+            Some(vec![("artist", &artist), ("count", &count)]),
+        )
+        .await
+    }
+    pub async fn get_random_songs(
+        &self,
+        size: usize,
+        genre: Option<String>,
+        from_year: Option<i32>,
+        to_year: Option<i32>,
+        folder: Option<FolderID>,
+    ) -> Result<GetRandomSongs, ExternalError> {
+        let mut params: Vec<(&str, String)> = vec![("size", size.to_string())];
+        if let Some(genre) = genre {
+            params.push(("genre", genre));
+        }
+        if let Some(from) = from_year {
+            params.push(("fromYear", from.to_string()));
+        }
+        if let Some(to) = to_year {
+            params.push(("toYear", to.to_string()));
+        }
+        if let Some(folder) = folder {
+            params.push(("musicFolderId", folder.to_string()));
+        }
+        let args = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        self.query_auth_text::<GetRandomSongs>(Method::GET, "getRandomSongs", Some(args))
+            .await
+    }
+    /// Saves the play queue on the server. Passing an empty list clears the saved queue.
+    pub async fn save_play_queue(
+        &self,
+        ids: Vec<MediaID>,
+        current: Option<MediaID>,
+        position: Option<u64>,
+    ) -> Result<Empty, ExternalError> {
+        let mut args: Vec<(&str, &str)> = ids.iter().map(|id| ("id", id.0.as_str())).collect();
+        if let Some(c) = &current {
+            args.push(("current", c));
+        };
+        let position = position.map(|p| p.to_string());
+        if let Some(p) = &position {
+            args.push(("position", p));
+        };
+        self.query_auth_text::<Empty>(Method::POST, "savePlayQueue", Some(args))
+            .await
+    }
+    /// Each item is a media and the time it was played in milliseconds since the UNIX epoch. If
+    /// `submission` is false, the server only updates the now-playing status.
+    pub async fn scrobble(
+        &self,
+        items: &[(MediaID, u64)],
+        submission: bool,
+    ) -> Result<Empty, ExternalError> {
+        let times: Vec<String> = items.iter().map(|(_, t)| t.to_string()).collect();
+        let mut args: Vec<(&str, &str)> = vec![];
+        for ((id, _), time) in items.iter().zip(&times) {
+            args.push(("id", id));
+            args.push(("time", time));
+        }
+        args.push(("submission", if submission { "true" } else { "false" }));
+        self.query_auth_text::<Empty>(Method::POST, "scrobble", Some(args))
+            .await
+    }
+    pub async fn get_play_queue(&self) -> Result<GetPlayQueue, ExternalError> {
+        self.query_auth_text::<GetPlayQueue>(Method::GET, "getPlayQueue", None)
+            .await
+    }
     pub async fn ping(&self) -> Result<Empty, ExternalError> {
         self.query_auth_text::<Empty>(Method::GET, "ping", None)
             .await
@@ -133,6 +361,30 @@ impl OSClient {
         self.query_auth_text::<Empty>(Method::GET, "unstar", Some(vec![("id", &id)]))
             .await
     }
+    /// Rates the media from 1 to 5. Rating 0 removes the rating.
+    pub async fn set_rating(&self, id: MediaID, rating: u8) -> Result<Empty, ExternalError> {
+        let rating = rating.to_string();
+        self.query_auth_text::<Empty>(
+            Method::GET,
+            "setRating",
+            Some(vec![("id", &id), ("rating", &rating)]),
+        )
+        .await
+    }
+    pub async fn set_album_star(&self, id: AlbumID, star: bool) -> Result<Empty, ExternalError> {
+        let endpoint = if star { "star" } else { "unstar" };
+        self.query_auth_text::<Empty>(Method::GET, endpoint, Some(vec![("albumId", &id)]))
+            .await
+    }
+    pub async fn set_artist_star(&self, id: ArtistID, star: bool) -> Result<Empty, ExternalError> {
+        let endpoint = if star { "star" } else { "unstar" };
+        self.query_auth_text::<Empty>(Method::GET, endpoint, Some(vec![("artistId", &id)]))
+            .await
+    }
+    pub async fn get_starred2(&self) -> Result<GetStarred2, ExternalError> {
+        self.query_auth_text::<GetStarred2>(Method::GET, "getStarred2", None)
+            .await
+    }
     fn get_path(&self, path: &str, query: Option<Vec<(&str, &str)>>) -> Url {
         let (mut params, mut url): (Vec<(&str, String)>, Url) = match &self.auth {
             // Login credential format can be found here
@@ -205,16 +457,26 @@ impl OSClient {
         });
         url
     }
+    /// Arguments of POST requests are sent in the body as a form, so that requests with long lists
+    /// of IDs do not exceed the length limit of the URL
     async fn query(
         &self,
         method: Method,
         path: &str,
         query: Option<Vec<(&str, &str)>>,
     ) -> Result<Response, reqwest::Error> {
-        self.client
-            .request(method, self.get_path(path, query))
-            .send()
-            .await
+        if method == Method::POST {
+            self.client
+                .request(method, self.get_path(path, None))
+                .form(&query.unwrap_or_default())
+                .send()
+                .await
+        } else {
+            self.client
+                .request(method, self.get_path(path, query))
+                .send()
+                .await
+        }
     }
     /// Make a request to an arbitrary endpoint and get binary response, or an decodable error
     /// Err(ExternalError): Fetching itself failed.
@@ -1,5 +1,17 @@
 pub mod empty;
+pub mod getalbum;
+pub mod getalbumlist2;
+pub mod getartist;
+pub mod getartists;
+pub mod getgenres;
+pub mod getmusicfolders;
 pub mod getplaylist;
 pub mod getplaylists;
+pub mod getplayqueue;
+pub mod getrandomsongs;
+pub mod getsimilarsongs2;
+pub mod getstarred2;
+pub mod gettopsongs;
 pub mod oserror;
+pub mod search3;
 pub mod wrapper;
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::{response::getplaylist::Media, types::AlbumID};
+
+use super::oserror::OSError;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FullAlbum {
+    pub id: AlbumID,
+    pub name: String,
+    pub artist: Option<String>,
+    #[serde(alias = "artistId")]
+    pub artist_id: Option<String>,
+    #[serde(alias = "coverArt")]
+    pub cover_art: Option<String>,
+    #[serde(alias = "songCount")]
+    pub song_count: u32,
+    pub duration: u32,
+    #[serde(alias = "playCount")]
+    pub play_count: Option<u64>,
+    pub created: Option<String>,
+    pub starred: Option<String>,
+    pub year: Option<i32>,
+    pub genre: Option<String>,
+    #[serde(default)]
+    pub song: Vec<Media>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetAlbum {
+    #[serde(alias = "ok")]
+    Ok { album: FullAlbum },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
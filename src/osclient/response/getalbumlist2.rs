@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::types::AlbumID;
+
+use super::oserror::OSError;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SimpleAlbum {
+    pub id: AlbumID,
+    pub name: String,
+    pub artist: Option<String>,
+    #[serde(alias = "artistId")]
+    pub artist_id: Option<String>,
+    #[serde(alias = "coverArt")]
+    pub cover_art: Option<String>,
+    #[serde(alias = "songCount")]
+    pub song_count: u32,
+    pub duration: u32,
+    #[serde(alias = "playCount")]
+    pub play_count: Option<u64>,
+    pub created: Option<String>,
+    pub starred: Option<String>,
+    pub year: Option<i32>,
+    pub genre: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetAlbumList2Wrapper {
+    /// Omitted by some servers if there are no albums to return
+    #[serde(default)]
+    pub album: Vec<SimpleAlbum>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetAlbumList2 {
+    #[serde(alias = "ok")]
+    Ok {
+        #[serde(alias = "albumList2")]
+        album_list2: GetAlbumList2Wrapper,
+    },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::{response::getalbumlist2::SimpleAlbum, types::ArtistID};
+
+use super::oserror::OSError;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FullArtist {
+    pub id: ArtistID,
+    pub name: String,
+    #[serde(alias = "coverArt")]
+    pub cover_art: Option<String>,
+    #[serde(alias = "albumCount")]
+    pub album_count: Option<u32>,
+    pub starred: Option<String>,
+    #[serde(default)]
+    pub album: Vec<SimpleAlbum>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetArtist {
+    #[serde(alias = "ok")]
+    Ok { artist: FullArtist },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::types::ArtistID;
+
+use super::oserror::OSError;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SimpleArtist {
+    pub id: ArtistID,
+    pub name: String,
+    #[serde(alias = "coverArt")]
+    pub cover_art: Option<String>,
+    #[serde(alias = "albumCount")]
+    pub album_count: Option<u32>,
+    pub starred: Option<String>,
+}
+
+/// Group of artists whose names start with the same letter
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ArtistIndex {
+    pub name: String,
+    #[serde(default)]
+    pub artist: Vec<SimpleArtist>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetArtistsWrapper {
+    #[serde(alias = "ignoredArticles")]
+    pub ignored_articles: Option<String>,
+    #[serde(default)]
+    pub index: Vec<ArtistIndex>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetArtists {
+    #[serde(alias = "ok")]
+    Ok { artists: GetArtistsWrapper },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use super::oserror::OSError;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Genre {
+    pub value: String,
+    #[serde(alias = "songCount")]
+    pub song_count: Option<u32>,
+    #[serde(alias = "albumCount")]
+    pub album_count: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct Genres {
+    /// Omitted by the server if there are no genres
+    #[serde(default)]
+    pub genre: Vec<Genre>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetGenres {
+    #[serde(alias = "ok")]
+    Ok {
+        #[serde(default)]
+        genres: Genres,
+    },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::types::FolderID;
+
+use super::oserror::OSError;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MusicFolder {
+    pub id: FolderID,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct MusicFolders {
+    /// Omitted by the server if there are no folders
+    #[serde(default, alias = "musicFolder")]
+    pub music_folder: Vec<MusicFolder>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetMusicFolders {
+    #[serde(alias = "ok")]
+    Ok {
+        #[serde(alias = "musicFolders", default)]
+        music_folders: MusicFolders,
+    },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
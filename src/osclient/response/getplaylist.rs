@@ -81,10 +81,30 @@ pub struct Media {
     #[serde(alias = "displayComposer")]
     pub display_composer: Option<String>,
     pub moods: Option<Vec<String>>,
-    // pub replay_gain: Option<ReplayGain>,
+    #[serde(alias = "replayGain")]
+    pub replay_gain: Option<ReplayGain>,
     #[serde(alias = "explicitStatus")]
     pub explicit_status: Option<String>,
 }
+/// Loudness information of a media. Gains are in dB, and peaks are linear amplitudes.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ReplayGain {
+    #[serde(alias = "trackGain")]
+    pub track_gain: Option<f32>,
+    #[serde(alias = "albumGain")]
+    pub album_gain: Option<f32>,
+    #[serde(alias = "trackPeak")]
+    pub track_peak: Option<f32>,
+    #[serde(alias = "albumPeak")]
+    pub album_peak: Option<f32>,
+    /// Gain already applied to the file (Ogg Opus output gain, for example)
+    #[serde(alias = "baseGain")]
+    pub base_gain: Option<f32>,
+    /// Gain that should be used if neither track gain nor album gain is available
+    #[serde(alias = "fallbackGain")]
+    pub fallback_gain: Option<f32>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct FullPlaylist {
     pub id: PlaylistID,
@@ -134,4 +154,44 @@ impl Media {
             "  ".to_string()
         }
     }
+
+    /// Sets the rating given by the user. Rating 0 means the media has not been rated.
+    pub fn set_rating(&mut self, rating: u8) {
+        self.user_rating = if rating == 0 {
+            None
+        } else {
+            Some(rating as i32)
+        };
+    }
+
+    /// Rating given by the user, which is 0 if the media has not been rated
+    pub fn get_rating(&self) -> u8 {
+        self.user_rating.unwrap_or(0).clamp(0, 5) as u8
+    }
+
+    /// Rating given by the user out of 5, or nothing if the media has not been rated
+    pub fn get_rating_marker(&self) -> String {
+        match self.user_rating {
+            Some(rating) if rating > 0 => format!("{}/5", rating),
+            _ => "".to_string(),
+        }
+    }
+
+    /// File extension and MIME type of the media when streamed in the given format. If the format
+    /// is not given, the server transcodes the media as specified in its own settings, which are
+    /// reflected in the `transcoded_*` fields.
+    pub fn stream_format<'a>(
+        &'a self,
+        format: Option<&'a str>,
+    ) -> (Option<&'a str>, Option<&'a str>) {
+        match format {
+            Some("raw") => (self.suffix.as_deref(), self.content_type.as_deref()),
+            Some(format) => (Some(format), None),
+            None if self.transcoded_suffix.is_some() => (
+                self.transcoded_suffix.as_deref(),
+                self.transcoded_content_type.as_deref(),
+            ),
+            None => (self.suffix.as_deref(), self.content_type.as_deref()),
+        }
+    }
 }
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::{response::getplaylist::Media, types::MediaID};
+
+use super::oserror::OSError;
+
+/// Play queue saved on the server by any client of the same user
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RemotePlayQueue {
+    #[serde(default)]
+    pub entry: Vec<Media>,
+    /// ID of the media that was being played
+    pub current: Option<MediaID>,
+    /// Position within the current media in milliseconds
+    pub position: Option<u64>,
+    pub username: Option<String>,
+    pub changed: Option<String>,
+    /// Name of the client that saved this queue
+    #[serde(alias = "changedBy")]
+    pub changed_by: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetPlayQueue {
+    /// `playQueue` is omitted entirely if nothing has been saved yet
+    #[serde(alias = "ok")]
+    Ok {
+        #[serde(alias = "playQueue", default)]
+        play_queue: Option<RemotePlayQueue>,
+    },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
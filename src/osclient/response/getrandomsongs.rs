@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::response::getplaylist::Media;
+
+use super::oserror::OSError;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct RandomSongs {
+    /// Omitted by the server if there are no media to return
+    #[serde(default)]
+    pub song: Vec<Media>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetRandomSongs {
+    #[serde(alias = "ok")]
+    Ok {
+        #[serde(alias = "randomSongs", default)]
+        random_songs: RandomSongs,
+    },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
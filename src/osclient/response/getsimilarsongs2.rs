@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::response::getplaylist::Media;
+
+use super::oserror::OSError;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct SimilarSongs2 {
+    /// Omitted by the server if there are no media to return
+    #[serde(default)]
+    pub song: Vec<Media>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetSimilarSongs2 {
+    #[serde(alias = "ok")]
+    Ok {
+        #[serde(alias = "similarSongs2", default)]
+        similar_songs2: SimilarSongs2,
+    },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::response::{
+    getalbumlist2::SimpleAlbum, getartists::SimpleArtist, getplaylist::Media,
+};
+
+use super::oserror::OSError;
+
+/// Everything the user has starred. Each list is omitted by the server if it is empty.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct Starred2 {
+    #[serde(default)]
+    pub artist: Vec<SimpleArtist>,
+    #[serde(default)]
+    pub album: Vec<SimpleAlbum>,
+    #[serde(default)]
+    pub song: Vec<Media>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetStarred2 {
+    #[serde(alias = "ok")]
+    Ok {
+        #[serde(default)]
+        starred2: Starred2,
+    },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
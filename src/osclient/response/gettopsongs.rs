@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::response::getplaylist::Media;
+
+use super::oserror::OSError;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct TopSongs {
+    /// Omitted by the server if there are no media to return
+    #[serde(default)]
+    pub song: Vec<Media>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum GetTopSongs {
+    #[serde(alias = "ok")]
+    Ok {
+        #[serde(alias = "topSongs", default)]
+        top_songs: TopSongs,
+    },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::response::{
+    getalbumlist2::SimpleAlbum, getartists::SimpleArtist, getplaylist::Media,
+};
+
+use super::oserror::OSError;
+
+/// Each field may be omitted by the server if there are no matches
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct SearchResult3 {
+    #[serde(default)]
+    pub artist: Vec<SimpleArtist>,
+    #[serde(default)]
+    pub album: Vec<SimpleAlbum>,
+    #[serde(default)]
+    pub song: Vec<Media>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "status")]
+pub enum Search3 {
+    #[serde(alias = "ok")]
+    Ok {
+        #[serde(alias = "searchResult3")]
+        search_result3: SearchResult3,
+    },
+    #[serde(alias = "failed")]
+    Failed { error: OSError },
+}
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use derive_deref::Deref;
 use serde::{Deserialize, Serialize};
 
@@ -9,3 +11,95 @@ pub struct PlaylistID(String);
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Deref)]
 pub struct CoverID(pub String);
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Deref)]
+pub struct AlbumID(pub String);
+
+/// Determines the order and the subset of albums returned by `getAlbumList2`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub enum AlbumListType {
+    Newest,
+    Recent,
+    Frequent,
+    AlphabeticalByName,
+    /// Albums released between the two years, inclusive. If `from` is greater than `to`, the
+    /// albums are sorted in reverse chronological order.
+    ByYear {
+        from: i32,
+        to: i32,
+    },
+    ByGenre(String),
+    Random,
+}
+
+impl AlbumListType {
+    /// Value of the `type` parameter in the request
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            AlbumListType::Newest => "newest",
+            AlbumListType::Recent => "recent",
+            AlbumListType::Frequent => "frequent",
+            AlbumListType::AlphabeticalByName => "alphabeticalByName",
+            AlbumListType::ByYear { from: _, to: _ } => "byYear",
+            AlbumListType::ByGenre(_) => "byGenre",
+            AlbumListType::Random => "random",
+        }
+    }
+
+    /// Returns the next list type that does not need any extra parameters. Types that do need them
+    /// ([`AlbumListType::ByYear`] and [`AlbumListType::ByGenre`]) go back to the start of the cycle.
+    pub fn next(&self) -> Self {
+        match self {
+            AlbumListType::Newest => AlbumListType::Recent,
+            AlbumListType::Recent => AlbumListType::Frequent,
+            AlbumListType::Frequent => AlbumListType::AlphabeticalByName,
+            AlbumListType::AlphabeticalByName => AlbumListType::Random,
+            _ => AlbumListType::Newest,
+        }
+    }
+
+    /// Reverse of [`AlbumListType::next`]
+    pub fn prev(&self) -> Self {
+        match self {
+            AlbumListType::Recent => AlbumListType::Newest,
+            AlbumListType::Frequent => AlbumListType::Recent,
+            AlbumListType::AlphabeticalByName => AlbumListType::Frequent,
+            AlbumListType::Random => AlbumListType::AlphabeticalByName,
+            _ => AlbumListType::Random,
+        }
+    }
+}
+
+impl Display for AlbumListType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlbumListType::Newest => write!(f, "Newest"),
+            AlbumListType::Recent => write!(f, "Recently played"),
+            AlbumListType::Frequent => write!(f, "Frequently played"),
+            AlbumListType::AlphabeticalByName => write!(f, "By name"),
+            AlbumListType::ByYear { from, to } => write!(f, "From {} to {}", from, to),
+            AlbumListType::ByGenre(genre) => write!(f, "Genre: {}", genre),
+            AlbumListType::Random => write!(f, "Random"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Deref)]
+pub struct ArtistID(pub String);
+
+/// Music folders are identified with a number by most servers, but some use strings instead
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum FolderID {
+    Number(i64),
+    Text(String),
+}
+
+impl Display for FolderID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FolderID::Number(id) => write!(f, "{}", id),
+            FolderID::Text(id) => write!(f, "{}", id),
+        }
+    }
+}
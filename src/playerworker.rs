@@ -1,31 +1,41 @@
 pub mod player;
 pub mod playerstatus;
 mod realtime;
+mod replaygain;
 mod streamerror;
 mod streamreader;
 
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use color_eyre::Result;
 use player::ToPlayerWorker;
+use reqwest::Url;
 use rodio::cpal::traits::HostTrait;
-use rodio::{cpal, OutputStream, Sink};
+use rodio::source::EmptyCallback;
+use rodio::{cpal, Decoder, OutputStream, Sink, Source};
 use streamerror::StreamError;
-use streamreader::StreamReader;
+use streamreader::{CacheTarget, MediaReader, StreamReader};
 use tokio::select;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
-use tokio::sync::RwLock;
+use tokio::sync::{oneshot, RwLock};
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use tokio_util::sync::CancellationToken;
 
 use crate::action::action::{Action, TargetedAction};
+use crate::audiocache::AudioCache;
+use crate::config::replaygainconfig::ReplayGainConfig;
 use crate::config::Config;
+use crate::osclient::response::getplaylist::Media;
 use crate::playerworker::player::FromPlayerWorker;
 use crate::playerworker::playerstatus::PlayerStatus;
 use crate::playerworker::realtime::{PosTime, RealTime};
 use crate::queryworker::highlevelquery::HighLevelQuery;
+use crate::queryworker::query::scrobble::ScrobbleParams;
 use crate::queryworker::query::ToQueryWorker;
 use crate::trace_dbg;
 
@@ -35,6 +45,33 @@ enum WorkerState {
     Idle,
 }
 
+/// Media that has been handed to the sink, or is about to be
+struct Track {
+    /// Unique to each load, so that the end of a media that is no longer relevant can be ignored
+    id: usize,
+    media: Media,
+    /// Cancelling this removes the media from the sink even if it has been appended already
+    token: CancellationToken,
+    /// False until the URL of the preloaded media arrives
+    loading: bool,
+    /// True once the media has been appended to the sink
+    ready: bool,
+    /// The media is loaded into the sink that fades in, rather than being appended after the current
+    /// media
+    crossfade: bool,
+}
+
+/// Crossfading is not done between the media from the same album so that gapless albums play as
+/// intended
+fn same_album(a: &Media, b: &Media) -> bool {
+    match (&a.album_id, &b.album_id) {
+        (Some(a), Some(b)) => a == b,
+        _ => a.album.is_some() && a.album == b.album,
+    }
+}
+
+static TRACK_COUNTER: AtomicUsize = AtomicUsize::new(1);
+
 pub struct PlayerWorker {
     state: WorkerState,
     playerstatus: Arc<RwLock<PlayerStatus>>,
@@ -43,13 +80,46 @@ pub struct PlayerWorker {
     action_tx: UnboundedSender<Action>,
     should_quit: bool,
     sink: Arc<Sink>,
+    /// Second sink connected to the mixer. The next media is loaded into this sink if it needs to be
+    /// crossfaded, and the two sinks are swapped once the fade starts.
+    fade_sink: Arc<Sink>,
+    /// Changes the volume of both sinks while the media is being crossfaded
+    fade: Option<JoinHandle<()>>,
+    crossfade: bool,
+    crossfade_duration: Duration,
+    replay_gain: ReplayGainConfig,
+    /// Played media is stored here, and played from here if it has been played before
+    cache: Option<AudioCache>,
+    /// Only the cached media is played if this is set
+    offline: bool,
     timer: RealTime,
     handle: OutputStream,
+    /// Set by [`ToPlayerWorker::ResumeFrom`], and consumed by the next [`ToPlayerWorker::PlayURL`]
+    resume_from: Option<Duration>,
+    /// Fraction of the media that needs to be played before it is scrobbled
+    scrobble_threshold: f32,
+    /// Submission for the current media, and the position at which it should be sent
+    pending_scrobble: Option<(Duration, ScrobbleParams)>,
+    /// Media at the front of the sink
+    current: Option<Track>,
+    /// Media appended after the current one
+    next: Option<Track>,
+    /// Resolves once the last loaded media has been appended to the sink
+    appended: Option<oneshot::Receiver<()>>,
+    /// Sends out [`ToPlayerWorker::Tick`] while the media is loaded
+    ticker: Option<JoinHandle<()>>,
 }
 
 impl PlayerWorker {
+    fn is_fading(&self) -> bool {
+        self.fade.as_ref().is_some_and(|f| !f.is_finished())
+    }
+
     async fn continue_stream(&mut self) {
         self.sink.play();
+        if self.is_fading() {
+            self.fade_sink.play();
+        }
         let mut lock = self.playerstatus.write().await;
         lock.playing = true;
         self.send_player_msg(FromPlayerWorker::Playing(true));
@@ -57,6 +127,9 @@ impl PlayerWorker {
 
     async fn pause_stream(&mut self) {
         self.sink.pause();
+        if self.is_fading() {
+            self.fade_sink.pause();
+        }
         let mut lock = self.playerstatus.write().await;
         lock.playing = false;
         self.send_player_msg(FromPlayerWorker::Playing(false));
@@ -85,102 +158,297 @@ impl PlayerWorker {
     fn change_speed(&mut self, to: f32) {
         self.timer.add(self.sink.get_pos(), self.sink.speed());
         self.sink.set_speed(to);
+        self.fade_sink.set_speed(to);
     }
 
-    /// Given a URL, `play_from_url` fetches music file from this URL and plays it
-    /// This spawns the following threads:
-    /// Function
-    /// |- Main playing thread
-    ///    |- Polling thread
-    ///
-    /// Main playing thread spawns a polling thread, and then runs `select!` macro to wait for the
-    /// music stream to complete.
+    /// Change the volume of the player. While media is being crossfaded, the fade applies the new
+    /// volume to both sinks instead.
+    async fn set_volume(&mut self, to: f32) {
+        self.playerstatus.write().await.volume = to;
+        if !self.is_fading() {
+            self.sink.set_volume(to);
+        }
+        self.send_player_msg(FromPlayerWorker::Volume(to));
+    }
+
+    /// Stop both sinks, and discard all loaded media
+    fn stop_sinks(&mut self) {
+        self.sink.stop();
+        self.fade_sink.stop();
+        if let Some(fade) = self.fade.take() {
+            if !fade.is_finished() {
+                fade.abort();
+                // Volumes of the two sinks always add up to the actual volume while fading
+                self.sink
+                    .set_volume(self.sink.volume() + self.fade_sink.volume());
+            }
+        }
+        if let WorkerState::Playing(token) = &self.state {
+            token.cancel();
+        };
+        self.current = None;
+        self.next = None;
+        self.appended = None;
+    }
+
+    /// Discard the preloaded media
+    fn discard_next(&mut self) {
+        if let Some(next) = self.next.take() {
+            next.token.cancel();
+            // Media in the sink that is paused is never polled, so it has to be removed manually
+            if next.crossfade && !self.is_fading() {
+                self.fade_sink.stop();
+            }
+        }
+    }
+
+    /// Fetches the media file from the URL and decodes it, ready to be appended to the sink
+    /// If the media is in the cache, the cached file is used instead.
+    async fn decode(
+        media: Media,
+        url: String,
+        cache: Option<AudioCache>,
+        offline: bool,
+        action_tx: UnboundedSender<Action>,
+    ) -> Result<Decoder<Box<dyn MediaReader>>, StreamError> {
+        let Ok(url) = url.parse::<Url>() else {
+            return Err(StreamError::parse(url));
+        };
+        let variant = AudioCache::variant(&url);
+        let id = media.id.clone();
+        // Used as a hint for the decoder, since the server may send the media in other formats
+        let format = url
+            .query_pairs()
+            .find(|(k, _)| k == "format")
+            .map(|(_, v)| v.to_string());
+        // Any version of the media is better than nothing if the server cannot be used
+        let cached = cache.as_ref().and_then(|c| {
+            if offline {
+                c.get_any(&id)
+            } else {
+                c.get(&id, &variant)
+            }
+        });
+        let (reader, len): (Box<dyn MediaReader>, Option<u64>) = match cached {
+            Some(path) => {
+                let file = File::open(path).map_err(StreamError::cache)?;
+                let len = file.metadata().ok().map(|m| m.len());
+                (Box::new(BufReader::new(file)), len)
+            }
+            None if offline => return Err(StreamError::offline()),
+            None => {
+                let target = cache.map(|cache| CacheTarget { cache, id, variant });
+                let reader = StreamReader::get_reader(url, action_tx, target).await?;
+                let len = reader.content_length();
+                (Box::new(reader), len)
+            }
+        };
+        tokio::task::spawn_blocking(move || {
+            // Panic may happen because Symphonia decoder is not being used
+            // Without Symphonia decoder, the decoding routine may contain `unwrap`
+            let mut builder = rodio::Decoder::builder().with_data(reader);
+            if let Some(len) = len {
+                builder = builder.with_byte_len(len);
+            }
+            let (suffix, mime) = media.stream_format(format.as_deref());
+            if let Some(suffix) = suffix {
+                builder = builder.with_hint(suffix);
+            }
+            if let Some(mime) = mime {
+                builder = builder.with_mime_type(mime);
+            }
+            builder.build().map_err(StreamError::decode)
+        })
+        .await
+        .map_err(StreamError::join)?
+    }
+
+    /// Given a URL, `load` fetches music file from this URL and appends it to the sink
+    /// This spawns a loading thread that does the following:
+    /// 1. Fetch and decode the media. This is stopped if the track is cancelled.
+    /// 2. Wait until the previous media is appended to the sink, if there is one. This is done
+    ///    regardless of the cancellation so that the order of the media in the sink is preserved.
+    /// 3. Append the media, followed by a callback that sends [`ToPlayerWorker::Ended`] once the
+    ///    sink reaches it.
     ///
-    /// Polling thread sends out current player position every 100 miliseconds. This is run in a
-    /// loop, and should never complete.
+    /// Because the sink plays appended media back to back, media that is loaded while another one
+    /// is playing starts without a gap. If the media is to be crossfaded, the given sink is paused
+    /// and muted instead so that the media can be faded in later.
     ///
-    /// The `select!` macro waits for 3 different things:
-    /// 1. Main playing thread
-    ///    The music has finished. `Skip` Action is sent out.
-    /// 2. Cancellation
-    ///    User has request cancellation, which causes music to stop.
-    ///    `Skip` is NOT sent out; This is handled by the `skip` function itself. Cancellation simply
-    ///    cancels the stream.
-    /// 3. Polling thread
-    ///    This should never happen, and will panic instead.
+    /// If decoding fails, only the callback is appended, so that the player moves on.
+    /// If `start_at` is given, the stream is seeked to that position as soon as it is appended.
     ///
-    /// Calling this function returns immediately with a token that can cancel the main playing
-    /// thread
-    fn play_from_url(&self, url: String) -> CancellationToken {
-        // Used by Main playing thread to append decoded source into it
-        let sink = self.sink.clone();
+    /// Calling this function returns immediately with a receiver that resolves once the media has
+    /// been appended.
+    fn load(
+        &self,
+        track: &Track,
+        sink: Arc<Sink>,
+        url: String,
+        start_at: Option<Duration>,
+        after: Option<oneshot::Receiver<()>>,
+    ) -> oneshot::Receiver<()> {
         let action_tx = self.action_tx.clone();
         let player_tx = self.player_tx.clone();
-
-        // Cancellation token used for returning
-        let token = CancellationToken::new();
-        // Cancellation token to listen to cancellation
-        let cloned_token = token.clone();
+        let token = track.token.clone();
+        let id = track.id;
+        let crossfade = track.crossfade;
+        let media = track.media.clone();
+        let cache = self.cache.clone();
+        let offline = self.offline;
+        let gain =
+            replaygain::volume_multiplier(track.media.replay_gain.as_ref(), &self.replay_gain);
+        let (done_tx, done_rx) = oneshot::channel();
         tokio::task::spawn(async move {
-            let reader =
-                match StreamReader::get_reader(url.parse().unwrap(), action_tx.clone()).await {
-                    Ok(r) => r,
-                    Err(_) => return,
-                };
-            let stream_token = reader.cancellation_token();
-            let play: JoinHandle<Result<(), StreamError>> =
-                tokio::task::spawn_blocking(move || {
-                    // Panic may happen because Symphonia decoder is not being used
-                    // Without Symphonia decoder, the decoding routine may contain `unwrap`
-                    let len = reader.content_length();
-                    let builder = rodio::Decoder::builder().with_data(reader);
-                    let source = match len {
-                        Some(len) => builder.with_byte_len(len),
-                        None => builder,
-                    }
-                    .build()
-                    .map_err(|e| StreamError::decode(e))?;
-
-                    sink.append(source);
-                    sink.sleep_until_end();
-                    Ok(())
-                });
-            let poll_state = tokio::task::spawn(async move {
-                loop {
-                    let _ = player_tx.send(ToPlayerWorker::Tick);
-                    sleep(Duration::from_millis(200)).await;
-                }
-            });
-            select! {
-                _ = cloned_token.cancelled() => {
-                    stream_token.cancel();
-                    let _ = action_tx.send(Action::Targeted(TargetedAction::Info(
-                                "Stream cancelled by user.".to_string()
-                    )));
-                    // Player does not need to do anything more, as cancellation
-                    // happens only when the stream is stopped or skipped
+            let source = select! {
+                _ = token.cancelled() => None,
+                source = Self::decode(media, url, cache, offline, action_tx.clone()) => Some(source),
+            };
+            if let Some(after) = after {
+                let _ = after.await;
+            }
+            let Some(source) = source else {
+                return;
+            };
+            if token.is_cancelled() {
+                return;
+            }
+            let loaded_tx = player_tx.clone();
+            let _ = tokio::task::spawn_blocking(move || {
+                if crossfade {
+                    sink.pause();
+                    sink.set_volume(0.0);
                 }
-                result = play => {
-                    match result {
-                        Ok(_) => {
-                            // let _ = action_tx.send(Action::NowPlaying);
-                        }
-
-                        Err(e) => {
-                            let _ = action_tx.send(Action::Targeted(TargetedAction::Err(
-                                        e.to_string()
-                            )));
+                match source {
+                    Ok(source) => {
+                        let cancelled = token.clone();
+                        sink.append(source.amplify(gain).stoppable().periodic_access(
+                            Duration::from_millis(50),
+                            move |s| {
+                                if cancelled.is_cancelled() {
+                                    s.stop();
+                                }
+                            },
+                        ));
+                        if let Some(pos) = start_at {
+                            // Failing to seek is not fatal; The media simply starts from the beginning
+                            let _ = sink.try_seek(pos);
                         }
                     }
-                    // Regardless of the error occurred, move on
-                    let _ = action_tx.send(Action::FromPlayer(FromPlayerWorker::Finished));
+                    Err(e) => {
+                        let _ =
+                            action_tx.send(Action::Targeted(TargetedAction::Err(e.to_string())));
+                    }
                 }
-                _ = poll_state => {
-                    // let _ = action_tx.send(Action::PlayerError("Stream polling crashed! Restart recommended.".to_string()));
+                sink.append(EmptyCallback::new(Box::new(move || {
+                    let _ = player_tx.send(ToPlayerWorker::Ended(id));
+                })));
+            })
+            .await;
+            let _ = loaded_tx.send(ToPlayerWorker::Loaded(id));
+            let _ = done_tx.send(());
+        });
+        done_rx
+    }
+
+    fn new_track(&self, media: Media) -> Option<Track> {
+        let WorkerState::Playing(token) = &self.state else {
+            return None;
+        };
+        Some(Track {
+            id: TRACK_COUNTER.fetch_add(1, Ordering::Relaxed),
+            media,
+            token: token.child_token(),
+            loading: false,
+            ready: false,
+            crossfade: false,
+        })
+    }
+
+    /// Polling thread sends out current player position every 200 miliseconds.
+    fn start_ticker(&mut self) {
+        if self.ticker.is_some() {
+            return;
+        }
+        let player_tx = self.player_tx.clone();
+        self.ticker = Some(tokio::task::spawn(async move {
+            loop {
+                let _ = player_tx.send(ToPlayerWorker::Tick);
+                sleep(Duration::from_millis(200)).await;
+            }
+        }));
+    }
+
+    /// Start playing the media loaded into the fade sink, fading out the current media over the
+    /// given duration. Nothing is appended to the sink that is fading out until the fade is done.
+    async fn start_crossfade(&mut self, next: Track, length: Duration) {
+        std::mem::swap(&mut self.sink, &mut self.fade_sink);
+        let from = self.fade_sink.clone();
+        let to = self.sink.clone();
+        if !from.is_paused() {
+            to.play();
+        }
+        let status = self.playerstatus.clone();
+        let (done_tx, done_rx) = oneshot::channel();
+        self.fade = Some(tokio::task::spawn(async move {
+            loop {
+                // Position is used instead of the elapsed time so that pausing pauses the fade too
+                let progress = if length.is_zero() {
+                    1.0
+                } else {
+                    (to.get_pos().as_secs_f32() / length.as_secs_f32()).min(1.0)
+                };
+                let volume = status.read().await.volume;
+                from.set_volume(volume * (1.0 - progress));
+                to.set_volume(volume * progress);
+                if progress >= 1.0 {
+                    break;
                 }
+                sleep(Duration::from_millis(20)).await;
             }
-        });
-        token
+            from.stop();
+            // Volume may have changed since it was last applied
+            to.set_volume(status.read().await.volume);
+            let _ = done_tx.send(());
+        }));
+        self.appended = Some(done_rx);
+        self.advance(next).await;
+    }
+
+    /// Start crossfading if the current media is about to end
+    async fn check_crossfade(&mut self, pos: Duration) {
+        let (Some(current), Some(next)) = (&self.current, &self.next) else {
+            return;
+        };
+        if !next.crossfade || !next.ready || self.is_fading() {
+            return;
+        }
+        let Some(duration) = current.media.duration else {
+            return;
+        };
+        let duration = Duration::from_secs(duration.max(0) as u64);
+        // Fading should not take up the majority of a short media
+        let length = self.crossfade_duration.min(duration / 2);
+        if pos + length >= duration {
+            if let Some(next) = self.next.take() {
+                self.start_crossfade(next, length).await;
+            }
+        }
+    }
+
+    /// The current media has been played to the end, and the preloaded media has taken its place
+    async fn advance(&mut self, next: Track) {
+        let media = next.media.clone();
+        self.current = Some(next);
+        self.timer.reset();
+        {
+            let mut lock = self.playerstatus.write().await;
+            lock.now_playing = Some(media.clone());
+            lock.position = Duration::from_secs(0);
+        }
+        self.start_scrobble(media.clone());
+        self.send_player_msg(FromPlayerWorker::Advanced(media.clone()));
+        self.send_player_msg(FromPlayerWorker::NowPlaying(Some(media)));
     }
 
     #[inline]
@@ -196,6 +464,31 @@ impl PlayerWorker {
             .send(Action::Targeted(TargetedAction::Err(msg)));
     }
 
+    /// Send a now-playing notification, and prepare the submission for the given media
+    fn start_scrobble(&mut self, media: Media) {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let now_playing = ScrobbleParams {
+            media,
+            time,
+            submission: false,
+        };
+        self.pending_scrobble = now_playing.media.duration.map(|secs| {
+            (
+                Duration::from_secs(secs.max(0) as u64).mul_f32(self.scrobble_threshold),
+                ScrobbleParams {
+                    submission: true,
+                    ..now_playing.clone()
+                },
+            )
+        });
+        let _ = self.action_tx.send(Action::ToQuery(ToQueryWorker::new(
+            HighLevelQuery::Scrobble(now_playing),
+        )));
+    }
+
     #[inline]
     fn send_player_msg(&self, action: FromPlayerWorker) {
         let _ = self.action_tx.send(Action::FromPlayer(action));
@@ -209,10 +502,13 @@ impl PlayerWorker {
             };
             match event {
                 ToPlayerWorker::Stop => {
-                    self.sink.stop();
-                    if let WorkerState::Playing(token) = &self.state {
-                        token.cancel();
-                    };
+                    self.resume_from = None;
+                    self.pending_scrobble = None;
+                    self.stop_sinks();
+                    self.state = WorkerState::Idle;
+                    if let Some(ticker) = self.ticker.take() {
+                        ticker.abort();
+                    }
                     self.timer.reset();
                     let mut lock = self.playerstatus.write().await;
                     lock.now_playing = None;
@@ -226,6 +522,19 @@ impl PlayerWorker {
                 ToPlayerWorker::Pause => self.pause_stream().await,
                 ToPlayerWorker::Resume => self.continue_stream().await,
                 ToPlayerWorker::Kill => self.should_quit = true,
+                ToPlayerWorker::ResumeFrom(pos) => self.resume_from = Some(pos),
+                ToPlayerWorker::SetOffline(offline) => self.offline = offline,
+                ToPlayerWorker::ToggleCrossfade => {
+                    self.crossfade = !self.crossfade;
+                    self.send_info(format!(
+                        "Crossfade {}",
+                        if self.crossfade {
+                            "enabled"
+                        } else {
+                            "disabled"
+                        }
+                    ));
+                }
                 ToPlayerWorker::PlayMedia { media } => {
                     let _ = self.action_tx.send(Action::ToQueryDelayed((
                         ToQueryWorker::new(HighLevelQuery::PlayMusicFromURL(media.clone())),
@@ -233,19 +542,106 @@ impl PlayerWorker {
                     )));
                 }
                 ToPlayerWorker::PlayURL { music, url } => {
-                    self.sink.stop();
-                    if let WorkerState::Playing(token) = &self.state {
-                        token.cancel();
-                    };
-                    let mut lock = self.playerstatus.write().await;
-                    lock.now_playing = Some(music.clone());
-                    lock.playing = true;
-                    let token = self.play_from_url(url);
+                    self.stop_sinks();
+                    self.state = WorkerState::Playing(CancellationToken::new());
+                    let resume_from = self.resume_from.take();
+                    let playing = resume_from.is_none();
+                    {
+                        let mut lock = self.playerstatus.write().await;
+                        lock.now_playing = Some(music.clone());
+                        lock.playing = playing;
+                    }
+                    if let Some(mut track) = self.new_track(music.clone()) {
+                        track.loading = true;
+                        // Sink position does not account for the playback speed
+                        self.appended = Some(self.load(
+                            &track,
+                            self.sink.clone(),
+                            url,
+                            resume_from.map(|pos| pos.div_f32(self.sink.speed())),
+                            None,
+                        ));
+                        self.current = Some(track);
+                    }
+                    self.start_ticker();
+                    self.start_scrobble(music.clone());
                     self.send_player_msg(FromPlayerWorker::NowPlaying(Some(music)));
-                    self.send_player_msg(FromPlayerWorker::Playing(true));
+                    self.send_player_msg(FromPlayerWorker::Playing(playing));
                     self.timer.reset();
-                    self.sink.play();
-                    self.state = WorkerState::Playing(token);
+                    if playing {
+                        self.sink.play();
+                    } else {
+                        self.sink.pause();
+                    }
+                }
+                ToPlayerWorker::Preload(media) => {
+                    if let (Some(media), Some(next)) = (&media, &self.next) {
+                        if media.id == next.media.id {
+                            continue;
+                        }
+                    }
+                    self.discard_next();
+                    if self.current.is_none() {
+                        continue;
+                    }
+                    if let Some(media) = media {
+                        self.next = self.new_track(media.clone());
+                        let _ = self.action_tx.send(Action::ToQuery(ToQueryWorker::new(
+                            HighLevelQuery::PreloadFromURL(media),
+                        )));
+                    }
+                }
+                ToPlayerWorker::PreloadURL { music, url } => {
+                    // Preloaded media may have been replaced while its URL was being fetched
+                    let Some(next) = self.next.as_mut() else {
+                        continue;
+                    };
+                    if next.media.id != music.id || next.loading {
+                        continue;
+                    }
+                    next.loading = true;
+                    next.crossfade = self.crossfade
+                        && !self
+                            .current
+                            .as_ref()
+                            .is_some_and(|c| same_album(&c.media, &music));
+                    let sink = if next.crossfade {
+                        self.fade_sink.clone()
+                    } else {
+                        self.sink.clone()
+                    };
+                    let after = self.appended.take();
+                    if let Some(next) = &self.next {
+                        self.appended = Some(self.load(next, sink, url, None, after));
+                    }
+                }
+                ToPlayerWorker::Loaded(id) => {
+                    if let Some(next) = self.next.as_mut().filter(|n| n.id == id) {
+                        next.ready = true;
+                    }
+                }
+                ToPlayerWorker::Ended(id) => {
+                    if self.current.as_ref().is_none_or(|c| c.id != id) {
+                        continue;
+                    }
+                    match self.next.take() {
+                        // Media in the fade sink does not play by itself, so it is started
+                        // without fading
+                        Some(next) if next.crossfade && next.ready => {
+                            self.start_crossfade(next, Duration::ZERO).await
+                        }
+                        Some(next) if next.crossfade => {
+                            self.next = Some(next);
+                            self.discard_next();
+                            self.current = None;
+                            self.send_player_msg(FromPlayerWorker::Finished);
+                        }
+                        Some(next) if next.loading => self.advance(next).await,
+                        _ => {
+                            self.current = None;
+                            self.send_player_msg(FromPlayerWorker::Finished);
+                        }
+                    }
                 }
                 ToPlayerWorker::GoToStart => {
                     if let Err(e) = self.sink.try_seek(Duration::from_secs(0)) {
@@ -272,7 +668,8 @@ impl PlayerWorker {
                     self.send_player_msg(FromPlayerWorker::Speed(cleaned));
                 }
                 ToPlayerWorker::ChangeVolume(by) => {
-                    let current = self.sink.volume();
+                    // The sink is quieter than the actual volume while media is fading in
+                    let current = self.playerstatus.read().await.volume;
                     let new_vol = current + by;
                     let cleaned = if new_vol < 0.0 {
                         0.0
@@ -281,10 +678,7 @@ impl PlayerWorker {
                     } else {
                         new_vol
                     };
-                    self.sink.set_volume(cleaned);
-                    let mut lock = self.playerstatus.write().await;
-                    lock.volume = cleaned;
-                    self.send_player_msg(FromPlayerWorker::Volume(cleaned));
+                    self.set_volume(cleaned).await;
                 }
                 ToPlayerWorker::SetVolume(to) => {
                     let cleaned = if to < 0.0 {
@@ -294,10 +688,7 @@ impl PlayerWorker {
                     } else {
                         to
                     };
-                    self.sink.set_volume(cleaned);
-                    let mut lock = self.playerstatus.write().await;
-                    lock.volume = cleaned;
-                    self.send_player_msg(FromPlayerWorker::Volume(cleaned));
+                    self.set_volume(cleaned).await;
                 }
                 ToPlayerWorker::ResumeOrPause => {
                     if self.sink.is_paused() {
@@ -325,9 +716,21 @@ impl PlayerWorker {
                     // goes backwards, it is blindly trusted.
                     self.timer.add(self.sink.get_pos(), self.sink.speed());
                     let pos = self.timer.get_now();
-                    let mut lock = self.playerstatus.write().await;
-                    lock.position = pos;
+                    {
+                        let mut lock = self.playerstatus.write().await;
+                        lock.position = pos;
+                    }
                     self.send_player_msg(FromPlayerWorker::Position(pos));
+                    if !self.sink.is_paused()
+                        && matches!(&self.pending_scrobble, Some((at, _)) if pos >= *at)
+                    {
+                        if let Some((_, params)) = self.pending_scrobble.take() {
+                            let _ = self.action_tx.send(Action::ToQuery(ToQueryWorker::new(
+                                HighLevelQuery::Scrobble(params),
+                            )));
+                        }
+                    }
+                    self.check_crossfade(pos).await;
                 }
             };
             if self.should_quit {
@@ -355,6 +758,11 @@ impl PlayerWorker {
             .unwrap();
         let sink = Arc::from(rodio::Sink::connect_new(handle.mixer()));
         sink.set_volume(config.init_state.volume);
+        // Volume changes start from the status, so it has to agree with the sink
+        if let Ok(mut status) = playerstatus.try_write() {
+            status.volume = config.init_state.volume;
+        }
+        let fade_sink = Arc::from(rodio::Sink::connect_new(handle.mixer()));
         Self {
             playerstatus,
             player_tx,
@@ -362,9 +770,25 @@ impl PlayerWorker {
             action_tx: sender,
             should_quit: false,
             sink,
+            fade_sink,
+            fade: None,
+            crossfade: config.behaviour.crossfade,
+            crossfade_duration: Duration::from_secs_f32(
+                config.behaviour.crossfade_duration.max(0.0),
+            ),
+            cache: AudioCache::new(&config),
+            offline: config.features.cache.offline,
+            replay_gain: config.features.replay_gain,
             state: WorkerState::Idle,
             timer: RealTime::new(),
             handle,
+            resume_from: None,
+            scrobble_threshold: config.features.scrobble.threshold,
+            pending_scrobble: None,
+            current: None,
+            next: None,
+            appended: None,
+            ticker: None,
         }
     }
     pub fn get_tx(&self) -> UnboundedSender<ToPlayerWorker> {
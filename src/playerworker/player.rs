@@ -41,6 +41,14 @@ pub enum ToPlayerWorker {
     /// Sets the position absolutely
     SetPosition(f32),
 
+    /// Loads the given media right after the current one so that it plays without a gap. Passing
+    /// None discards the media loaded this way.
+    Preload(Option<Media>),
+    /// Turns crossfading between media on or off
+    ToggleCrossfade,
+    /// Only play the media that has been cached if set to true
+    SetOffline(bool),
+
     /// Below should not be used by the user directly.
     PlayURL {
         music: Media,
@@ -49,17 +57,41 @@ pub enum ToPlayerWorker {
     PlayMedia {
         media: Media,
     },
+    PreloadURL {
+        music: Media,
+        url: String,
+    },
+    /// Sent once the media with the given ID has been appended to the sink
+    Loaded(usize),
+    /// Sent by the sink once the media with the given ID has been played to the end
+    Ended(usize),
+    /// The next media that gets played starts paused at the given position. Used to restore the
+    /// state from the previous session.
+    ResumeFrom(Duration),
     Kill,
     Tick,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// Order in which the items in the queue are played
+#[derive(Debug, Clone, Copy, Default, PartialEq, Display, Deserialize, Serialize)]
 pub enum PlayOrder {
+    #[default]
     Normal,
+    /// Play a random item each time. The queue never ends in this mode.
     Random,
     Reverse,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Display, Deserialize, Serialize)]
+pub enum RepeatMode {
+    #[default]
+    Off,
+    /// Repeat the current item
+    One,
+    /// Go back to the start once the end of the queue is reached
+    All,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum FromPlayerWorker {
     Playing(bool),
@@ -69,8 +101,11 @@ pub enum FromPlayerWorker {
     NowPlaying(Option<Media>),
     Volume(f32),
     Speed(f32),
-    /// Fired when the current music file has been finished
+    /// Fired when the current music file has been finished, and nothing was preloaded
     Finished,
+    /// Fired when the current music file has been finished, and the preloaded media started
+    /// playing right after it
+    Advanced(Media),
     /// Fired when the entire queue has been exhausted
     Complete,
 }
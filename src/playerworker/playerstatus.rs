@@ -1,6 +1,9 @@
 use std::time::Duration;
 
-use crate::osclient::response::getplaylist::Media;
+use crate::{
+    osclient::response::getplaylist::Media,
+    playerworker::player::{PlayOrder, RepeatMode},
+};
 
 pub struct PlayerStatus {
     pub playing: bool,
@@ -8,6 +11,8 @@ pub struct PlayerStatus {
     pub now_playing: Option<Media>,
     pub volume: f32,
     pub speed: f32,
+    pub repeat: RepeatMode,
+    pub order: PlayOrder,
 }
 
 impl Default for PlayerStatus {
@@ -18,6 +23,8 @@ impl Default for PlayerStatus {
             now_playing: None,
             volume: 0.5,
             speed: 1.0,
+            repeat: RepeatMode::default(),
+            order: PlayOrder::default(),
         }
     }
 }
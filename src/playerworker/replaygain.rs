@@ -0,0 +1,111 @@
+use crate::{
+    config::replaygainconfig::{ReplayGainConfig, ReplayGainMode},
+    osclient::response::getplaylist::ReplayGain,
+};
+
+/// Returns the amount the media should be amplified by. This is applied on top of the volume set by
+/// the user.
+pub fn volume_multiplier(gain: Option<&ReplayGain>, config: &ReplayGainConfig) -> f32 {
+    let Some(gain) = gain else {
+        return 1.0;
+    };
+    let (db, peak) = match config.mode {
+        ReplayGainMode::Off => return 1.0,
+        ReplayGainMode::Track => (
+            gain.track_gain.or(gain.album_gain),
+            gain.track_peak.or(gain.album_peak),
+        ),
+        ReplayGainMode::Album => (
+            gain.album_gain.or(gain.track_gain),
+            gain.album_peak.or(gain.track_peak),
+        ),
+    };
+    let Some(db) = db.or(gain.fallback_gain) else {
+        return 1.0;
+    };
+    let db = db + gain.base_gain.unwrap_or(0.0) + config.preamp;
+    let multiplier = 10f32.powf(db / 20.0);
+    // Amplifying the peak above 1.0 would cause clipping
+    match peak {
+        Some(peak) if peak > 0.0 && multiplier * peak > 1.0 => 1.0 / peak,
+        _ => multiplier,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gain() -> ReplayGain {
+        ReplayGain {
+            track_gain: Some(-6.0),
+            album_gain: Some(-3.0),
+            track_peak: Some(0.5),
+            album_peak: Some(0.9),
+            base_gain: None,
+            fallback_gain: None,
+        }
+    }
+
+    fn config(mode: ReplayGainMode, preamp: f32) -> ReplayGainConfig {
+        ReplayGainConfig { mode, preamp }
+    }
+
+    fn approx(a: f32, b: f32) -> bool {
+        (a - b).abs() < 0.001
+    }
+
+    #[test]
+    fn test_modes() {
+        let g = gain();
+        assert!(approx(
+            volume_multiplier(Some(&g), &config(ReplayGainMode::Track, 0.0)),
+            0.501
+        ));
+        assert!(approx(
+            volume_multiplier(Some(&g), &config(ReplayGainMode::Album, 0.0)),
+            0.708
+        ));
+        assert_eq!(
+            volume_multiplier(Some(&g), &config(ReplayGainMode::Off, 0.0)),
+            1.0
+        );
+        assert_eq!(
+            volume_multiplier(None, &config(ReplayGainMode::Track, 0.0)),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_peak_limit() {
+        // +12dB would amplify the peak of 0.5 to 2.0, so it is limited to 2x
+        let g = ReplayGain {
+            track_gain: Some(12.0),
+            ..gain()
+        };
+        assert!(approx(
+            volume_multiplier(Some(&g), &config(ReplayGainMode::Track, 0.0)),
+            2.0
+        ));
+    }
+
+    #[test]
+    fn test_fallback() {
+        let g = ReplayGain {
+            track_gain: None,
+            album_gain: None,
+            track_peak: None,
+            album_peak: None,
+            base_gain: None,
+            fallback_gain: Some(-6.0),
+        };
+        assert!(approx(
+            volume_multiplier(Some(&g), &config(ReplayGainMode::Album, 0.0)),
+            0.501
+        ));
+        assert!(approx(
+            volume_multiplier(Some(&g), &config(ReplayGainMode::Album, 6.0)),
+            1.0
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use std::{error::Error, fmt::Display};
+use std::{error::Error, fmt::Display, io};
 use stream_download::{
     http::{HttpStream, HttpStreamError},
     StreamInitializationError,
@@ -14,6 +14,8 @@ enum ErrType {
     Rodio(rodio::StreamError),
     Decode(rodio::decoder::DecoderError),
     Play(rodio::PlayError),
+    Cache(io::Error),
+    Offline,
 }
 impl Display for ErrType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -25,6 +27,8 @@ impl Display for ErrType {
             ErrType::Rodio(e) => write!(f, "Output stream error: {}", e),
             ErrType::Decode(e) => write!(f, "Decode error: {}", e),
             ErrType::Play(e) => write!(f, "Player error: {}", e),
+            ErrType::Cache(e) => write!(f, "Failed to read cached media: {}", e),
+            ErrType::Offline => write!(f, "Media is not cached, and cannot be played offline"),
         }
     }
 }
@@ -84,4 +88,16 @@ impl StreamError {
             reason: ErrType::Decode(e),
         }
     }
+
+    pub fn cache(e: io::Error) -> StreamError {
+        Self {
+            reason: ErrType::Cache(e),
+        }
+    }
+
+    pub fn offline() -> StreamError {
+        Self {
+            reason: ErrType::Offline,
+        }
+    }
 }
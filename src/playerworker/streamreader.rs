@@ -1,23 +1,61 @@
+use std::io::{Read, Seek};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use reqwest::Url;
 use stream_download::http::HttpStream;
 use stream_download::source::SourceStream;
-use stream_download::storage::temp::TempStorageProvider;
+use stream_download::storage::temp::{tempfile, TempStorageProvider};
 use stream_download::{Settings, StreamDownload, StreamPhase};
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::action::action::{Action, TargetedAction};
+use crate::audiocache::AudioCache;
+use crate::osclient::types::MediaID;
 
 use super::streamerror::StreamError;
 
+/// Anything the decoder can read the media from
+pub trait MediaReader: Read + Seek + Send + Sync {}
+
+impl<T> MediaReader for T where T: Read + Seek + Send + Sync {}
+
+/// Where the downloaded media should go once the download is complete
+pub struct CacheTarget {
+    pub cache: AudioCache,
+    pub id: MediaID,
+    pub variant: String,
+}
+
 pub struct StreamReader {}
 
 impl StreamReader {
+    /// Download into the cache directory instead of the OS temporary directory so that the file
+    /// can be linked into the cache without copying it
+    fn storage(
+        target: &Option<CacheTarget>,
+        path: Arc<Mutex<Option<PathBuf>>>,
+    ) -> TempStorageProvider {
+        let Some(dir) = target.as_ref().and_then(|t| t.cache.partial_dir().ok()) else {
+            return TempStorageProvider::new();
+        };
+        TempStorageProvider::with_tempfile_builder(move || {
+            let file = tempfile::Builder::new().tempfile_in(&dir)?;
+            if let Ok(mut lock) = path.lock() {
+                *lock = Some(file.path().to_path_buf());
+            }
+            Ok(file)
+        })
+    }
+
     pub async fn get_reader(
         url: Url,
         action_tx: UnboundedSender<Action>,
+        mut target: Option<CacheTarget>,
     ) -> Result<StreamDownload<TempStorageProvider>, StreamError> {
+        let path = Arc::new(Mutex::new(None));
+        let storage = Self::storage(&target, path.clone());
         let mut last_event = Instant::now();
         let settings = Settings::default().on_progress(move |stream: &HttpStream<_>, state, _| {
             // std::thread::sleep(Duration::from_millis(200));
@@ -47,13 +85,25 @@ impl StreamReader {
                     )
                 }
                 StreamPhase::Complete => {
+                    let file = path.lock().ok().and_then(|mut p| p.take());
+                    let complete = stream
+                        .content_length()
+                        .is_none_or(|len| len == state.current_position);
+                    if let (Some(file), Some(target), true) = (file, target.take(), complete) {
+                        tokio::task::spawn_blocking(move || {
+                            if let Err(e) = target.cache.insert(&target.id, &target.variant, &file)
+                            {
+                                tracing::error!("Failed to cache media: {e}");
+                            }
+                        });
+                    }
                     format!("{:.2?} download complete", state.elapsed)
                 }
                 _ => String::default(),
             };
             action_tx.send(Action::Targeted(TargetedAction::Info(msg)));
         });
-        match StreamDownload::new_http(url, TempStorageProvider::new(), settings).await {
+        match StreamDownload::new_http(url, storage, settings).await {
             Ok(reader) => Ok(reader),
             Err(e) => Err(StreamError::stream_init(e)),
         }
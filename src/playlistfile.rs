@@ -0,0 +1,487 @@
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::{
+    config::playlistfileconfig::PlaylistFormat,
+    osclient::{
+        response::{
+            empty::Empty,
+            getplaylist::{GetPlaylist, IndeterminedPlaylist, Media},
+            getplaylists::{GetPlaylists, SimplePlaylist},
+            search3::Search3,
+        },
+        types::PlaylistID,
+        OSClient,
+    },
+    queryworker::query::download::sanitise,
+};
+
+/// Number of search results compared against each entry when importing
+const CANDIDATES: usize = 20;
+
+/// Track read from a playlist file. Files written by other players may only have some of these.
+#[derive(Debug, Default, PartialEq)]
+pub struct Entry {
+    pub location: Option<String>,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    /// Length in seconds
+    pub duration: Option<i32>,
+    /// MusicBrainz recording ID
+    pub mbid: Option<String>,
+}
+
+/// Outcome of importing a playlist file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportResult {
+    /// Name of the playlist created on the server
+    pub name: String,
+    /// Number of entries that were found on the server
+    pub count: usize,
+    /// Entries that could not be found on the server
+    pub unmatched: Vec<String>,
+}
+
+/// Lowercase letters and digits only, so that small differences in punctuation are ignored
+fn normalise(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// URI of the media in an XSPF file. Absolute paths become file URIs, while the paths relative to
+/// the music folder are kept relative, to be resolved against the location of the playlist.
+fn to_uri(path: &str) -> String {
+    let encoded: String = path
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect();
+    if path.starts_with('/') {
+        format!("file://{encoded}")
+    } else {
+        encoded
+    }
+}
+
+/// Path of the media in an XSPF file, which is the reverse of [`to_uri`]
+fn from_uri(uri: &str) -> String {
+    let uri = uri.strip_prefix("file://").unwrap_or(uri).as_bytes();
+    let mut bytes = Vec::with_capacity(uri.len());
+    let mut i = 0;
+    while i < uri.len() {
+        let hex = uri
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (uri[i], hex) {
+            (b'%', Some(b)) => {
+                bytes.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                bytes.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+impl Entry {
+    /// Text the server is searched with
+    fn search_term(&self) -> Option<String> {
+        self.title.clone().or_else(|| {
+            let location = self.location.as_ref()?;
+            let stem = Path::new(location).file_stem()?.to_str()?;
+            Some(stem.to_string())
+        })
+    }
+
+    /// Picks the media that is most likely to be this entry. The path is compared first, then the
+    /// MusicBrainz ID, then the artist and the title.
+    fn pick<'a>(&self, candidates: &'a [Media]) -> Option<&'a Media> {
+        let by_path = self.location.as_ref().and_then(|location| {
+            candidates.iter().find(|m| {
+                m.path.as_ref().is_some_and(|path| {
+                    // Whole components are compared, so that "1.mp3" does not match "11.mp3"
+                    let (location, path) = (Path::new(location), Path::new(path));
+                    !path.as_os_str().is_empty()
+                        && (location.ends_with(path) || path.ends_with(location))
+                })
+            })
+        });
+        let by_mbid = || {
+            let mbid = self.mbid.as_ref()?;
+            candidates
+                .iter()
+                .find(|m| m.music_brainz_id.as_ref() == Some(mbid))
+        };
+        let by_name = || {
+            let title = normalise(self.title.as_ref()?);
+            let artist = self.artist.as_deref().map(normalise);
+            candidates.iter().find(|m| {
+                normalise(&m.title) == title
+                    && match (&artist, &m.artist) {
+                        (Some(a), Some(b)) => {
+                            let b = normalise(b);
+                            b.contains(a.as_str()) || a.contains(b.as_str())
+                        }
+                        _ => true,
+                    }
+            })
+        };
+        by_path.or_else(by_mbid).or_else(by_name)
+    }
+
+    /// Text shown to the user if the entry cannot be found
+    pub fn describe(&self) -> String {
+        match (&self.artist, &self.title, &self.location) {
+            (Some(artist), Some(title), _) => format!("{artist} - {title}"),
+            (None, Some(title), _) => title.clone(),
+            (_, None, Some(location)) => location.clone(),
+            _ => "Unknown entry".to_string(),
+        }
+    }
+}
+
+/// Converts the items into the content of a playlist file. Media without a path are written
+/// without a location, so that they can still be found by their title when imported.
+pub fn write(name: &str, items: &[Media], format: PlaylistFormat) -> String {
+    match format {
+        PlaylistFormat::M3u => {
+            let mut out = format!("#EXTM3U\n#PLAYLIST:{name}\n");
+            for m in items {
+                let info = match &m.artist {
+                    Some(artist) => format!("{artist} - {}", m.title),
+                    None => m.title.clone(),
+                };
+                out.push_str(&format!("#EXTINF:{},{info}\n", m.duration.unwrap_or(-1)));
+                if let Some(path) = &m.path {
+                    out.push_str(&format!("{path}\n"));
+                }
+            }
+            out
+        }
+        PlaylistFormat::Xspf => {
+            let mut out = format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                <playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n  \
+                <title>{}</title>\n  <trackList>\n",
+                escape(name)
+            );
+            for m in items {
+                out.push_str("    <track>\n");
+                let fields = [
+                    ("location", m.path.as_deref().map(to_uri)),
+                    (
+                        "identifier",
+                        m.music_brainz_id
+                            .as_ref()
+                            .map(|id| format!("https://musicbrainz.org/recording/{id}")),
+                    ),
+                    ("title", Some(m.title.clone())),
+                    ("creator", m.artist.clone()),
+                    ("album", m.album.clone()),
+                    (
+                        "duration",
+                        m.duration.map(|d| (d as i64 * 1000).to_string()),
+                    ),
+                ];
+                for (tag, value) in fields {
+                    if let Some(v) = value {
+                        out.push_str(&format!("      <{tag}>{}</{tag}>\n", escape(&v)));
+                    }
+                }
+                out.push_str("    </track>\n");
+            }
+            out.push_str("  </trackList>\n</playlist>\n");
+            out
+        }
+    }
+}
+
+/// Reads the name of the playlist, if there is one, and the entries from the content of a
+/// playlist file
+pub fn read(text: &str, format: PlaylistFormat) -> (Option<String>, Vec<Entry>) {
+    let text = text.trim_start_matches('\u{feff}');
+    match format {
+        PlaylistFormat::M3u => {
+            let mut name = None;
+            let mut entries = vec![];
+            let mut current = Entry::default();
+            for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                if let Some(info) = line.strip_prefix("#EXTINF:") {
+                    // The previous entry had no location
+                    if current != Entry::default() {
+                        entries.push(std::mem::take(&mut current));
+                    }
+                    let (duration, rest) = info.split_once(',').unwrap_or((info, ""));
+                    current.duration = duration.trim().parse().ok().filter(|d| *d >= 0);
+                    match rest.split_once(" - ") {
+                        Some((artist, title)) => {
+                            current.artist = Some(artist.trim().to_string());
+                            current.title = Some(title.trim().to_string());
+                        }
+                        None if !rest.trim().is_empty() => {
+                            current.title = Some(rest.trim().to_string())
+                        }
+                        None => {}
+                    }
+                } else if let Some(n) = line.strip_prefix("#PLAYLIST:") {
+                    name = Some(n.trim().to_string());
+                } else if !line.starts_with('#') {
+                    current.location = Some(line.to_string());
+                    entries.push(std::mem::take(&mut current));
+                }
+            }
+            if current != Entry::default() {
+                entries.push(current);
+            }
+            (name, entries)
+        }
+        PlaylistFormat::Xspf => {
+            let track = Regex::new(r"(?s)<track>(.*?)</track>").expect("Invalid regex");
+            let field = |body: &str, tag: &str| {
+                Regex::new(&format!(r"(?s)<{tag}>(.*?)</{tag}>"))
+                    .expect("Invalid regex")
+                    .captures(body)
+                    .map(|c| unescape(c[1].trim()))
+            };
+            let name = text
+                .split("<trackList>")
+                .next()
+                .and_then(|head| field(head, "title"));
+            let entries = track
+                .captures_iter(text)
+                .map(|c| {
+                    let body = &c[1];
+                    Entry {
+                        location: field(body, "location").map(|l| from_uri(&l)),
+                        title: field(body, "title"),
+                        artist: field(body, "creator"),
+                        duration: field(body, "duration")
+                            .and_then(|d| d.parse::<i64>().ok())
+                            .map(|ms| (ms / 1000) as i32),
+                        mbid: field(body, "identifier").and_then(|id| {
+                            id.contains("musicbrainz")
+                                .then(|| id.rsplit('/').next().map(str::to_string))
+                                .flatten()
+                        }),
+                    }
+                })
+                .collect();
+            (name, entries)
+        }
+    }
+}
+
+/// Path of the exported playlist in the given directory
+pub fn export_path(dir: &Path, name: &str, format: PlaylistFormat) -> PathBuf {
+    dir.join(format!("{}.{}", sanitise(name), format.extension()))
+}
+
+/// Writes the items to a playlist file, creating the directory if needed
+pub async fn export(
+    path: &Path,
+    name: &str,
+    items: &[Media],
+    format: PlaylistFormat,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    tokio::fs::write(path, write(name, items, format))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Fetches every media in the playlist
+pub async fn fetch_items(c: &OSClient, id: PlaylistID) -> Result<Vec<Media>, String> {
+    match c.get_playlist(id).await {
+        Ok(GetPlaylist::Ok { playlist }) => match playlist {
+            IndeterminedPlaylist::FullPlaylist(p) => Ok(p.entry),
+            IndeterminedPlaylist::AmpacheEmpty(_) | IndeterminedPlaylist::NavidromeEmpty(_) => {
+                Ok(vec![])
+            }
+        },
+        Ok(GetPlaylist::Failed { error }) => Err(error.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Finds the playlist with the given ID, or the given name if no ID matches
+pub async fn find_playlist(c: &OSClient, query: &str) -> Result<SimplePlaylist, String> {
+    let playlists = match c.get_playlists().await {
+        Ok(GetPlaylists::Ok { playlists }) => playlists.playlist,
+        Ok(GetPlaylists::Failed { error }) => return Err(error.to_string()),
+        Err(e) => return Err(e.to_string()),
+    };
+    let by_id = playlists.iter().position(|p| p.id.as_str() == query);
+    let by_name = || playlists.iter().position(|p| p.name == query);
+    match by_id.or_else(by_name) {
+        Some(pos) => Ok(playlists[pos].clone()),
+        None => Err(format!("No playlist is named {}", query)),
+    }
+}
+
+/// Searches the server for the media the entry refers to
+async fn find(c: &OSClient, entry: &Entry) -> Result<Option<Media>, String> {
+    let Some(term) = entry.search_term() else {
+        return Ok(None);
+    };
+    match c.search3(term, 0, 0, CANDIDATES).await {
+        Ok(Search3::Ok { search_result3 }) => Ok(entry.pick(&search_result3.song).cloned()),
+        Ok(Search3::Failed { error }) => Err(error.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Reads a playlist file and creates a playlist on the server with every entry that could be
+/// found. The name in the file, or the name of the file, is used unless a name is given.
+pub async fn import(
+    c: &OSClient,
+    path: &Path,
+    name: Option<String>,
+) -> Result<ImportResult, String> {
+    let format = PlaylistFormat::from_path(path)
+        .ok_or_else(|| format!("{} is not an M3U or XSPF file", path.display()))?;
+    let text = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (title, entries) = read(&text, format);
+    let name = name
+        .or(title)
+        .or_else(|| Some(path.file_stem()?.to_string_lossy().to_string()))
+        .unwrap_or("Imported".to_string());
+    let mut ids = vec![];
+    let mut unmatched = vec![];
+    for entry in &entries {
+        match find(c, entry).await {
+            Ok(Some(media)) => ids.push(media.id),
+            Ok(None) => unmatched.push(entry.describe()),
+            Err(e) => unmatched.push(format!("{} ({})", entry.describe(), e)),
+        }
+    }
+    if ids.is_empty() {
+        return Err(format!(
+            "None of the {} items in {} were found on the server",
+            entries.len(),
+            path.display()
+        ));
+    }
+    let count = ids.len();
+    match c.save_playlist(None, Some(name.clone()), ids).await {
+        Ok(Empty::Ok) => Ok(ImportResult {
+            name,
+            count,
+            unmatched,
+        }),
+        Ok(Empty::Failed { error }) => Err(error.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn media(title: &str, artist: &str, path: &str) -> Media {
+        serde_json::from_value(serde_json::json!({
+            "id": title,
+            "isDir": false,
+            "title": title,
+            "artist": artist,
+            "path": path,
+            "duration": 61,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let items = vec![
+            media("Title & More", "Artist", "Artist/Album/01 - Title.flac"),
+            media("Second", "Other - Artist", "Other/Second.mp3"),
+        ];
+        for format in [PlaylistFormat::M3u, PlaylistFormat::Xspf] {
+            let (name, entries) = read(&write("Mix", &items, format), format);
+            assert_eq!(name, Some("Mix".to_string()));
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].location.as_deref(), items[0].path.as_deref());
+            assert_eq!(entries[0].duration, Some(61));
+            assert_eq!(entries[0].pick(&items), Some(&items[0]));
+            assert_eq!(entries[1].pick(&items), Some(&items[1]));
+        }
+    }
+
+    #[test]
+    fn test_locations() {
+        let mut items = vec![
+            media("Absolute", "Artist", "/music/A & B/01 #1.flac"),
+            media("No Path", "Artist", ""),
+        ];
+        items[1].path = None;
+        let m3u = write("Mix", &items, PlaylistFormat::M3u);
+        assert!(!m3u.lines().any(|l| l == "No Path"));
+        let xspf = write("Mix", &items, PlaylistFormat::Xspf);
+        assert!(xspf.contains("<location>file:///music/A%20%26%20B/01%20%231.flac</location>"));
+        assert_eq!(xspf.matches("<location>").count(), 1);
+        for format in [PlaylistFormat::M3u, PlaylistFormat::Xspf] {
+            let (_, entries) = read(&write("Mix", &items, format), format);
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].location.as_deref(), items[0].path.as_deref());
+            assert_eq!(entries[1].location, None);
+            assert_eq!(entries[1].title.as_deref(), Some("No Path"));
+        }
+    }
+
+    #[test]
+    fn test_pick_by_name() {
+        let items = vec![
+            media("Song", "Someone Else", "a.mp3"),
+            media("Song!", "The Artist", "b.mp3"),
+        ];
+        let (_, entries) = read(
+            "#EXTM3U\n#EXTINF:100,the artist - song\n/music/elsewhere.mp3\n",
+            PlaylistFormat::M3u,
+        );
+        assert_eq!(entries[0].pick(&items), Some(&items[1]));
+        assert_eq!(entries[0].describe(), "the artist - song");
+    }
+
+    #[test]
+    fn test_pick_by_path() {
+        let items = vec![
+            media("Eleven", "Artist", "Album/11.mp3"),
+            media("One", "Artist", "Album/1.mp3"),
+        ];
+        let (_, entries) = read("/music/Album/1.mp3\n1.mp3\n", PlaylistFormat::M3u);
+        assert_eq!(entries[0].pick(&items), Some(&items[1]));
+        assert_eq!(entries[1].pick(&items), Some(&items[1]));
+    }
+}
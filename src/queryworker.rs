@@ -1,34 +1,67 @@
 pub mod highlevelquery;
 pub mod query;
 
+use std::collections::HashMap;
 use std::io::Cursor;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::action::action::Action;
 use crate::compid::CompID;
+use crate::config::playlistfileconfig::PlaylistFileConfig;
+use crate::config::streamingconfig::StreamProfile;
 use crate::config::Config;
+use crate::datastore::DataStore;
 use crate::lyricsclient::getlyrics::GetLyricsParams;
 use crate::lyricsclient::lrclib::LrcLib;
 use crate::lyricsclient::LyricsClient;
+use crate::osclient::response::empty::AlwaysError;
 use crate::osclient::response::empty::Empty;
+use crate::osclient::response::getalbum::GetAlbum;
+use crate::osclient::response::getalbumlist2::GetAlbumList2;
+use crate::osclient::response::getartist::GetArtist;
+use crate::osclient::response::getartists::GetArtists;
+use crate::osclient::response::getgenres::GetGenres;
+use crate::osclient::response::getmusicfolders::GetMusicFolders;
 use crate::osclient::response::getplaylist::{GetPlaylist, IndeterminedPlaylist, Media};
 use crate::osclient::response::getplaylists::{GetPlaylists, SimplePlaylist};
-use crate::osclient::types::CoverID;
+use crate::osclient::response::getplayqueue::GetPlayQueue;
+use crate::osclient::response::getrandomsongs::GetRandomSongs;
+use crate::osclient::response::getsimilarsongs2::GetSimilarSongs2;
+use crate::osclient::response::getstarred2::GetStarred2;
+use crate::osclient::response::gettopsongs::GetTopSongs;
+use crate::osclient::response::search3::Search3;
+use crate::osclient::types::{CoverID, MediaID};
 use crate::osclient::OSClient;
 use crate::playerworker::player::ToPlayerWorker;
+use crate::playlistfile;
 use crate::queryworker::highlevelquery::HighLevelQuery;
+use crate::queryworker::query::download::file_path;
+use crate::queryworker::query::exportplaylist::ExportSource;
+use crate::queryworker::query::getalbum::GetAlbumResponse;
+use crate::queryworker::query::getalbumlist::GetAlbumListResponse;
+use crate::queryworker::query::getartist::GetArtistResponse;
 use crate::queryworker::query::getplaylist::GetPlaylistResponse;
-use crate::queryworker::query::setcredential::Credential;
+use crate::queryworker::query::radio::RadioParams;
+use crate::queryworker::query::saveplaylist::SaveTarget;
+use crate::queryworker::query::scrobble::ScrobbleParams;
+use crate::queryworker::query::setrating::SetRatingResponse;
 use crate::queryworker::query::{QueryStatus, ResponseType};
+use crate::scrobbler::listen::Listen;
+use crate::scrobbler::listenbrainz::ListenBrainz;
+use crate::scrobbler::Scrobbler;
 use crate::trace_dbg;
 use bytes::Bytes;
 use color_eyre::eyre::Error;
 use color_eyre::Result;
 use image::{DynamicImage, ImageReader};
 use query::ToQueryWorker;
+use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Default)]
 struct Cache {
@@ -43,10 +76,53 @@ pub struct QueryWorker {
     action_tx: UnboundedSender<Action>,
     should_quit: bool,
     cache: Arc<Mutex<Cache>>,
+    /// Report played media to the server
+    scrobble: bool,
+    /// Holds the submissions that could not reach the server or the external scrobbler, so that
+    /// they can be sent again later, even after a restart
+    store: Option<Arc<Mutex<DataStore>>>,
+    /// External scrobbler
+    listenbrainz: Option<Arc<ListenBrainz>>,
+    /// Directory and the file name template for the downloaded media
+    download: (PathBuf, String),
+    /// Downloads that are running, which can be stopped with [`HighLevelQuery::CancelTask`]
+    downloads: HashMap<usize, CancellationToken>,
+    /// Transcoding parameters of the media streamed from now on
+    stream_profile: StreamProfile,
+    /// Where and how playlists are exported
+    playlist_file: PlaylistFileConfig,
+    /// The server is not reached at login, and browsing the server is refused
+    offline: bool,
 }
 
+/// Minimum interval between the progress updates of a download
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
 static COUNTER: AtomicUsize = AtomicUsize::new(1);
 
+fn scrobble_result<E: ToString>(res: Result<Empty, E>) -> Result<(), String> {
+    match res {
+        Ok(Empty::Ok) => Ok(()),
+        Ok(Empty::Failed { error }) => Err(error.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Sends every saved submission to the server, and removes them once they are accepted
+async fn flush_scrobbles(c: &OSClient, store: &mut DataStore) -> Result<(), String> {
+    let backlog = store.get_scrobbles().map_err(|e| e.to_string())?;
+    if backlog.is_empty() {
+        return Ok(());
+    }
+    let items: Vec<(MediaID, u64)> = backlog
+        .iter()
+        .map(|(_, id, time)| (id.clone(), *time))
+        .collect();
+    scrobble_result(c.scrobble(&items, true).await)?;
+    let ids: Vec<i64> = backlog.iter().map(|(id, _, _)| *id).collect();
+    store.remove_scrobbles(&ids).map_err(|e| e.to_string())
+}
+
 impl QueryWorker {
     /// Returns a unique ticket number
     /// This value must be included in every request sent to this worker
@@ -113,7 +189,44 @@ impl QueryWorker {
         Ok(())
     }
 
+    /// Response for the queries that browse the server, which are not available in offline mode
+    fn offline_response(query: &HighLevelQuery) -> Option<ResponseType> {
+        let msg = "Not available in offline mode".to_string();
+        Some(match query {
+            HighLevelQuery::ListPlaylists | HighLevelQuery::ListPlaylistsPopup(_) => {
+                ResponseType::GetPlaylists(Err(msg))
+            }
+            HighLevelQuery::ListAlbums(params) => {
+                ResponseType::GetAlbumList(GetAlbumListResponse {
+                    params: params.clone(),
+                    albums: Err(msg),
+                })
+            }
+            HighLevelQuery::ListArtists => ResponseType::GetArtists(Err(msg)),
+            HighLevelQuery::SelectArtist(params) => {
+                ResponseType::GetArtist(GetArtistResponse::Failure {
+                    id: params.id.clone(),
+                    name: params.name.clone(),
+                    msg,
+                })
+            }
+            HighLevelQuery::Search(_) => ResponseType::Search(Err(msg)),
+            HighLevelQuery::ListStarred => ResponseType::GetStarred(Err(msg)),
+            _ => return None,
+        })
+    }
+
     fn handle_event(&mut self, query: ToQueryWorker) -> Result<(), Error> {
+        if self.offline {
+            if let Some(res) = Self::offline_response(&query.query) {
+                self.action_tx.send(Action::FromQuery {
+                    dest: query.dest,
+                    ticket: query.ticket,
+                    res: QueryStatus::Finished(res),
+                })?;
+                return Ok(());
+            }
+        }
         match query.query {
             HighLevelQuery::SetStar { media, star } => {
                 let (tx, c) = self.prepare_async();
@@ -134,37 +247,146 @@ impl QueryWorker {
                     })
                 });
             }
+            HighLevelQuery::SetRating {
+                media,
+                rating,
+                previous,
+            } => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.set_rating(media.clone(), rating).await {
+                        Ok(Empty::Ok) => SetRatingResponse::Success,
+                        Ok(Empty::Failed { error }) => SetRatingResponse::Failure {
+                            media,
+                            previous,
+                            msg: error.to_string(),
+                        },
+                        Err(err) => SetRatingResponse::Failure {
+                            media,
+                            previous,
+                            msg: err.to_string(),
+                        },
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::SetRating(res)),
+                    });
+                });
+            }
+            HighLevelQuery::SetAlbumStar { album, star } => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.set_album_star(album, star).await {
+                        Ok(_) => Ok(()),
+                        Err(err) => Err(err.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::Star(res)),
+                    });
+                });
+            }
+            HighLevelQuery::SetArtistStar { artist, star } => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.set_artist_star(artist, star).await {
+                        Ok(_) => Ok(()),
+                        Err(err) => Err(err.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::Star(res)),
+                    });
+                });
+            }
+            HighLevelQuery::ListStarred => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.get_starred2().await {
+                        Ok(GetStarred2::Ok { starred2 }) => Ok(starred2),
+                        Ok(GetStarred2::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::GetStarred(res)),
+                    });
+                });
+            }
+            HighLevelQuery::AddArtistToQueue(params) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.get_artist(params.id).await {
+                        Ok(GetArtist::Ok { artist }) => {
+                            let mut songs = vec![];
+                            let mut error = None;
+                            for album in artist.album {
+                                match c.get_album(album.id).await {
+                                    Ok(GetAlbum::Ok { album }) => songs.extend(album.song),
+                                    Ok(GetAlbum::Failed { error: e }) => {
+                                        error = Some(e.to_string())
+                                    }
+                                    Err(e) => error = Some(e.to_string()),
+                                }
+                            }
+                            // Albums that failed to load are skipped unless none of them loaded
+                            match error {
+                                Some(e) if songs.is_empty() => Err(e),
+                                _ => Ok(songs),
+                            }
+                        }
+                        Ok(GetArtist::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::GetArtistSongs(res)),
+                    });
+                });
+            }
             HighLevelQuery::ListPlaylists => self.get_playlists(query, true),
             HighLevelQuery::ListPlaylistsPopup(force) => self.get_playlists(query, force),
             HighLevelQuery::Login(creds) => {
-                let client = match creds {
-                    Credential::Password {
-                        url,
-                        secure,
-                        username,
-                        password,
-                        legacy,
-                    } => OSClient::use_password(url, username, password, legacy, secure),
-                    Credential::APIKey {
-                        url,
-                        secure,
-                        username,
-                        apikey,
-                    } => OSClient::use_apikey(url, username, apikey, secure),
-                };
-                match client {
+                match creds.into_client() {
+                    // The server may not be reachable at all, so it is not pinged
+                    Ok(client) if self.offline => {
+                        self.client = Some(Arc::from(client));
+                        self.action_tx.send(Action::FromQuery {
+                            dest: query.dest,
+                            ticket: query.ticket,
+                            res: QueryStatus::Finished(ResponseType::Login(Ok(()))),
+                        })?;
+                    }
                     Ok(client) => {
                         self.client = Some(Arc::from(client));
                         let (tx, c) = self.prepare_async();
+                        let store = self.store.clone().filter(|_| self.scrobble);
                         tokio::spawn(async move {
                             let ping = c.ping().await;
                             match ping {
-                                Ok(c) => match c {
-                                    Empty::Ok => tx.send(Action::FromQuery {
-                                        dest: query.dest,
-                                        ticket: query.ticket,
-                                        res: QueryStatus::Finished(ResponseType::Login(Ok(()))),
-                                    }),
+                                Ok(res) => match res {
+                                    Empty::Ok => {
+                                        let sent = tx.send(Action::FromQuery {
+                                            dest: query.dest,
+                                            ticket: query.ticket,
+                                            res: QueryStatus::Finished(ResponseType::Login(Ok(()))),
+                                        });
+                                        // Submissions left over from the last session
+                                        if let Some(store) = store {
+                                            let mut store = store.lock().await;
+                                            if let Err(e) = flush_scrobbles(&c, &mut store).await {
+                                                tracing::warn!(
+                                                    "Failed to send saved scrobbles: {e}"
+                                                );
+                                            }
+                                        }
+                                        sent
+                                    }
                                     Empty::Failed { error } => tx.send(Action::FromQuery {
                                         dest: query.dest,
                                         ticket: query.ticket,
@@ -268,8 +490,218 @@ impl QueryWorker {
                     }
                 });
             }
+            HighLevelQuery::ListAlbums(params) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let albums = match c
+                        .get_album_list2(&params.list_type, params.size, params.offset)
+                        .await
+                    {
+                        Ok(r) => match r {
+                            GetAlbumList2::Ok { album_list2 } => Ok(album_list2.album),
+                            GetAlbumList2::Failed { error } => Err(error.to_string()),
+                        },
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::GetAlbumList(
+                            GetAlbumListResponse { params, albums },
+                        )),
+                    });
+                });
+            }
+            HighLevelQuery::SelectAlbum(params) | HighLevelQuery::AddAlbumToQueue(params) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.get_album(params.id.clone()).await {
+                        Ok(r) => match r {
+                            GetAlbum::Ok { album } => GetAlbumResponse::Success(album),
+                            GetAlbum::Failed { error } => GetAlbumResponse::Failure {
+                                id: params.id,
+                                name: params.name,
+                                msg: error.to_string(),
+                            },
+                        },
+                        Err(e) => GetAlbumResponse::Failure {
+                            id: params.id,
+                            name: params.name,
+                            msg: e.to_string(),
+                        },
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::GetAlbum(res)),
+                    });
+                });
+            }
+            HighLevelQuery::ListArtists => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.get_artists().await {
+                        Ok(r) => match r {
+                            GetArtists::Ok { artists } => Ok(artists.index),
+                            GetArtists::Failed { error } => Err(error.to_string()),
+                        },
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::GetArtists(res)),
+                    });
+                });
+            }
+            HighLevelQuery::ListGenres => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.get_genres().await {
+                        Ok(GetGenres::Ok { genres }) => Ok(genres.genre),
+                        Ok(GetGenres::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::GetGenres(res)),
+                    });
+                });
+            }
+            HighLevelQuery::ListMusicFolders => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.get_music_folders().await {
+                        Ok(GetMusicFolders::Ok { music_folders }) => Ok(music_folders.music_folder),
+                        Ok(GetMusicFolders::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::GetMusicFolders(res)),
+                    });
+                });
+            }
+            HighLevelQuery::RandomMix(params) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c
+                        .get_random_songs(
+                            params.count,
+                            params.genre,
+                            params.from_year,
+                            params.to_year,
+                            params.folder,
+                        )
+                        .await
+                    {
+                        Ok(GetRandomSongs::Ok { random_songs }) => {
+                            Ok((random_songs.song, params.location))
+                        }
+                        Ok(GetRandomSongs::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::RandomMix(res)),
+                    });
+                });
+            }
+            HighLevelQuery::SelectArtist(params) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.get_artist(params.id.clone()).await {
+                        Ok(r) => match r {
+                            GetArtist::Ok { artist } => GetArtistResponse::Success(artist),
+                            GetArtist::Failed { error } => GetArtistResponse::Failure {
+                                id: params.id,
+                                name: params.name,
+                                msg: error.to_string(),
+                            },
+                        },
+                        Err(e) => GetArtistResponse::Failure {
+                            id: params.id,
+                            name: params.name,
+                            msg: e.to_string(),
+                        },
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::GetArtist(res)),
+                    });
+                });
+            }
+            HighLevelQuery::Search(params) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c
+                        .search3(
+                            params.query,
+                            params.artist_count,
+                            params.album_count,
+                            params.song_count,
+                        )
+                        .await
+                    {
+                        Ok(r) => match r {
+                            Search3::Ok { search_result3 } => Ok(search_result3),
+                            Search3::Failed { error } => Err(error.to_string()),
+                        },
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::Search(res)),
+                    });
+                });
+            }
+            HighLevelQuery::SavePlayQueue(params) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c
+                        .save_play_queue(params.ids, params.current, params.position)
+                        .await
+                    {
+                        Ok(Empty::Ok) => Ok(()),
+                        Ok(Empty::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::SavePlayQueue(res)),
+                    });
+                });
+            }
+            HighLevelQuery::GetPlayQueue => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.get_play_queue().await {
+                        Ok(r) => match r {
+                            GetPlayQueue::Ok { play_queue } => Ok(play_queue),
+                            GetPlayQueue::Failed { error } => Err(error.to_string()),
+                        },
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::GetPlayQueue(res)),
+                    });
+                });
+            }
+            HighLevelQuery::Scrobble(params) => self.scrobble(query.dest, query.ticket, params),
+            HighLevelQuery::Radio(params) => self.radio(query.dest, query.ticket, params),
             HighLevelQuery::PlayMusicFromURL(media) => {
-                self.play_from_url(media);
+                self.play_from_url(media, false);
+            }
+            HighLevelQuery::PreloadFromURL(media) => {
+                self.play_from_url(media, true);
             }
             HighLevelQuery::GetLyrics(params) => {
                 self.get_lyrics(query.dest, query.ticket, params);
@@ -277,6 +709,125 @@ impl QueryWorker {
             HighLevelQuery::GetCover(cover_id) => {
                 self.get_cover(query.dest, query.ticket, cover_id);
             }
+            HighLevelQuery::Download(params) => {
+                let token = self.register_download(query.ticket);
+                let (tx, c) = self.prepare_async();
+                let target = self.download.clone();
+                let (dest, ticket) = (query.dest, query.ticket);
+                tokio::spawn(async move {
+                    let _guard = token.clone().drop_guard();
+                    let res =
+                        Self::download(&tx, &c, &dest, ticket, params.items, &target, &token).await;
+                    Self::finish_download(&tx, dest, ticket, res);
+                });
+            }
+            HighLevelQuery::DownloadPlaylist(params) => {
+                let token = self.register_download(query.ticket);
+                let (tx, c) = self.prepare_async();
+                let target = self.download.clone();
+                let (dest, ticket) = (query.dest, query.ticket);
+                tokio::spawn(async move {
+                    let _guard = token.clone().drop_guard();
+                    let res = match playlistfile::fetch_items(&c, params.id).await {
+                        Ok(items) => {
+                            Self::download(&tx, &c, &dest, ticket, items, &target, &token).await
+                        }
+                        Err(e) => Err(Some(e)),
+                    };
+                    Self::finish_download(&tx, dest, ticket, res);
+                });
+            }
+            HighLevelQuery::SetStreamProfile(profile) => self.stream_profile = profile,
+            HighLevelQuery::SetOffline(offline) => self.offline = offline,
+            HighLevelQuery::CancelTask(ticket) => {
+                if let Some(token) = self.downloads.remove(&ticket) {
+                    token.cancel();
+                }
+            }
+            HighLevelQuery::CreatePlaylist(name) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.create_playlist(name).await {
+                        Ok(Empty::Ok) => Ok(()),
+                        Ok(Empty::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::CreatePlaylist(res)),
+                    });
+                });
+            }
+            HighLevelQuery::ExportPlaylist(params) => {
+                let (tx, c) = self.prepare_async();
+                let config = self.playlist_file.clone();
+                tokio::spawn(async move {
+                    let items = match params.source {
+                        ExportSource::Playlist(id) => playlistfile::fetch_items(&c, id).await,
+                        ExportSource::Items(items) => Ok(items),
+                    };
+                    let path = playlistfile::export_path(&config.dir, &params.name, config.format);
+                    let res = match items {
+                        Ok(items) => {
+                            playlistfile::export(&path, &params.name, &items, config.format)
+                                .await
+                                .map(|_| path)
+                        }
+                        Err(e) => Err(e),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::ExportPlaylist(res)),
+                    });
+                });
+            }
+            HighLevelQuery::ImportPlaylist(path) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = playlistfile::import(&c, &path, None).await;
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::ImportPlaylist(res)),
+                    });
+                });
+            }
+            HighLevelQuery::SavePlaylist(params) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let (id, name) = match params.target {
+                        SaveTarget::New(name) => (None, Some(name)),
+                        SaveTarget::Existing { id, name: _ } => (Some(id), None),
+                    };
+                    let res = match c.save_playlist(id, name, params.items).await {
+                        Ok(Empty::Ok) => Ok(()),
+                        Ok(Empty::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::SavePlaylist(res)),
+                    });
+                });
+            }
+            HighLevelQuery::DeletePlaylist(params) => {
+                let (tx, c) = self.prepare_async();
+                tokio::spawn(async move {
+                    let res = match c.delete_playlist(params.id).await {
+                        Ok(Empty::Ok) => Ok(()),
+                        Ok(Empty::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = tx.send(Action::FromQuery {
+                        dest: query.dest,
+                        ticket: query.ticket,
+                        res: QueryStatus::Finished(ResponseType::DeletePlaylist(res)),
+                    });
+                });
+            }
             HighLevelQuery::UpdatePlaylist(update_playlist_params) => {
                 let (tx, c) = self.prepare_async();
                 tokio::spawn(async move {
@@ -294,7 +845,8 @@ impl QueryWorker {
                         dest: query.dest,
                         ticket: query.ticket,
                         res: QueryStatus::Finished(ResponseType::UpdatePlaylist(match res {
-                            Ok(_) => Ok(()),
+                            Ok(Empty::Ok) => Ok(()),
+                            Ok(Empty::Failed { error }) => Err(error.to_string()),
                             Err(e) => Err(e.to_string()),
                         })),
                     });
@@ -304,6 +856,148 @@ impl QueryWorker {
         Ok(())
     }
 
+    /// Reports the listen to the external scrobbler. Submissions are stored on disk first, and
+    /// removed only once they are accepted, so that listens are not lost while the scrobbler is
+    /// unreachable.
+    fn submit_listen(&self, params: &ScrobbleParams) {
+        let (Some(lb), Some(store)) = (self.listenbrainz.clone(), self.store.clone()) else {
+            return;
+        };
+        let listen = Listen::new(&params.media, Some(params.time / 1000));
+        let submission = params.submission;
+        tokio::spawn(async move {
+            let store = store.lock().await;
+            if submission {
+                if let Err(e) = store.push_listen(&listen) {
+                    tracing::error!("Failed to save listen: {e}");
+                }
+            } else if let Err(e) = lb.now_playing(&listen).await {
+                tracing::warn!("Failed to send now playing to ListenBrainz: {e}");
+                return;
+            }
+            let listens = match store.get_listens() {
+                Ok(l) => l,
+                Err(e) => {
+                    tracing::error!("Failed to load saved listens: {e}");
+                    return;
+                }
+            };
+            for (id, listen) in listens {
+                if let Err(e) = lb.submit(&listen).await {
+                    tracing::warn!("Failed to submit listen to ListenBrainz: {e}");
+                    return;
+                }
+                if let Err(e) = store.remove_listen(id) {
+                    tracing::error!("Failed to remove submitted listen: {e}");
+                    return;
+                }
+            }
+        });
+    }
+
+    fn scrobble(&mut self, dest: Vec<CompID>, ticket: usize, params: ScrobbleParams) {
+        self.submit_listen(&params);
+        let tx = self.action_tx.clone();
+        if !self.scrobble {
+            let _ = tx.send(Action::FromQuery {
+                dest,
+                ticket,
+                res: QueryStatus::Finished(ResponseType::Scrobble(Ok(()))),
+            });
+            return;
+        }
+        let (tx, c) = self.prepare_async();
+        let store = self.store.clone();
+        tokio::spawn(async move {
+            let item = (params.media.id, params.time);
+            let res = match (store, params.submission) {
+                // Submissions are saved first, and removed only once the server accepts them
+                (Some(store), true) => {
+                    let mut store = store.lock().await;
+                    match store.push_scrobble(&item.0, item.1) {
+                        Ok(()) => flush_scrobbles(&c, &mut store).await,
+                        Err(e) => {
+                            tracing::error!("Failed to save scrobble: {e}");
+                            scrobble_result(c.scrobble(&[item], true).await)
+                        }
+                    }
+                }
+                (None, submission) => scrobble_result(c.scrobble(&[item], submission).await),
+                (Some(store), false) => {
+                    let res = scrobble_result(c.scrobble(&[item], false).await);
+                    // The server is back, so the submissions that failed earlier are sent again
+                    if res.is_ok() {
+                        if let Err(e) = flush_scrobbles(&c, &mut *store.lock().await).await {
+                            tracing::warn!("Failed to send saved scrobbles: {e}");
+                        }
+                    }
+                    res
+                }
+            };
+            if let Err(e) = &res {
+                tracing::warn!("Scrobbling failed: {e}");
+            }
+            let _ = tx.send(Action::FromQuery {
+                dest,
+                ticket,
+                res: QueryStatus::Finished(ResponseType::Scrobble(res)),
+            });
+        });
+    }
+
+    fn radio(&mut self, dest: Vec<CompID>, ticket: usize, params: RadioParams) {
+        let (tx, c) = self.prepare_async();
+        tokio::spawn(async move {
+            let res = match params {
+                RadioParams::Similar { artists, count } => {
+                    let mut songs = vec![];
+                    let mut error = None;
+                    for id in artists {
+                        match c.get_similar_songs2(id, count).await {
+                            Ok(GetSimilarSongs2::Ok { similar_songs2 }) => {
+                                songs.extend(similar_songs2.song)
+                            }
+                            Ok(GetSimilarSongs2::Failed { error: e }) => {
+                                error = Some(e.to_string())
+                            }
+                            Err(e) => error = Some(e.to_string()),
+                        }
+                    }
+                    // Failing to find media similar to some of the artists is fine
+                    match error {
+                        Some(e) if songs.is_empty() => Err(e),
+                        _ => Ok(songs),
+                    }
+                }
+                RadioParams::TopSongs { artist, count } => {
+                    match c.get_top_songs(artist, count).await {
+                        Ok(GetTopSongs::Ok { top_songs }) => Ok(top_songs.song),
+                        Ok(GetTopSongs::Failed { error }) => Err(error.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    }
+                }
+                RadioParams::Random {
+                    count,
+                    genre,
+                    from_year,
+                    to_year,
+                } => match c
+                    .get_random_songs(count, genre, from_year, to_year, None)
+                    .await
+                {
+                    Ok(GetRandomSongs::Ok { random_songs }) => Ok(random_songs.song),
+                    Ok(GetRandomSongs::Failed { error }) => Err(error.to_string()),
+                    Err(e) => Err(e.to_string()),
+                },
+            };
+            let _ = tx.send(Action::FromQuery {
+                dest,
+                ticket,
+                res: QueryStatus::Finished(ResponseType::Radio(res)),
+            });
+        });
+    }
+
     fn get_lyrics(&mut self, dest: Vec<CompID>, ticket: usize, params: GetLyricsParams) {
         let c = self.lyrics.clone();
         let tx = self.action_tx.clone();
@@ -363,17 +1057,122 @@ impl QueryWorker {
         });
     }
 
-    fn play_from_url(&mut self, media: Media) {
+    /// Creates a token that stops the download with the given ticket once cancelled. Tokens of the
+    /// downloads that have already ended are removed here as well.
+    fn register_download(&mut self, ticket: usize) -> CancellationToken {
+        self.downloads.retain(|_, t| !t.is_cancelled());
+        let token = CancellationToken::new();
+        self.downloads.insert(ticket, token.clone());
+        token
+    }
+
+    fn finish_download(
+        tx: &UnboundedSender<Action>,
+        dest: Vec<CompID>,
+        ticket: usize,
+        res: Result<usize, Option<String>>,
+    ) {
+        let res = match res {
+            Ok(count) => QueryStatus::Finished(ResponseType::Download(Ok(count))),
+            // Download has been cancelled by the user
+            Err(None) => QueryStatus::Aborted(true),
+            Err(Some(e)) => QueryStatus::Finished(ResponseType::Download(Err(e))),
+        };
+        let _ = tx.send(Action::FromQuery { dest, ticket, res });
+    }
+
+    /// Saves the original files of the given media, and returns the number of files downloaded.
+    /// Media that already exists in the download directory is skipped. Returns `Err(None)` if the
+    /// download has been cancelled, in which case the file that is being written is removed.
+    async fn download(
+        tx: &UnboundedSender<Action>,
+        c: &OSClient,
+        dest: &[CompID],
+        ticket: usize,
+        items: Vec<Media>,
+        (dir, template): &(PathBuf, String),
+        token: &CancellationToken,
+    ) -> Result<usize, Option<String>> {
+        let total = items.iter().map(|m| m.size).sum::<Option<u64>>();
+        let mut done = 0;
+        let mut count = 0;
+        let mut last = Instant::now();
+        for media in items.into_iter().filter(|m| !m.is_dir) {
+            let path = file_path(dir, template, &media);
+            if path.exists() {
+                done += media.size.unwrap_or(0);
+                continue;
+            }
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| Some(e.to_string()))?;
+            }
+            let mut res = match c.download(media.id.clone()).await {
+                Ok(Ok(res)) => res,
+                Ok(Err(AlwaysError { error })) => {
+                    return Err(Some(format!("{}: {}", media.title, error)))
+                }
+                Err(e) => return Err(Some(format!("{}: {}", media.title, e))),
+            };
+            let mut part = path.clone().into_os_string();
+            part.push(".part");
+            let part = PathBuf::from(part);
+            let mut file = tokio::fs::File::create(&part)
+                .await
+                .map_err(|e| Some(e.to_string()))?;
+            let written: Result<(), Option<String>> = async {
+                loop {
+                    let chunk = tokio::select! {
+                        _ = token.cancelled() => return Err(None),
+                        chunk = res.chunk() => chunk.map_err(|e| Some(e.to_string()))?,
+                    };
+                    let Some(chunk) = chunk else {
+                        break;
+                    };
+                    file.write_all(&chunk)
+                        .await
+                        .map_err(|e| Some(e.to_string()))?;
+                    done += chunk.len() as u64;
+                    if last.elapsed() >= PROGRESS_INTERVAL {
+                        last = Instant::now();
+                        let _ = tx.send(Action::FromQuery {
+                            dest: dest.to_vec(),
+                            ticket,
+                            res: QueryStatus::Progress { done, total },
+                        });
+                    }
+                }
+                file.flush().await.map_err(|e| Some(e.to_string()))
+            }
+            .await;
+            drop(file);
+            if let Err(e) = written {
+                let _ = tokio::fs::remove_file(&part).await;
+                return Err(e);
+            }
+            tokio::fs::rename(&part, &path)
+                .await
+                .map_err(|e| Some(e.to_string()))?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn play_from_url(&mut self, media: Media, preload: bool) {
         match &self.client {
             Some(c) => {
                 let id = media.id.clone();
-                let url = c.stream_link(id).to_string();
-                let _ = self
-                    .action_tx
-                    .send(Action::ToPlayer(ToPlayerWorker::PlayURL {
-                        music: media,
-                        url,
-                    }));
+                let profile = &self.stream_profile;
+                let url = c
+                    .stream_link(id, profile.format.as_deref(), profile.max_bit_rate)
+                    .to_string();
+                let msg = if preload {
+                    ToPlayerWorker::PreloadURL { music: media, url }
+                } else {
+                    ToPlayerWorker::PlayURL { music: media, url }
+                };
+                let _ = self.action_tx.send(Action::ToPlayer(msg));
             }
             None => tracing::error!("Invalid state: Tried querying, but client does not exist!"),
         };
@@ -391,6 +1190,50 @@ impl QueryWorker {
             action_tx: sender,
             should_quit: false,
             cache: Arc::new(Mutex::new(Cache::default())),
+            scrobble: config.features.scrobble.enable,
+            store: Self::open_store(&config),
+            listenbrainz: config.features.listenbrainz.enable.then(|| {
+                Arc::new(ListenBrainz::new(
+                    config.features.listenbrainz.url.clone(),
+                    config.features.listenbrainz.token.clone(),
+                ))
+            }),
+            download: (
+                config.features.download.dir.clone(),
+                config.features.download.template.clone(),
+            ),
+            downloads: HashMap::new(),
+            stream_profile: config
+                .features
+                .streaming
+                .profiles
+                .get(config.features.streaming.initial())
+                .cloned()
+                .unwrap_or_default(),
+            playlist_file: config.features.playlist_file.clone(),
+            offline: config.features.cache.offline,
+        }
+    }
+
+    /// Opens the store for the backlog of the scrobblers, if any of them is enabled
+    fn open_store(config: &Config) -> Option<Arc<Mutex<DataStore>>> {
+        if !config.features.scrobble.enable && !config.features.listenbrainz.enable {
+            return None;
+        }
+        let store = if config.config.persist_data {
+            DataStore::open(&config.config.data_dir).or_else(|e| {
+                tracing::error!("Failed to open data store, scrobbles will not be kept: {e}");
+                DataStore::open_in_memory()
+            })
+        } else {
+            DataStore::open_in_memory()
+        };
+        match store {
+            Ok(store) => Some(Arc::new(Mutex::new(store))),
+            Err(e) => {
+                tracing::error!("Failed to set up the scrobble backlog: {e}");
+                None
+            }
         }
     }
     pub fn get_tx(&self) -> UnboundedSender<ToQueryWorker> {
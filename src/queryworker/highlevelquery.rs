@@ -1,12 +1,19 @@
+use std::path::PathBuf;
+
 use crate::{
     compid::CompID,
+    config::streamingconfig::StreamProfile,
     lyricsclient::getlyrics::GetLyricsParams,
     osclient::{
         response::getplaylist::Media,
-        types::{CoverID, MediaID},
+        types::{AlbumID, ArtistID, CoverID, MediaID},
     },
     queryworker::query::{
-        getplaylist::GetPlaylistParams, setcredential::Credential,
+        download::DownloadParams, exportplaylist::ExportParams, getalbum::GetAlbumParams,
+        getalbumlist::GetAlbumListParams, getartist::GetArtistParams,
+        getplaylist::GetPlaylistParams, radio::RadioParams, randommix::RandomMixParams,
+        saveplaylist::SavePlaylistParams, saveplayqueue::SavePlayQueueParams,
+        scrobble::ScrobbleParams, search::SearchParams, setcredential::Credential,
         updateplaylist::UpdatePlaylistParams,
     },
 };
@@ -23,6 +30,8 @@ use crate::{
 pub enum HighLevelQuery {
     /// Given [`Media`] object, get its URL to play it with [`PlayerWorker`]
     PlayMusicFromURL(Media),
+    /// Same as [`HighLevelQuery::PlayMusicFromURL`], but the media is played after the current one
+    PreloadFromURL(Media),
     /// Given a playlist ID, fetch the content to display the musics in a playlist
     SelectPlaylist(GetPlaylistParams),
     /// Fetches the content of a playlist, and add all or part of them to the queue
@@ -31,41 +40,167 @@ pub enum HighLevelQuery {
     ListPlaylists,
     /// Fetches the list of playlists for the playlist selection popup
     ListPlaylistsPopup(bool),
+    /// Fetches a page of albums for the album browser
+    ListAlbums(GetAlbumListParams),
+    /// Given an album ID, fetch the content to display the musics in an album
+    SelectAlbum(GetAlbumParams),
+    /// Fetches the content of an album, and add all of them to the queue
+    AddAlbumToQueue(GetAlbumParams),
+    /// Fetches the artist index for the artist browser
+    ListArtists,
+    /// Given an artist ID, fetch the albums of that artist
+    SelectArtist(GetArtistParams),
+    /// Search artists, albums and songs across the entire server
+    Search(SearchParams),
+    /// Uploads the current queue so that it can be resumed from other clients
+    SavePlayQueue(SavePlayQueueParams),
+    /// Fetches the queue saved by any client, and replaces the current queue with it
+    GetPlayQueue,
+    /// Reports the media being played, or the media that has been played
+    Scrobble(ScrobbleParams),
     /// Stars/unstars a music
     SetStar {
         media: MediaID,
         star: bool,
     },
+    /// Rates a music from 1 to 5, or removes the rating if 0
+    SetRating {
+        media: MediaID,
+        rating: u8,
+        /// Rating before the change, which is restored if the server rejects it
+        previous: u8,
+    },
+    SetAlbumStar {
+        album: AlbumID,
+        star: bool,
+    },
+    SetArtistStar {
+        artist: ArtistID,
+        star: bool,
+    },
+    /// Fetches all starred artists, albums and songs
+    ListStarred,
+    /// Fetches every album of an artist, and adds all of them to the queue
+    AddArtistToQueue(GetArtistParams),
     /// Fetch lyrics from lrclib.net
     GetLyrics(GetLyricsParams),
     GetCover(CoverID),
     /// Sets the credential for this client, and sends a ping to ensure it is valid
     Login(Credential),
     UpdatePlaylist(UpdatePlaylistParams),
+    /// Creates an empty playlist with the given name
+    CreatePlaylist(String),
+    DeletePlaylist(GetPlaylistParams),
+    /// Creates a playlist with the given media, or replaces everything in an existing playlist
+    SavePlaylist(SavePlaylistParams),
+    /// Finds more media to add to the end of the queue in radio mode
+    Radio(RadioParams),
+    /// Fetches the genres for the random mix popup
+    ListGenres,
+    /// Fetches the music folders for the random mix popup
+    ListMusicFolders,
+    /// Picks random media, and adds them to the queue
+    RandomMix(RandomMixParams),
+    /// Writes the media to a playlist file in the directory set in the config
+    ExportPlaylist(ExportParams),
+    /// Reads a playlist file, and creates a playlist with the media found on the server
+    ImportPlaylist(PathBuf),
+    /// Saves the original files of the media to the download directory
+    Download(DownloadParams),
+    /// Fetches the content of a playlist, and downloads all of them
+    DownloadPlaylist(GetPlaylistParams),
+    /// Stops the task with the given ticket, if it can be stopped
+    CancelTask(usize),
+    /// Changes how the media is transcoded from the next media onwards
+    SetStreamProfile(StreamProfile),
+    /// Switches to or from offline mode, in which the server is not browsed
+    SetOffline(bool),
 }
 
 impl HighLevelQuery {
     pub fn get_dest(&self) -> Vec<CompID> {
         match self {
             HighLevelQuery::PlayMusicFromURL(_) => vec![CompID::NowPlaying],
-            HighLevelQuery::SelectPlaylist(_) => {
+            HighLevelQuery::SelectPlaylist(_) | HighLevelQuery::SelectAlbum(_) => {
                 vec![CompID::PlaylistQueue]
             }
+            HighLevelQuery::ListAlbums(_) => vec![CompID::AlbumList],
+            // Albums can be added to the queue from every list that shows them
+            HighLevelQuery::AddAlbumToQueue(_) => {
+                vec![
+                    CompID::AlbumList,
+                    CompID::ArtistList,
+                    CompID::GlobalSearch,
+                    CompID::Starred,
+                ]
+            }
+            HighLevelQuery::ListArtists | HighLevelQuery::SelectArtist(_) => {
+                vec![CompID::ArtistList]
+            }
             HighLevelQuery::AddPlaylistToQueue(_) | HighLevelQuery::ListPlaylists => {
                 vec![CompID::PlaylistList]
             }
             HighLevelQuery::Login(_) => vec![CompID::Home],
-            HighLevelQuery::SetStar { media: _, star: _ } => {
-                vec![CompID::PlaylistQueue, CompID::PlayQueue]
+            HighLevelQuery::SetStar { media: _, star: _ } | HighLevelQuery::SetRating { .. } => {
+                vec![
+                    CompID::PlaylistQueue,
+                    CompID::PlayQueue,
+                    CompID::GlobalSearch,
+                    CompID::Starred,
+                ]
             }
+            HighLevelQuery::SetAlbumStar { .. }
+            | HighLevelQuery::SetArtistStar { .. }
+            | HighLevelQuery::AddArtistToQueue(_) => vec![CompID::GlobalSearch, CompID::Starred],
+            HighLevelQuery::ListStarred => vec![CompID::Starred],
+            HighLevelQuery::Search(_) => vec![CompID::GlobalSearch],
+            HighLevelQuery::SavePlayQueue(_)
+            | HighLevelQuery::GetPlayQueue
+            | HighLevelQuery::Radio(_)
+            | HighLevelQuery::PreloadFromURL(_) => {
+                vec![CompID::PlayQueue]
+            }
+            HighLevelQuery::Scrobble(_) => vec![CompID::MainScreen],
             HighLevelQuery::GetLyrics(_) => vec![CompID::Lyrics],
             HighLevelQuery::GetCover(_) => vec![CompID::ImageComp],
             HighLevelQuery::ListPlaylistsPopup(_) => vec![CompID::MainScreen],
-            HighLevelQuery::UpdatePlaylist(_) => vec![CompID::MainScreen],
+            // Playlist queue undoes the changes made to the playlist if the update fails
+            HighLevelQuery::UpdatePlaylist(_) => vec![CompID::MainScreen, CompID::PlaylistQueue],
+            HighLevelQuery::CreatePlaylist(_)
+            | HighLevelQuery::DeletePlaylist(_)
+            | HighLevelQuery::SavePlaylist(_) => vec![CompID::MainScreen],
+            HighLevelQuery::ExportPlaylist(_) | HighLevelQuery::ImportPlaylist(_) => {
+                vec![CompID::MainScreen]
+            }
+            HighLevelQuery::ListGenres
+            | HighLevelQuery::ListMusicFolders
+            | HighLevelQuery::RandomMix(_) => vec![CompID::MainScreen],
+            HighLevelQuery::Download(_) | HighLevelQuery::DownloadPlaylist(_) => {
+                vec![CompID::MainScreen]
+            }
+            HighLevelQuery::CancelTask(_)
+            | HighLevelQuery::SetStreamProfile(_)
+            | HighLevelQuery::SetOffline(_) => vec![],
         }
     }
     pub fn show_task(&self) -> bool {
-        !matches!(self, HighLevelQuery::PlayMusicFromURL(_))
+        !matches!(
+            self,
+            HighLevelQuery::PlayMusicFromURL(_)
+                | HighLevelQuery::PreloadFromURL(_)
+                | HighLevelQuery::SavePlayQueue(_)
+                | HighLevelQuery::Scrobble(_)
+                | HighLevelQuery::CancelTask(_)
+                | HighLevelQuery::SetStreamProfile(_)
+                | HighLevelQuery::SetOffline(_)
+        )
+    }
+    /// True if the task can be stopped by the user with [`HighLevelQuery::CancelTask`]
+    pub fn can_cancel(&self) -> bool {
+        matches!(
+            self,
+            HighLevelQuery::Download(_) | HighLevelQuery::DownloadPlaylist(_)
+        )
     }
 }
 
@@ -73,15 +208,54 @@ impl ToString for HighLevelQuery {
     fn to_string(&self) -> String {
         match self {
             HighLevelQuery::PlayMusicFromURL(_) => "Loading media from URL",
+            HighLevelQuery::PreloadFromURL(_) => "Loading next media from URL",
             HighLevelQuery::SelectPlaylist(_) => "Fetching playlist content",
             HighLevelQuery::AddPlaylistToQueue(_) => "Adding playlist to the queue",
             HighLevelQuery::ListPlaylists => "Fetching all playlists",
             HighLevelQuery::SetStar { media: _, star: _ } => "Toggle favourite status of a music",
+            HighLevelQuery::SetRating { .. } => "Rate a music",
+            HighLevelQuery::SetAlbumStar { .. } => "Toggle favourite status of an album",
+            HighLevelQuery::SetArtistStar { .. } => "Toggle favourite status of an artist",
+            HighLevelQuery::ListStarred => "Fetching starred items",
+            HighLevelQuery::AddArtistToQueue(params) => {
+                return format!("Adding every album of {} to the queue", params.name)
+            }
             HighLevelQuery::GetLyrics(_) => "Fetching lyrics",
             HighLevelQuery::GetCover(_) => "Fetching cover image",
             HighLevelQuery::Login(_) => "Set login credentials and check validitiy",
             HighLevelQuery::UpdatePlaylist(_) => "Update playlist",
+            HighLevelQuery::CreatePlaylist(name) => return format!("Creating playlist {}", name),
+            HighLevelQuery::DeletePlaylist(params) => {
+                return format!("Deleting playlist {}", params.name)
+            }
+            HighLevelQuery::ExportPlaylist(params) => return format!("Exporting {}", params.name),
+            HighLevelQuery::ImportPlaylist(path) => return format!("Importing {}", path.display()),
+            HighLevelQuery::SavePlaylist(params) => {
+                return format!("Saving {} items to {}", params.items.len(), params.name())
+            }
             HighLevelQuery::ListPlaylistsPopup(_) => "Fetching playlists for the popup",
+            HighLevelQuery::ListAlbums(_) => "Fetching albums",
+            HighLevelQuery::SelectAlbum(_) => "Fetching album content",
+            HighLevelQuery::AddAlbumToQueue(_) => "Adding album to the queue",
+            HighLevelQuery::ListArtists => "Fetching all artists",
+            HighLevelQuery::SelectArtist(_) => "Fetching albums of an artist",
+            HighLevelQuery::Search(_) => "Searching the server",
+            HighLevelQuery::SavePlayQueue(_) => "Saving the queue to the server",
+            HighLevelQuery::GetPlayQueue => "Fetching the queue saved on the server",
+            HighLevelQuery::Radio(_) => "Finding more media for the radio",
+            HighLevelQuery::ListGenres => "Fetching genres",
+            HighLevelQuery::ListMusicFolders => "Fetching music folders",
+            HighLevelQuery::RandomMix(params) => {
+                return format!("Picking {} random media", params.count)
+            }
+            HighLevelQuery::Scrobble(_) => "Reporting played media",
+            HighLevelQuery::Download(params) => return format!("Downloading {}", params.name),
+            HighLevelQuery::DownloadPlaylist(params) => {
+                return format!("Downloading playlist {}", params.name)
+            }
+            HighLevelQuery::CancelTask(_) => "Cancelling a task",
+            HighLevelQuery::SetStreamProfile(_) => "Changing the streaming profile",
+            HighLevelQuery::SetOffline(_) => "Changing the offline mode",
         }
         .to_string()
     }
@@ -1,15 +1,41 @@
+pub mod download;
+pub mod exportplaylist;
+pub mod getalbum;
+pub mod getalbumlist;
+pub mod getartist;
 pub mod getplaylist;
+pub mod radio;
+pub mod randommix;
+pub mod saveplaylist;
+pub mod saveplayqueue;
+pub mod scrobble;
+pub mod search;
 pub mod setcredential;
+pub mod setrating;
 pub mod updateplaylist;
 
+use std::path::PathBuf;
+
 use image::DynamicImage;
 
 use crate::{
     compid::CompID,
     lyricsclient::getlyrics::GetLyricsResponse,
-    osclient::response::getplaylists::SimplePlaylist,
+    osclient::response::{
+        getartists::ArtistIndex, getgenres::Genre, getmusicfolders::MusicFolder,
+        getplaylist::Media, getplaylists::SimplePlaylist, getplayqueue::RemotePlayQueue,
+        getstarred2::Starred2, search3::SearchResult3,
+    },
+    playerworker::player::QueueLocation,
+    playlistfile::ImportResult,
     queryworker::{
-        highlevelquery::HighLevelQuery, query::getplaylist::GetPlaylistResponse, QueryWorker,
+        highlevelquery::HighLevelQuery,
+        query::{
+            getalbum::GetAlbumResponse, getalbumlist::GetAlbumListResponse,
+            getartist::GetArtistResponse, getplaylist::GetPlaylistResponse,
+            setrating::SetRatingResponse,
+        },
+        QueryWorker,
     },
 };
 
@@ -40,12 +66,39 @@ impl ToQueryWorker {
 pub enum ResponseType {
     // Responses from the queries
     Star(Result<(), String>),
+    SetRating(SetRatingResponse),
     UpdatePlaylist(Result<(), String>),
+    CreatePlaylist(Result<(), String>),
+    DeletePlaylist(Result<(), String>),
+    SavePlaylist(Result<(), String>),
     GetPlaylists(Result<Vec<SimplePlaylist>, String>),
     GetPlaylist(GetPlaylistResponse),
+    GetAlbumList(GetAlbumListResponse),
+    GetAlbum(GetAlbumResponse),
+    GetArtists(Result<Vec<ArtistIndex>, String>),
+    GetArtist(GetArtistResponse),
+    Search(Result<SearchResult3, String>),
+    SavePlayQueue(Result<(), String>),
+    Scrobble(Result<(), String>),
+    /// None if the server does not have a saved queue
+    GetPlayQueue(Result<Option<RemotePlayQueue>, String>),
     GetLyrics(Result<Option<GetLyricsResponse>, String>),
     GetCover(Result<DynamicImage, String>),
     Login(Result<(), String>),
+    /// Number of files downloaded
+    Download(Result<usize, String>),
+    /// Path of the exported file
+    ExportPlaylist(Result<PathBuf, String>),
+    ImportPlaylist(Result<ImportResult, String>),
+    /// Media found for radio mode, which may include ones that have been played recently
+    Radio(Result<Vec<Media>, String>),
+    GetGenres(Result<Vec<Genre>, String>),
+    GetMusicFolders(Result<Vec<MusicFolder>, String>),
+    GetStarred(Result<Starred2, String>),
+    /// Media in every album of an artist
+    GetArtistSongs(Result<Vec<Media>, String>),
+    /// Media to add to the queue, and where it should be added
+    RandomMix(Result<(Vec<Media>, QueueLocation), String>),
 }
 
 #[derive(Debug, Clone)]
@@ -57,4 +110,6 @@ pub enum QueryStatus {
     Aborted(bool),
     /// Query has been finished with external resources
     Finished(ResponseType),
+    /// Number of bytes the query has received so far, and the number of bytes expected if known
+    Progress { done: u64, total: Option<u64> },
 }
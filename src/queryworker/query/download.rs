@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+use crate::osclient::response::getplaylist::Media;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadParams {
+    /// Shown in the task list
+    pub name: String,
+    pub items: Vec<Media>,
+}
+
+/// Replaces the characters that cannot be used in a file name
+pub fn sanitise(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+/// Path of the downloaded file, built from the template in the config
+pub fn file_path(dir: &Path, template: &str, media: &Media) -> PathBuf {
+    let fields = [
+        (
+            "{artist}",
+            media.artist.clone().unwrap_or("Unknown Artist".to_string()),
+        ),
+        (
+            "{album}",
+            media.album.clone().unwrap_or("Unknown Album".to_string()),
+        ),
+        (
+            "{track}",
+            media.track.map(|t| format!("{t:02}")).unwrap_or_default(),
+        ),
+        ("{title}", media.title.clone()),
+        (
+            "{suffix}",
+            media.suffix.clone().unwrap_or("bin".to_string()),
+        ),
+    ];
+    // Each component is filled in separately so that the fields cannot add extra directories
+    template
+        .split('/')
+        .map(|part| {
+            let mut part = part.to_string();
+            for (key, value) in &fields {
+                part = part.replace(key, &sanitise(value));
+            }
+            sanitise(&part)
+        })
+        .filter(|part| !part.is_empty())
+        .fold(dir.to_path_buf(), |path, part| path.join(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_file_path() {
+        let media: Media = serde_json::from_value(json!({
+            "id": "1",
+            "isDir": false,
+            "title": "What/Ever?",
+            "artist": "AC/DC",
+            "track": 3,
+            "suffix": "flac",
+        }))
+        .unwrap();
+        let path = file_path(
+            Path::new("/music"),
+            "{artist}/{album}/{track} - {title}.{suffix}",
+            &media,
+        );
+        assert_eq!(
+            path,
+            PathBuf::from("/music/AC_DC/Unknown Album/03 - What_Ever_.flac")
+        );
+    }
+}
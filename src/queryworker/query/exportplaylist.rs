@@ -0,0 +1,15 @@
+use crate::osclient::{response::getplaylist::Media, types::PlaylistID};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportSource {
+    /// Fetch the content of the playlist from the server first
+    Playlist(PlaylistID),
+    Items(Vec<Media>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportParams {
+    /// Name of the playlist, which is also used as the name of the file
+    pub name: String,
+    pub source: ExportSource,
+}
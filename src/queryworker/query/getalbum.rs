@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::{response::getalbum::FullAlbum, types::AlbumID};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GetAlbumParams {
+    // This field is not necessary, but is used to inform user whenever the query fails
+    pub name: String,
+    pub id: AlbumID,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum GetAlbumResponse {
+    Success(FullAlbum),
+    Failure {
+        id: AlbumID,
+        name: String,
+        msg: String,
+    },
+}
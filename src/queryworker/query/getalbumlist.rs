@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::{response::getalbumlist2::SimpleAlbum, types::AlbumListType};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GetAlbumListParams {
+    pub list_type: AlbumListType,
+    /// Maximum number of albums to return
+    pub size: usize,
+    /// Number of albums to skip. Used for fetching the list page by page.
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GetAlbumListResponse {
+    /// Parameters of the original request, so that the component can tell whether the result
+    /// replaces the current list or extends it
+    pub params: GetAlbumListParams,
+    pub albums: Result<Vec<SimpleAlbum>, String>,
+}
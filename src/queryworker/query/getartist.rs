@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::{response::getartist::FullArtist, types::ArtistID};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GetArtistParams {
+    // This field is not necessary, but is used to inform user whenever the query fails
+    pub name: String,
+    pub id: ArtistID,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum GetArtistResponse {
+    Success(FullArtist),
+    Failure {
+        id: ArtistID,
+        name: String,
+        msg: String,
+    },
+}
@@ -0,0 +1,19 @@
+use crate::osclient::types::ArtistID;
+
+/// Media to look for when the queue is about to run out in radio mode
+#[derive(Debug, Clone, PartialEq)]
+pub enum RadioParams {
+    /// Media similar to any of the artists
+    Similar {
+        artists: Vec<ArtistID>,
+        count: usize,
+    },
+    /// Most popular media of the artist
+    TopSongs { artist: String, count: usize },
+    Random {
+        count: usize,
+        genre: Option<String>,
+        from_year: Option<i32>,
+        to_year: Option<i32>,
+    },
+}
@@ -0,0 +1,13 @@
+use crate::{osclient::types::FolderID, playerworker::player::QueueLocation};
+
+/// Random media to add to the queue, limited to the given genre, years and music folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct RandomMixParams {
+    pub count: usize,
+    pub genre: Option<String>,
+    pub from_year: Option<i32>,
+    pub to_year: Option<i32>,
+    pub folder: Option<FolderID>,
+    /// Where the media is added once it is found
+    pub location: QueueLocation,
+}
@@ -0,0 +1,24 @@
+use crate::osclient::types::{MediaID, PlaylistID};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveTarget {
+    /// Create a new playlist with the given name
+    New(String),
+    /// Replace everything in an existing playlist
+    Existing { id: PlaylistID, name: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavePlaylistParams {
+    pub target: SaveTarget,
+    /// Every media that should be in the playlist, in order
+    pub items: Vec<MediaID>,
+}
+
+impl SavePlaylistParams {
+    pub fn name(&self) -> &str {
+        match &self.target {
+            SaveTarget::New(name) | SaveTarget::Existing { id: _, name } => name,
+        }
+    }
+}
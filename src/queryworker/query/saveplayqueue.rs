@@ -0,0 +1,11 @@
+use crate::osclient::types::MediaID;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavePlayQueueParams {
+    /// Every media in the queue, in order
+    pub ids: Vec<MediaID>,
+    /// Media that is currently being played
+    pub current: Option<MediaID>,
+    /// Position within the current media in milliseconds
+    pub position: Option<u64>,
+}
@@ -0,0 +1,10 @@
+use crate::osclient::response::getplaylist::Media;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrobbleParams {
+    pub media: Media,
+    /// Time at which the media started playing, in milliseconds since the UNIX epoch
+    pub time: u64,
+    /// False if this is a now-playing notification, true if the media has been played
+    pub submission: bool,
+}
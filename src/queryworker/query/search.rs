@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchParams {
+    pub query: String,
+    /// Maximum number of artists to return
+    pub artist_count: usize,
+    /// Maximum number of albums to return
+    pub album_count: usize,
+    /// Maximum number of songs to return
+    pub song_count: usize,
+}
@@ -1,6 +1,11 @@
+use std::{error::Error, process::Command};
+
+use color_eyre::Result;
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
+use crate::{config::Config, osclient::OSClient};
+
 #[derive(Debug, Clone, PartialEq, Eq, Display, Serialize, Deserialize)]
 pub enum Credential {
     // Use your password to log in
@@ -21,6 +26,52 @@ pub enum Credential {
 }
 
 impl Credential {
+    /// Reads the credentials set in the config, running the commands if necessary
+    pub fn from_config(config: &Config) -> Option<Credential> {
+        if let Some(creds) = &config.auth {
+            fn run_cmd(cmd: &String) -> Result<String> {
+                let exec = Command::new("sh").arg("-c").arg(cmd).output()?;
+                let stdout = String::from_utf8_lossy(&exec.stdout);
+                Ok(stdout.trim().to_string())
+            }
+            Some(Credential::Password {
+                url: run_cmd(&creds.url).ok()?,
+                secure: true,
+                username: run_cmd(&creds.username).ok()?,
+                password: run_cmd(&creds.password).ok()?,
+                legacy: config.config.use_legacy_auth,
+            })
+        } else {
+            config
+                .unsafe_auth
+                .clone()
+                .map(|unsafe_creds| Credential::Password {
+                    url: unsafe_creds.url,
+                    username: unsafe_creds.username,
+                    password: unsafe_creds.password,
+                    legacy: config.config.use_legacy_auth,
+                    secure: true,
+                })
+        }
+    }
+    /// Creates a client that uses these credentials without contacting the server
+    pub fn into_client(self) -> Result<OSClient, Box<dyn Error>> {
+        match self {
+            Credential::Password {
+                url,
+                secure,
+                username,
+                password,
+                legacy,
+            } => OSClient::use_password(url, username, password, legacy, secure),
+            Credential::APIKey {
+                url,
+                secure,
+                username,
+                apikey,
+            } => OSClient::use_apikey(url, username, apikey, secure),
+        }
+    }
     pub fn get_username(&self) -> String {
         match self {
             Credential::Password {
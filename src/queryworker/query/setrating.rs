@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::types::MediaID;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SetRatingResponse {
+    Success,
+    /// Contains the rating the media had before the request, so that it can be restored
+    Failure {
+        media: MediaID,
+        previous: u8,
+        msg: String,
+    },
+}
@@ -0,0 +1,39 @@
+use std::fmt::Display;
+
+use reqwest::{Error, StatusCode};
+
+use crate::scrobbler::listen::Listen;
+
+pub mod listen;
+pub mod listenbrainz;
+
+pub enum FailReason {
+    URLParsing,
+    ErrStatus(StatusCode),
+    Querying(Error),
+}
+
+impl Display for FailReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailReason::URLParsing => {
+                write!(
+                    f,
+                    "Failed to parse scrobbler URL; Please check your config."
+                )
+            }
+            FailReason::ErrStatus(status_code) => {
+                write!(f, "Scrobbler responded with error code {}", status_code)
+            }
+            FailReason::Querying(error) => write!(f, "Failed to send listen: {}", error),
+        }
+    }
+}
+
+/// External service that keeps track of the listening history
+pub trait Scrobbler {
+    /// Notify the service that the media has started playing
+    async fn now_playing(&self, listen: &Listen) -> Result<(), FailReason>;
+    /// Submit a media that has been played
+    async fn submit(&self, listen: &Listen) -> Result<(), FailReason>;
+}
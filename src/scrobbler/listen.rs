@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::osclient::response::getplaylist::Media;
+
+/// A single listen in the format ListenBrainz expects
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Listen {
+    /// Seconds since the UNIX epoch. Must be omitted for "playing_now" listens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listened_at: Option<u64>,
+    pub track_metadata: TrackMetadata,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackMetadata {
+    pub artist_name: String,
+    pub track_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_name: Option<String>,
+    pub additional_info: AdditionalInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdditionalInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recording_mbid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracknumber: Option<i32>,
+    pub media_player: String,
+    pub submission_client: String,
+    pub submission_client_version: String,
+}
+
+impl Listen {
+    pub fn new(media: &Media, listened_at: Option<u64>) -> Self {
+        Self {
+            listened_at,
+            track_metadata: TrackMetadata {
+                // MusicBrainz uses this name for unknown artists
+                artist_name: media
+                    .display_artist
+                    .clone()
+                    .or(media.artist.clone())
+                    .unwrap_or("[unknown]".to_string()),
+                track_name: media.title.clone(),
+                release_name: media.album.clone(),
+                additional_info: AdditionalInfo {
+                    recording_mbid: media.music_brainz_id.clone().filter(|id| !id.is_empty()),
+                    duration_ms: media.duration.filter(|d| *d > 0).map(|d| d as u64 * 1000),
+                    tracknumber: media.track,
+                    media_player: "ampterm".to_string(),
+                    submission_client: "ampterm".to_string(),
+                    submission_client_version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use reqwest::{Client, Url};
+use serde::Serialize;
+
+use crate::scrobbler::{listen::Listen, FailReason, Scrobbler};
+
+#[derive(Serialize)]
+struct SubmitListens<'a> {
+    listen_type: &'static str,
+    payload: [&'a Listen; 1],
+}
+
+/// Client for ListenBrainz, or any server that implements its submission API
+pub struct ListenBrainz {
+    client: Client,
+    url: String,
+    token: String,
+}
+
+impl ListenBrainz {
+    pub fn new(url: String, token: String) -> Self {
+        Self {
+            url,
+            token,
+            client: Client::builder()
+                .build()
+                .expect("Failed to create reqwest client for ListenBrainz."),
+        }
+    }
+
+    /// URL of the endpoint, relative to the configured base so that servers mounted under a prefix
+    /// are supported
+    fn endpoint(&self, path: &str) -> Option<Url> {
+        let mut base = Url::parse(&self.url).ok()?;
+        if !base.path().ends_with('/') {
+            base.set_path(&format!("{}/", base.path()));
+        }
+        base.join(path).ok()
+    }
+
+    /// Sends a listen using /1/submit-listens
+    async fn send(&self, listen_type: &'static str, listen: &Listen) -> Result<(), FailReason> {
+        let Some(url) = self.endpoint("1/submit-listens") else {
+            return Err(FailReason::URLParsing);
+        };
+        let res = self
+            .client
+            .post(url)
+            .header("Authorization", format!("Token {}", self.token))
+            .json(&SubmitListens {
+                listen_type,
+                payload: [listen],
+            })
+            .send()
+            .await
+            .map_err(FailReason::Querying)?;
+        match res.error_for_status() {
+            Ok(_) => Ok(()),
+            Err(e) => match e.status() {
+                Some(code) => Err(FailReason::ErrStatus(code)),
+                None => Err(FailReason::Querying(e)),
+            },
+        }
+    }
+}
+
+impl Scrobbler for ListenBrainz {
+    async fn now_playing(&self, listen: &Listen) -> Result<(), FailReason> {
+        let mut listen = listen.clone();
+        listen.listened_at = None;
+        self.send("playing_now", &listen).await
+    }
+
+    async fn submit(&self, listen: &Listen) -> Result<(), FailReason> {
+        self.send("single", listen).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::osclient::response::getplaylist::Media;
+
+    use super::*;
+    use reqwest::StatusCode;
+
+    /// Accepts a single request, and returns its head and the JSON body
+    async fn stand_in(listener: TcpListener, status: &str) -> (String, Value) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = vec![];
+        let mut chunk = [0; 1024];
+        let (head, body_start, len) = loop {
+            let n = stream.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&buf);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let head = text[..end].to_string();
+                let len = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length: ")
+                            .map(|v| v.parse::<usize>().unwrap())
+                    })
+                    .unwrap();
+                break (head, end + 4, len);
+            }
+        };
+        while buf.len() < body_start + len {
+            let n = stream.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        stream
+            .write_all(format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        (
+            head,
+            serde_json::from_slice(&buf[body_start..body_start + len]).unwrap(),
+        )
+    }
+
+    fn media() -> Media {
+        serde_json::from_value(json!({
+            "id": "1",
+            "isDir": false,
+            "title": "Title",
+            "album": "Album",
+            "artist": "Artist",
+            "duration": 120,
+            "musicBrainzId": "mbid"
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_submit() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(stand_in(listener, "200 OK"));
+        let client = ListenBrainz::new(url, "secret".to_string());
+        assert!(client
+            .submit(&Listen::new(&media(), Some(100)))
+            .await
+            .is_ok());
+        let (head, body) = server.await.unwrap();
+        assert!(head.starts_with("POST /1/submit-listens "));
+        assert!(head.to_lowercase().contains("authorization: token secret"));
+        assert_eq!(body["listen_type"], "single");
+        assert_eq!(body["payload"][0]["listened_at"], 100);
+        let metadata = &body["payload"][0]["track_metadata"];
+        assert_eq!(metadata["artist_name"], "Artist");
+        assert_eq!(metadata["track_name"], "Title");
+        assert_eq!(metadata["release_name"], "Album");
+        assert_eq!(metadata["additional_info"]["recording_mbid"], "mbid");
+        assert_eq!(metadata["additional_info"]["duration_ms"], 120000);
+    }
+
+    #[tokio::test]
+    async fn test_submit_with_prefix() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "http://{}/apis/listenbrainz",
+            listener.local_addr().unwrap()
+        );
+        let server = tokio::spawn(stand_in(listener, "200 OK"));
+        let client = ListenBrainz::new(url, "secret".to_string());
+        assert!(client
+            .submit(&Listen::new(&media(), Some(100)))
+            .await
+            .is_ok());
+        let (head, _) = server.await.unwrap();
+        assert!(head.starts_with("POST /apis/listenbrainz/1/submit-listens "));
+    }
+
+    #[test]
+    fn test_endpoint() {
+        for base in [
+            "https://host/apis/listenbrainz",
+            "https://host/apis/listenbrainz/",
+        ] {
+            let client = ListenBrainz::new(base.to_string(), String::new());
+            assert_eq!(
+                client.endpoint("1/submit-listens").unwrap().as_str(),
+                "https://host/apis/listenbrainz/1/submit-listens"
+            );
+        }
+        let client = ListenBrainz::new("https://api.listenbrainz.org".to_string(), String::new());
+        assert_eq!(
+            client.endpoint("1/submit-listens").unwrap().as_str(),
+            "https://api.listenbrainz.org/1/submit-listens"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_now_playing() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(stand_in(listener, "401 Unauthorized"));
+        let client = ListenBrainz::new(url, "wrong".to_string());
+        let res = client.now_playing(&Listen::new(&media(), Some(100))).await;
+        assert!(matches!(
+            res,
+            Err(FailReason::ErrStatus(StatusCode::UNAUTHORIZED))
+        ));
+        let (_, body) = server.await.unwrap();
+        assert_eq!(body["listen_type"], "playing_now");
+        assert!(body["payload"][0].get("listened_at").is_none());
+    }
+}